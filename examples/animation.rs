@@ -0,0 +1,35 @@
+use rui::*;
+
+fn main() {
+    state(
+        || (vec![0], false),
+        |s, cx| {
+            let (items, expanded) = cx[s].clone();
+            vstack((
+                hstack((
+                    button("add", move |cx| {
+                        let next = cx[s].0.len();
+                        cx[s].0.push(next)
+                    }),
+                    button("remove", move |cx| {
+                        cx[s].0.pop();
+                    }),
+                    button("toggle", move |cx| cx[s].1 = !cx[s].1),
+                )),
+                rectangle()
+                    .color(AZURE_HIGHLIGHT)
+                    .size(if expanded {
+                        [200.0, 100.0]
+                    } else {
+                        [100.0, 50.0]
+                    })
+                    .animation(Curve::SPRING),
+                cond(expanded, "expanded", "collapsed")
+                    .transition(Transition::Fade, Curve::EASE_IN_OUT),
+                list(items, |i| format!("item {}", i).padding(Auto))
+                    .transition(Transition::Slide([-100.0, 0.0].into()), Curve::EASE_OUT),
+            ))
+        },
+    )
+    .run()
+}
@@ -347,6 +347,9 @@ impl Calculator {
                                 cx[button_state].is_touched = false;
                                 cx[s].button_action(button_clone.clone());
                             }
+                            TouchState::Cancel => {
+                                cx[button_state].is_touched = false;
+                            }
                         })
                         .hover(move |cx, hovered| {
                             cx[button_state].is_hovered = hovered;
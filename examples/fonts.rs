@@ -0,0 +1,29 @@
+use rui::*;
+
+fn main() {
+    // `env` gives us a mutable context to register the font with.
+    env(|_: (), cx| {
+        let atkinson = cx
+            .register_font(include_bytes!(
+                "../assets/Atkinson-Hyperlegible-Regular-102.ttf"
+            ))
+            .unwrap();
+        vstack((
+            text("vger's built-in font"),
+            vstack((
+                text("Atkinson Hyperlegible"),
+                rich_text(vec![
+                    span("Spans pick the "),
+                    span("bold").bold(),
+                    span(" and "),
+                    span("italic").italic(),
+                    span(" faces of the family."),
+                ]),
+                text("Larger text").font_size(30),
+            ))
+            .font(atkinson),
+        ))
+        .padding(Auto)
+    })
+    .run()
+}
@@ -19,6 +19,27 @@ struct MyState {
     dragged: LocalOffset,
 }
 
+/// Scale and angle of the pinched square, and what they were when the
+/// current gesture began.
+#[derive(Clone, Copy)]
+struct Pinched {
+    scale: f32,
+    angle: f32,
+    start_scale: f32,
+    start_angle: f32,
+}
+
+impl Default for Pinched {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            angle: 0.0,
+            start_scale: 1.0,
+            start_angle: 0.0,
+        }
+    }
+}
+
 fn main() {
     hstack((
         circle()
@@ -47,6 +68,28 @@ fn main() {
                 })
                 .padding(Auto)
         }),
+        // pinch with two fingers or a trackpad to scale and turn
+        state(Pinched::default, move |s, cx| {
+            rectangle()
+                .corner_radius(5.0)
+                .color(GREEN_HIGHLIGHT.alpha(0.8))
+                .size([100.0, 100.0])
+                .scale(cx[s].scale)
+                .rotation(cx[s].angle)
+                .pinch(move |cx, scale, state| {
+                    cx[s].scale = cx[s].start_scale * scale;
+                    if state == GestureState::Ended {
+                        cx[s].start_scale = cx[s].scale;
+                    }
+                })
+                .rotate_gesture(move |cx, angle, state| {
+                    cx[s].angle = cx[s].start_angle + angle;
+                    if state == GestureState::Ended {
+                        cx[s].start_angle = cx[s].angle;
+                    }
+                })
+                .padding(Auto)
+        }),
     ))
     .run()
 }
@@ -0,0 +1,10 @@
+use rui::*;
+
+fn main() {
+    let ids = (0usize..100_000).collect();
+
+    vscroll(lazy_vlist(ids, RowHeight::Fixed(30.0), |id| {
+        format!("parameter {}", id).padding(Auto)
+    }))
+    .run()
+}
@@ -0,0 +1,33 @@
+use rui::*;
+
+fn main() {
+    state(
+        || 0,
+        |count, cx| {
+            let n = cx[count];
+            state(
+                || false,
+                move |shown, _| {
+                    vstack((
+                        format!("count: {}", n)
+                            .padding(Auto)
+                            .tooltip("Right-click for a menu")
+                            .context_menu(vec![
+                                MenuItem::new("Increment", move |cx| cx[count] += 1),
+                                MenuItem::new("Reset", move |cx| cx[count] = 0),
+                            ]),
+                        button("show popover", move |cx| cx[shown] = true)
+                            .padding(Auto)
+                            .popover(
+                                shown,
+                                "click outside or press Escape"
+                                    .padding(Auto)
+                                    .background(rectangle().color(CONTROL_BACKGROUND)),
+                            ),
+                    ))
+                },
+            )
+        },
+    )
+    .run()
+}
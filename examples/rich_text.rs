@@ -0,0 +1,31 @@
+use rui::*;
+
+fn main() {
+    state(
+        || String::from("tap a link"),
+        |status, cx| {
+            vstack((
+                rich_text(vec![
+                    span("Spans can be "),
+                    span("bold").bold(),
+                    span(", "),
+                    span("colored").color(RED_HIGHLIGHT),
+                    span(", "),
+                    span("bigger").size(28),
+                    span(", "),
+                    span("underlined").underline(),
+                    span(" or "),
+                    span("struck out").strikethrough(),
+                    span(". Read the "),
+                    span("docs").link("https://docs.rs/rui"),
+                    span("."),
+                ])
+                .max_width(300.0)
+                .on_link(move |cx, link| cx[status] = format!("tapped {}", link)),
+                text(&cx[status]),
+            ))
+            .padding(Auto)
+        },
+    )
+    .run()
+}
@@ -0,0 +1,10 @@
+use rui::*;
+
+fn main() {
+    let ids = (0usize..100).collect();
+
+    vscroll(list(ids, |id| {
+        format!("row {}", id).padding(Auto).size([200.0, 30.0])
+    }))
+    .run()
+}
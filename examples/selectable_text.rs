@@ -0,0 +1,10 @@
+use rui::*;
+
+fn main() {
+    vstack((
+        selectable_text("Drag to select, double click a word, then copy it."),
+        text("This one is larger.").font_size(24).selectable(),
+    ))
+    .padding(Auto)
+    .run()
+}
@@ -0,0 +1,36 @@
+use rui::*;
+
+fn main() {
+    state(
+        || false,
+        |show_sheet, _| {
+            state(
+                || false,
+                move |show_alert, _| {
+                    vstack((
+                        button("show sheet", move |cx| cx[show_sheet] = true).padding(Auto),
+                        button("show alert", move |cx| cx[show_alert] = true).padding(Auto),
+                    ))
+                    .sheet(
+                        show_sheet,
+                        vstack((
+                            "This is a sheet".padding(Auto),
+                            button("close", move |cx| cx[show_sheet] = false).padding(Auto),
+                        ))
+                        .background(rectangle().color(CONTROL_BACKGROUND)),
+                    )
+                    .alert(
+                        show_alert,
+                        "Delete everything?",
+                        "This can't be undone.",
+                        vec![
+                            MenuItem::new("Cancel", |_| ()),
+                            MenuItem::new("Delete", |_| println!("deleted")),
+                        ],
+                    )
+                },
+            )
+        },
+    )
+    .run()
+}
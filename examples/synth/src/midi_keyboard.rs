@@ -527,7 +527,7 @@ impl MidiKeyboard {
                                     }
                                 }
                             }
-                            GestureState::Ended => {
+                            GestureState::Ended | GestureState::Cancelled => {
                                 cx[s].mouse_dragging = false;
                                 cx[s].current_drag_key = None;
                                 cx[s].release_not_pressed_keys();
@@ -0,0 +1,26 @@
+use rui::*;
+
+fn label(name: &str) -> impl View {
+    text(name)
+        .font_size(12)
+        .max_width(60.0)
+        .text_align(TextAlign::Center)
+        .line_limit(1)
+        .truncation(Truncation::Middle)
+}
+
+fn main() {
+    vstack((
+        hstack((
+            label("Cutoff"),
+            label("Resonance"),
+            label("Envelope Amount"),
+        )),
+        text("Justified text spreads its words out so every line but the last fills the width.")
+            .max_width(240.0)
+            .text_align(TextAlign::Justified)
+            .line_spacing(4.0),
+    ))
+    .padding(Auto)
+    .run()
+}
@@ -0,0 +1,28 @@
+use rui::*;
+
+fn main() {
+    state(
+        || 0.5,
+        |value, _| {
+            state(
+                || false,
+                move |show_inspector, cx| {
+                    vstack((
+                        format!("value: {:.2}", cx[value]).padding(Auto),
+                        button("toggle inspector", move |cx| {
+                            cx[show_inspector] = !cx[show_inspector]
+                        })
+                        .padding(Auto),
+                        cond(
+                            cx[show_inspector],
+                            window("inspector", [300.0, 100.0], hslider(value).padding(Auto))
+                                .on_close(move |cx| cx[show_inspector] = false),
+                            EmptyView {},
+                        ),
+                    ))
+                },
+            )
+        },
+    )
+    .run()
+}
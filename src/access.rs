@@ -0,0 +1,257 @@
+use crate::*;
+use accesskit::{Action, ActionData, ActionHandler, ActionRequest};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// How far Increment and Decrement move a 0 to 1 value.
+pub const ACCESS_STEP: f32 = 0.1;
+
+/// Holds action requests from an assistive technology, such as a screen
+/// reader pressing a button, until the next update delivers them. Give it
+/// to the platform's AccessKit adapter.
+#[derive(Clone, Default)]
+pub struct AccessActionQueue {
+    requests: Arc<Mutex<Vec<ActionRequest>>>,
+}
+
+impl ActionHandler for AccessActionQueue {
+    fn do_action(&mut self, request: ActionRequest) {
+        self.requests.lock().unwrap().push(request)
+    }
+}
+
+impl Context {
+    /// Returns the handler an AccessKit adapter should send action
+    /// requests to.
+    pub fn access_action_handler(&self) -> AccessActionQueue {
+        self.access_queue.clone()
+    }
+
+    /// Builds the accessibility tree for the window at `root`, noting
+    /// which views handle actions on which nodes.
+    pub(crate) fn access_nodes(
+        &mut self,
+        view: &impl View,
+        root: &IdPath,
+        window_height: f32,
+    ) -> Vec<(accesskit::NodeId, accesskit::Node)> {
+        self.access_handlers.clear();
+        let mut nodes = vec![];
+        let mut path = root.clone();
+        view.access(&mut path, self, &mut nodes);
+        assert_eq!(path, *root);
+
+        let paths: HashMap<accesskit::NodeId, &IdPath> = self
+            .view_ids
+            .iter()
+            .map(|(path, id)| (id.access_id(), path))
+            .collect();
+        let max_depth = self.layout.keys().map(|path| path.len()).max().unwrap_or(0);
+        nodes
+            .into_iter()
+            .map(|(id, mut builder)| {
+                if let Some(bounds) = paths
+                    .get(&id)
+                    .and_then(|path| self.access_bounds(path, root, window_height, max_depth))
+                {
+                    builder.set_bounds(bounds);
+                }
+                (id, builder.build())
+            })
+            .collect()
+    }
+
+    /// Window space bounds of the view at `path`, from the layout map.
+    /// Modifiers don't store a rectangle, so this looks down through
+    /// first children for one. AccessKit wants y pointing down.
+    fn access_bounds(
+        &self,
+        path: &[u64],
+        root: &IdPath,
+        window_height: f32,
+        max_depth: usize,
+    ) -> Option<accesskit::Rect> {
+        let mut path = path.to_vec();
+        let rect = loop {
+            match self.layout.get(&path) {
+                Some(b) if !b.rect.is_empty() => break b.rect,
+                _ if path.len() < max_depth => path.push(0),
+                _ => return None,
+            }
+        };
+        let offset = (1..=path.len())
+            .filter_map(|n| self.layout.get(&path[..n]))
+            .fold(LocalOffset::zero(), |acc, b| acc + b.offset);
+        let root_offset = self.root_offsets.get(root).cloned().unwrap_or_default();
+        let rect = rect.translate(offset + root_offset);
+        Some(accesskit::Rect {
+            x0: rect.min_x() as f64,
+            y0: (window_height - rect.max_y()) as f64,
+            x1: rect.max_x() as f64,
+            y1: (window_height - rect.min_y()) as f64,
+        })
+    }
+
+    /// Delivers queued action requests to the views which handle them.
+    pub(crate) fn process_access_actions(&mut self, view: &impl View) {
+        let requests = std::mem::take(&mut *self.access_queue.requests.lock().unwrap());
+        for request in requests {
+            self.process(view, &Event::Access(request));
+        }
+    }
+
+    /// Makes the view at `path` handle `actions` on the accessibility
+    /// node `node`, which must already be in `nodes`.
+    pub(crate) fn handle_access_actions(
+        &mut self,
+        path: &IdPath,
+        node: accesskit::NodeId,
+        actions: &[Action],
+        nodes: &mut [(accesskit::NodeId, accesskit::NodeBuilder)],
+    ) {
+        let id = self.view_id(path);
+        let handlers = self.access_handlers.entry(node).or_default();
+        if !handlers.contains(&id) {
+            handlers.push(id);
+        }
+        if let Some((_, builder)) = nodes.iter_mut().rev().find(|(nid, _)| *nid == node) {
+            for action in actions {
+                builder.add_action(*action);
+            }
+        }
+    }
+
+    /// Is the view at `path` handling this request?
+    pub(crate) fn handles_access_action(&mut self, path: &IdPath, request: &ActionRequest) -> bool {
+        let id = self.view_id(path);
+        self.access_handlers
+            .get(&request.target)
+            .is_some_and(|ids| ids.contains(&id))
+    }
+}
+
+/// Lets assistive technologies read and set a 0 to 1 value, or step it
+/// by `ACCESS_STEP`.
+pub(crate) fn adjustable(view: impl View, value: impl Binding<f32>) -> impl View {
+    view.access_node(move |cx, node| {
+        node.set_numeric_value(*value.get(cx) as f64);
+        node.set_min_numeric_value(0.0);
+        node.set_max_numeric_value(1.0);
+        node.set_numeric_value_step(ACCESS_STEP as f64);
+    })
+    .access_action(Action::SetValue, move |cx, data| {
+        if let Some(ActionData::NumericValue(v)) = data {
+            *value.get_mut(cx) = (*v as f32).clamp(0.0, 1.0)
+        }
+    })
+    .access_action(Action::Increment, move |cx, _| {
+        value.with_mut(cx, |v| *v = (*v + ACCESS_STEP).min(1.0))
+    })
+    .access_action(Action::Decrement, move |cx, _| {
+        value.with_mut(cx, |v| *v = (*v - ACCESS_STEP).max(0.0))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesskit::Role;
+
+    #[test]
+    fn test_click_button() {
+        let ui = state(|| 0, |count, _| button("press", move |cx| cx[count] += 1));
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let count = h.root_state::<i32>();
+
+        let node = h.find_access_node(Role::Button).unwrap();
+        let nodes = h.access_nodes();
+        let (_, button) = nodes.iter().find(|(id, _)| *id == node).unwrap();
+        assert!(button.supports_action(Action::Default));
+
+        h.access_action(node, Action::Default, None);
+        assert_eq!(*h.get(count), 1);
+
+        // Other nodes, and other actions, do nothing.
+        h.access_action(node, Action::Increment, None);
+        h.access_action(accesskit::NodeId(1), Action::Default, None);
+        assert_eq!(*h.get(count), 1);
+    }
+
+    #[test]
+    fn test_toggle() {
+        let ui = state(|| false, |on, _| toggle(on));
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let on = h.root_state::<bool>();
+
+        let node = h.find_access_node(Role::Switch).unwrap();
+        h.access_action(node, Action::Default, None);
+        assert!(*h.get(on));
+
+        let nodes = h.access_nodes();
+        let (_, switch) = nodes.iter().find(|(id, _)| *id == node).unwrap();
+        assert_eq!(switch.toggled(), Some(accesskit::Toggled::True));
+    }
+
+    #[test]
+    fn test_adjust_slider() {
+        let ui = state(|| 0.5, |value, _| hslider(value));
+        let mut h = TestHarness::new(&ui, [100.0, 20.0]);
+        let value = h.root_state::<f32>();
+        let node = h.find_access_node(Role::Slider).unwrap();
+
+        h.access_action(node, Action::SetValue, Some(ActionData::NumericValue(0.25)));
+        assert_eq!(*h.get(value), 0.25);
+        h.access_action(node, Action::Increment, None);
+        assert!((*h.get(value) - 0.35).abs() < 1e-6);
+        h.access_action(node, Action::Decrement, None);
+        h.access_action(node, Action::Decrement, None);
+        h.access_action(node, Action::Decrement, None);
+        h.access_action(node, Action::Decrement, None);
+        assert_eq!(*h.get(value), 0.0);
+
+        // Out of range values are clamped.
+        h.access_action(node, Action::SetValue, Some(ActionData::NumericValue(3.0)));
+        assert_eq!(*h.get(value), 1.0);
+
+        let nodes = h.access_nodes();
+        let (_, slider) = nodes.iter().find(|(id, _)| *id == node).unwrap();
+        assert_eq!(slider.numeric_value(), Some(1.0));
+        assert_eq!(slider.min_numeric_value(), Some(0.0));
+        assert_eq!(slider.max_numeric_value(), Some(1.0));
+        assert_eq!(slider.numeric_value_step(), Some(ACCESS_STEP as f64));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let ui = vstack((
+            rectangle().size([20.0, 10.0]).access_label("top"),
+            rectangle().size([20.0, 10.0]).access_label("bottom"),
+        ));
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let nodes = h.access_nodes();
+        let bounds = |label| {
+            nodes
+                .iter()
+                .find(|(_, n)| n.name() == Some(label))
+                .and_then(|(_, n)| n.bounds())
+                .unwrap()
+        };
+        let (top, bottom) = (bounds("top"), bounds("bottom"));
+        assert_eq!(top.width(), 20.0);
+        assert_eq!(top.height(), 10.0);
+
+        // y points down.
+        assert_eq!(top.y1, bottom.y0);
+        assert!(top.y0 < bottom.y0);
+    }
+
+    #[test]
+    fn test_focus() {
+        let ui = "field".focusable();
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let node = h.find_access_node(Role::Label).unwrap();
+
+        h.access_action(node, Action::Focus, None);
+        assert_eq!(h.cx.focused(), Some(h.view_id(&[0])));
+    }
+}
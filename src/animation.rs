@@ -0,0 +1,539 @@
+use crate::*;
+use std::any::Any;
+
+/// How long animations with timed curves take, in seconds, unless told otherwise.
+pub const DEFAULT_ANIMATION_DURATION: f32 = 0.25;
+
+/// Time step for an animation frame. Like `anim`, we assume 60fps for now.
+pub(crate) const ANIMATION_FRAME: f32 = 1.0 / 60.0;
+
+/// Shape of an animation over time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Curve {
+    /// Constant speed.
+    Linear,
+
+    /// CSS-style cubic Bézier timing function from (0, 0) to (1, 1) with
+    /// control points (x1, y1) and (x2, y2).
+    CubicBezier(f32, f32, f32, f32),
+
+    /// Damped spring with unit mass. Springs ignore durations and run
+    /// until they come to rest.
+    Spring { stiffness: f32, damping: f32 },
+}
+
+impl Curve {
+    pub const EASE: Curve = Curve::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    pub const EASE_IN: Curve = Curve::CubicBezier(0.42, 0.0, 1.0, 1.0);
+    pub const EASE_OUT: Curve = Curve::CubicBezier(0.0, 0.0, 0.58, 1.0);
+    pub const EASE_IN_OUT: Curve = Curve::CubicBezier(0.42, 0.0, 0.58, 1.0);
+    pub const SPRING: Curve = Curve::Spring {
+        stiffness: 170.0,
+        damping: 26.0,
+    };
+
+    /// Maps time `t` in 0..1 to progress for timed curves.
+    fn ease(&self, t: f32) -> f32 {
+        match *self {
+            Curve::Linear | Curve::Spring { .. } => t,
+            Curve::CubicBezier(x1, y1, x2, y2) => {
+                // Coefficients of the polynomial for one coordinate.
+                let bezier = |a: f32, b: f32, s: f32| {
+                    let c = 3.0 * a;
+                    let b = 3.0 * (b - a) - c;
+                    let a = 1.0 - c - b;
+                    ((a * s + b) * s + c) * s
+                };
+
+                // Find the curve parameter where x == t by bisection. x is
+                // monotonic since x1 and x2 are in 0..1.
+                let (mut lo, mut hi) = (0.0, 1.0);
+                let mut s = t;
+                for _ in 0..32 {
+                    let x = bezier(x1, x2, s);
+                    if (x - t).abs() < 1e-6 {
+                        break;
+                    }
+                    if x < t {
+                        lo = s;
+                    } else {
+                        hi = s;
+                    }
+                    s = (lo + hi) / 2.0;
+                }
+                bezier(y1, y2, s)
+            }
+        }
+    }
+}
+
+/// Values which can be animated. They're interpolated component-wise.
+pub trait Animatable: Copy + PartialEq + 'static {
+    fn to_components(self) -> [f32; 4];
+    fn from_components(c: [f32; 4]) -> Self;
+}
+
+impl Animatable for f32 {
+    fn to_components(self) -> [f32; 4] {
+        [self, 0.0, 0.0, 0.0]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        c[0]
+    }
+}
+
+impl Animatable for LocalOffset {
+    fn to_components(self) -> [f32; 4] {
+        [self.x, self.y, 0.0, 0.0]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        [c[0], c[1]].into()
+    }
+}
+
+impl Animatable for LocalPoint {
+    fn to_components(self) -> [f32; 4] {
+        [self.x, self.y, 0.0, 0.0]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        [c[0], c[1]].into()
+    }
+}
+
+impl Animatable for LocalSize {
+    fn to_components(self) -> [f32; 4] {
+        [self.width, self.height, 0.0, 0.0]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        [c[0], c[1]].into()
+    }
+}
+
+impl Animatable for Color {
+    fn to_components(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        Color::new(c[0], c[1], c[2], c[3])
+    }
+}
+
+/// A value moving towards a target along a curve.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Tween<T> {
+    pub value: T,
+    pub to: T,
+    from: T,
+    velocity: [f32; 4],
+    elapsed: f32,
+    curve: Curve,
+    duration: f32,
+    running: bool,
+}
+
+impl<T: Animatable> Tween<T> {
+    /// A tween at rest at `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            to: value,
+            from: value,
+            velocity: [0.0; 4],
+            elapsed: 0.0,
+            curve: Curve::Linear,
+            duration: 0.0,
+            running: false,
+        }
+    }
+
+    /// Starts moving from the current value towards `to`. Springs keep
+    /// their velocity, so retargeting mid-flight is smooth.
+    pub fn animate_to(&mut self, to: T, curve: Curve, duration: f32) {
+        self.from = self.value;
+        self.to = to;
+        self.elapsed = 0.0;
+        self.curve = curve;
+        self.duration = duration;
+        self.running = self.value != to;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Advances the animation by `dt` seconds.
+    pub fn step(&mut self, dt: f32) {
+        if !self.running {
+            return;
+        }
+
+        let to = self.to.to_components();
+        match self.curve {
+            Curve::Spring { stiffness, damping } => {
+                const SUBSTEP: f32 = 1.0 / 240.0;
+                let mut x = self.value.to_components();
+                let mut remaining = dt;
+                while remaining > 0.0 {
+                    let h = remaining.min(SUBSTEP);
+                    for i in 0..4 {
+                        let a = -stiffness * (x[i] - to[i]) - damping * self.velocity[i];
+                        self.velocity[i] += a * h;
+                        x[i] += self.velocity[i] * h;
+                    }
+                    remaining -= h;
+                }
+
+                let at_rest =
+                    (0..4).all(|i| (x[i] - to[i]).abs() < 1e-3 && self.velocity[i].abs() < 1e-2);
+                if at_rest {
+                    self.finish();
+                } else {
+                    self.value = T::from_components(x);
+                }
+            }
+            curve => {
+                self.elapsed += dt;
+                let t = if self.duration > 0.0 {
+                    (self.elapsed / self.duration).min(1.0)
+                } else {
+                    1.0
+                };
+                // Allow for rounding in the accumulated frame times.
+                if t >= 1.0 - 1e-4 {
+                    self.finish();
+                } else {
+                    let p = curve.ease(t);
+                    let from = self.from.to_components();
+                    let mut x = [0.0; 4];
+                    for i in 0..4 {
+                        x[i] = from[i] + (to[i] - from[i]) * p;
+                    }
+                    self.value = T::from_components(x);
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        self.value = self.to;
+        self.velocity = [0.0; 4];
+        self.running = false;
+    }
+}
+
+/// Identifies an animated value, so a new animation of the same value
+/// can replace a running one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct AnimationKey {
+    /// The view state holding the value.
+    state: Option<ViewId>,
+    /// Where the value is, relative to its state. Stable for as long as
+    /// the state lives, unlike the value's address once it's dropped.
+    offset: usize,
+}
+
+/// An animation started with `Context::animate`.
+pub(crate) trait ScheduledAnimation {
+    fn key(&self) -> AnimationKey;
+
+    /// Moves the value along. Returns false once it has arrived, or
+    /// the state holding it is gone.
+    fn step(&mut self, cx: &mut Context, dt: f32) -> bool;
+}
+
+struct BindingAnimation<T, B> {
+    binding: B,
+    key: AnimationKey,
+    tween: Tween<T>,
+}
+
+impl<T, B> ScheduledAnimation for BindingAnimation<T, B>
+where
+    T: Animatable,
+    B: Binding<T>,
+{
+    fn key(&self) -> AnimationKey {
+        self.key
+    }
+
+    fn step(&mut self, cx: &mut Context, dt: f32) -> bool {
+        if let Some(id) = self.key.state {
+            if !cx.state_map.contains_key(&id) {
+                return false;
+            }
+        }
+        self.tween.step(dt);
+        *self.binding.get_mut(cx) = self.tween.value;
+        self.tween.is_running()
+    }
+}
+
+impl Context {
+    /// Moves the value of `binding` towards `target` along `curve` on each
+    /// animation frame. Replaces any animation already running on the same
+    /// value. The animation stops if the binding's state goes away.
+    pub fn animate<T: Animatable, B: Binding<T>>(
+        &mut self,
+        binding: B,
+        target: T,
+        curve: Curve,
+        duration: f32,
+    ) {
+        let value = binding.get(self);
+        let state = binding.state_id();
+        let base = match state {
+            Some(id) => self.state_map[&id].state.as_ref() as *const dyn Any as *const u8 as usize,
+            None => 0,
+        };
+        let key = AnimationKey {
+            state,
+            offset: (value as *const T as usize).wrapping_sub(base),
+        };
+        let mut tween = Tween::new(*value);
+        tween.animate_to(target, curve, duration);
+
+        self.animations.retain(|a| a.key() != key);
+        if tween.is_running() {
+            self.animations.push(Box::new(BindingAnimation {
+                binding,
+                key,
+                tween,
+            }));
+        }
+    }
+
+    /// Is anything animating? When this is false, the event loop can
+    /// sleep until the next event.
+    pub fn is_animating(&self) -> bool {
+        self.animating || self.frame_requested || !self.animations.is_empty()
+    }
+
+    /// Asks for another animation frame, for views waiting on the clock,
+    /// without marking anything as changed.
+    pub(crate) fn request_frame(&mut self) {
+        self.frame_requested = true;
+    }
+
+    /// Advances animations started with `animate`.
+    pub(crate) fn step_animations(&mut self, dt: f32) {
+        let mut animations = std::mem::take(&mut self.animations);
+        animations.retain_mut(|a| a.step(self, dt));
+
+        // Keep animations started while stepping, unless they replaced ours.
+        let started = std::mem::take(&mut self.animations);
+        animations.retain(|a| started.iter().all(|b| b.key() != a.key()));
+        animations.extend(started);
+        self.animations = animations;
+    }
+
+    /// Sends `Event::Anim` to the view tree and advances animations by
+    /// the time since the last frame, noting whether anything changed.
+    pub(crate) fn run_animations(&mut self, view: &impl View) {
+        // We're woken for input as well as for frames, so go by the clock.
+        let now = self.now();
+        self.frame_time = match self.last_frame {
+            Some(last) => now.duration_since(last).as_secs_f32(),
+            None => ANIMATION_FRAME,
+        };
+
+        let was_dirty = self.dirty;
+        self.dirty = false;
+        self.frame_requested = false;
+
+        let mut path = vec![0];
+        let mut actions = vec![];
+        view.process(&Event::Anim, &mut path, self, &mut actions);
+        assert!(path.len() == 1);
+        self.step_animations(self.frame_time);
+
+        self.animating = self.dirty;
+        self.dirty |= was_dirty;
+        self.last_frame = self.is_animating().then_some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run<T: Animatable>(tween: &mut Tween<T>, seconds: f32) {
+        let frames = (seconds / ANIMATION_FRAME).round() as usize;
+        for _ in 0..frames {
+            tween.step(ANIMATION_FRAME);
+        }
+    }
+
+    #[test]
+    fn test_bezier_ease() {
+        for curve in [Curve::EASE, Curve::EASE_IN_OUT, Curve::Linear] {
+            assert!(curve.ease(0.0).abs() < 1e-4);
+            assert!((curve.ease(1.0) - 1.0).abs() < 1e-4);
+        }
+        // Symmetric curves pass through the middle.
+        assert!((Curve::EASE_IN_OUT.ease(0.5) - 0.5).abs() < 1e-3);
+        // Ease in starts slowly.
+        assert!(Curve::EASE_IN.ease(0.25) < 0.25);
+        assert!(Curve::EASE_OUT.ease(0.25) > 0.25);
+    }
+
+    #[test]
+    fn test_timed_tween() {
+        let mut tween = Tween::new(0.0f32);
+        tween.animate_to(10.0, Curve::Linear, 1.0);
+        assert!(tween.is_running());
+
+        run(&mut tween, 0.5);
+        assert!((tween.value - 5.0).abs() < 1e-3, "{}", tween.value);
+
+        run(&mut tween, 0.5);
+        assert_eq!(tween.value, 10.0);
+        assert!(!tween.is_running());
+    }
+
+    #[test]
+    fn test_spring_settles() {
+        let mut tween = Tween::new(LocalOffset::zero());
+        tween.animate_to([100.0, -50.0].into(), Curve::SPRING, 0.0);
+
+        run(&mut tween, 0.1);
+        assert!(tween.value.x > 0.0 && tween.value.x < 100.0);
+
+        run(&mut tween, 3.0);
+        assert!(!tween.is_running());
+        assert_eq!(tween.value, LocalOffset::new(100.0, -50.0));
+    }
+
+    #[test]
+    fn test_retarget_from_current_value() {
+        let mut tween = Tween::new(0.0f32);
+        tween.animate_to(10.0, Curve::Linear, 1.0);
+        run(&mut tween, 0.5);
+        tween.animate_to(0.0, Curve::Linear, 1.0);
+        run(&mut tween, 0.5);
+        assert!((tween.value - 2.5).abs() < 1e-3, "{}", tween.value);
+    }
+
+    #[test]
+    fn test_animate_binding() {
+        let mut cx = Context::new();
+        let id = ViewId::default();
+        cx.init_state(id, &|| 0.0f32);
+        let value = StateHandle::<f32>::new(id);
+
+        cx.animate(value, 1.0, Curve::Linear, 0.5);
+        assert!(cx.is_animating());
+
+        for _ in 0..15 {
+            cx.step_animations(ANIMATION_FRAME);
+        }
+        assert!((cx[value] - 0.5).abs() < 1e-3, "{}", cx[value]);
+
+        // Retargeting replaces the running animation.
+        cx.animate(value, 0.0, Curve::Linear, 0.5);
+        assert_eq!(cx.animations.len(), 1);
+
+        for _ in 0..30 {
+            cx.step_animations(ANIMATION_FRAME);
+        }
+        assert_eq!(cx[value], 0.0);
+        assert!(!cx.is_animating());
+    }
+
+    #[test]
+    fn test_animate_color_and_point() {
+        let mut cx = Context::new();
+        let (a, b) = (ViewId { id: 1 }, ViewId { id: 2 });
+        cx.init_state(a, &|| BLACK);
+        cx.init_state(b, &LocalPoint::zero);
+        let color = StateHandle::<Color>::new(a);
+        let point = StateHandle::<LocalPoint>::new(b);
+
+        cx.animate(color, WHITE, Curve::EASE, 0.25);
+        cx.animate(point, [10.0, 20.0].into(), Curve::SPRING, 0.0);
+        assert_eq!(cx.animations.len(), 2);
+
+        for _ in 0..300 {
+            cx.step_animations(ANIMATION_FRAME);
+        }
+        assert_eq!(cx[color], WHITE);
+        assert_eq!(cx[point], LocalPoint::new(10.0, 20.0));
+        assert!(!cx.is_animating());
+    }
+
+    #[test]
+    fn test_animation_stops_when_state_is_gone() {
+        let ui = state(
+            || true,
+            |show, cx| {
+                cond(
+                    cx[show],
+                    state(
+                        || 0.0f32,
+                        move |v, _| {
+                            rectangle().tap(move |cx| {
+                                cx.animate(v, 1.0, Curve::Linear, 1.0);
+                                cx[show] = false;
+                            })
+                        },
+                    ),
+                    rectangle(),
+                )
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+
+        h.tap([50.0, 50.0]);
+        assert_eq!(h.cx.animations.len(), 1);
+        h.gc();
+        h.tick();
+        assert!(h.cx.animations.is_empty());
+    }
+
+    #[test]
+    fn test_animations_follow_the_clock() {
+        let ui = state(
+            || 0.0f32,
+            |x, _| rectangle().tap(move |cx| cx.animate(x, 100.0, Curve::Linear, 1.0)),
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let x = h.root_state::<f32>();
+
+        h.tap([50.0, 50.0]);
+        h.tick();
+        let first = *h.get(x);
+
+        // Input wakes the event loop without time passing.
+        for _ in 0..10 {
+            h.cx.run_animations(&ui);
+        }
+        assert_eq!(*h.get(x), first);
+
+        h.advance_time(std::time::Duration::from_millis(500));
+        assert!((*h.get(x) - first - 50.0).abs() < 1e-2, "{}", h.get(x));
+    }
+
+    #[test]
+    fn test_animating_fields_of_one_state() {
+        let mut cx = Context::new();
+        let id = ViewId::default();
+        cx.init_state(id, &LocalPoint::zero);
+        let p = StateHandle::<LocalPoint>::new(id);
+        make_lens!(X, LocalPoint, f32, x);
+        make_lens!(Y, LocalPoint, f32, y);
+
+        cx.animate(bind(p, X {}), 10.0, Curve::Linear, 0.5);
+        cx.animate(bind(p, Y {}), 20.0, Curve::Linear, 0.5);
+        assert_eq!(cx.animations.len(), 2);
+
+        for _ in 0..30 {
+            cx.step_animations(ANIMATION_FRAME);
+        }
+        assert_eq!(cx[p], LocalPoint::new(10.0, 20.0));
+    }
+
+    #[test]
+    fn test_color_components() {
+        let c = Color::new(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(Color::from_components(c.to_components()), c);
+    }
+}
@@ -5,6 +5,12 @@ pub trait Binding<S>: Clone + Copy + 'static {
     fn get<'a>(&self, cx: &'a Context) -> &'a S;
     fn get_mut<'a>(&self, cx: &'a mut Context) -> &'a mut S;
 
+    /// The view state holding the value, if any. Animations of the value
+    /// stop once that state is gone.
+    fn state_id(&self) -> Option<ViewId> {
+        None
+    }
+
     fn with<T>(&self, cx: &Context, f: impl FnOnce(&S) -> T) -> T {
         f(self.get(cx))
     }
@@ -84,6 +90,9 @@ where
     fn get_mut<'a>(&self, cx: &'a mut Context) -> &'a mut S {
         self.lens.focus_mut(self.binding.get_mut(cx))
     }
+    fn state_id(&self) -> Option<ViewId> {
+        self.binding.state_id()
+    }
 }
 
 #[cfg(test)]
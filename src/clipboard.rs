@@ -0,0 +1,83 @@
+use crate::*;
+use std::sync::{Arc, Mutex};
+
+/// A place to cut and copy text to, and paste it from.
+///
+/// The winit backend installs the system clipboard. Embedders can
+/// supply their own with `Context::set_clipboard`.
+pub trait Clipboard {
+    /// Returns the text on the clipboard, if any.
+    fn get_text(&self) -> Option<String>;
+
+    /// Replaces the contents of the clipboard.
+    fn set_text(&self, text: &str);
+}
+
+/// A clipboard which only lives in memory.
+///
+/// This is the default when no system clipboard is installed,
+/// which makes it handy for headless tests.
+#[derive(Default)]
+pub struct MemoryClipboard {
+    text: Mutex<Option<String>>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_text(&self) -> Option<String> {
+        self.text.lock().unwrap().clone()
+    }
+
+    fn set_text(&self, text: &str) {
+        *self.text.lock().unwrap() = Some(text.to_string());
+    }
+}
+
+/// Environment value holding the current clipboard.
+#[derive(Clone)]
+pub(crate) struct ClipboardEnv(pub Arc<dyn Clipboard>);
+
+impl Default for ClipboardEnv {
+    fn default() -> Self {
+        Self(Arc::new(MemoryClipboard::new()))
+    }
+}
+
+impl Context {
+    /// Returns the clipboard used for cut, copy and paste.
+    pub fn clipboard(&mut self) -> Arc<dyn Clipboard> {
+        self.init_env(&ClipboardEnv::default).0
+    }
+
+    /// Replaces the clipboard used for cut, copy and paste.
+    pub fn set_clipboard(&mut self, clipboard: impl Clipboard + 'static) {
+        self.set_env(&ClipboardEnv(Arc::new(clipboard)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_clipboard_is_shared() {
+        let mut cx = Context::new();
+        assert_eq!(cx.clipboard().get_text(), None);
+        cx.clipboard().set_text("hello");
+        assert_eq!(cx.clipboard().get_text(), Some("hello".to_string()));
+    }
+
+    #[test]
+    fn test_set_clipboard() {
+        let mut cx = Context::new();
+        let clipboard = MemoryClipboard::new();
+        clipboard.set_text("preset");
+        cx.set_clipboard(clipboard);
+        assert_eq!(cx.clipboard().get_text(), Some("preset".to_string()));
+    }
+}
@@ -57,6 +57,14 @@ pub const CONTROL_BACKGROUND: Color = Color {
     b: 0.148,
     a: 1.0,
 };
+pub const FOCUS_RING_COLOR: Color = AZURE_HIGHLIGHT;
+pub const LINK_COLOR: Color = AZURE_HIGHLIGHT;
+pub const BACKDROP_COLOR: Color = Color {
+    r: 0.0,
+    g: 0.0,
+    b: 0.0,
+    a: 0.5,
+};
 pub const MEDIUM_GRAY: Color = Color {
     r: 0.533,
     g: 0.533,
@@ -6,6 +6,7 @@ use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 use std::ops;
 use std::sync::Arc;
+use web_time::Instant;
 
 pub type LocalSpace = vger::defs::LocalSpace;
 pub type WorldSpace = vger::defs::WorldSpace;
@@ -17,6 +18,7 @@ pub type WorldRect = Rect<f32, WorldSpace>;
 pub type WorldPoint = Point2D<f32, WorldSpace>;
 pub type LocalToWorld = Transform2D<f32, LocalSpace, WorldSpace>;
 pub type WorldToLocal = Transform2D<f32, WorldSpace, LocalSpace>;
+pub type LocalToLocal = Transform2D<f32, LocalSpace, LocalSpace>;
 
 #[derive(Clone, Eq, PartialEq)]
 pub struct CommandInfo {
@@ -52,10 +54,13 @@ pub struct RenderInfo<'a, 'window> {
 /// shouldn't have to interact with it directly.
 pub struct Context {
     /// Layout information for all views.
-    layout: HashMap<IdPath, LayoutBox>,
+    pub(crate) layout: HashMap<IdPath, LayoutBox>,
 
     /// Allocated ViewIds.
-    view_ids: HashMap<IdPath, ViewId>,
+    pub(crate) view_ids: HashMap<IdPath, ViewId>,
+
+    /// The path each ViewId was allocated for.
+    id_paths: HashMap<ViewId, IdPath>,
 
     /// Next allocated id.
     next_id: ViewId,
@@ -63,6 +68,10 @@ pub struct Context {
     /// Which views each touch (or mouse pointer) is interacting with.
     pub(crate) touches: [ViewId; 16],
 
+    /// Platform ids of the touches using each slot of `touches`.
+    #[cfg(any(feature = "winit", test))]
+    pub(crate) touch_ids: [Option<u64>; 16],
+
     /// Points at which touches (or click-drags) started.
     pub(crate) starts: [LocalPoint; 16],
 
@@ -75,12 +84,31 @@ pub struct Context {
     /// Mouse button state.
     pub mouse_buttons: MouseButtons,
 
+    /// Views under the pointer in each window, keyed by root path.
+    pub(crate) hovered: HashMap<IdPath, Vec<ViewId>>,
+
     /// Keyboard modifiers state.
     pub key_mods: KeyboardModifiers,
 
     /// The view that has the keyboard focus.
     pub(crate) focused_id: Option<ViewId>,
 
+    /// Focusable views and their `focus_order`, collected while building
+    /// the focus chain.
+    pub(crate) focus_chain: Option<Vec<(ViewId, i32)>>,
+
+    /// `focus_order` of the views being visited.
+    pub(crate) focus_order: i32,
+
+    /// Action requests from assistive technologies, waiting to be processed.
+    pub(crate) access_queue: AccessActionQueue,
+
+    /// Views handling actions on each accessibility node.
+    pub(crate) access_handlers: HashMap<accesskit::NodeId, Vec<ViewId>>,
+
+    /// Fonts loaded with `register_font`.
+    pub(crate) fonts: Fonts,
+
     /// The current title of the window
     pub window_title: Arc<str>,
 
@@ -111,8 +139,8 @@ pub struct Context {
     /// Previous window size.
     window_size: Size2D<f32, WorldSpace>,
 
-    /// Offset for events at the root level.
-    root_offset: LocalOffset,
+    /// Offsets for events at the root of each window, keyed by root path.
+    pub(crate) root_offsets: HashMap<IdPath, LocalOffset>,
 
     /// Render the dirty rectangle for debugging?
     render_dirty: bool,
@@ -122,6 +150,48 @@ pub struct Context {
 
     /// Value of grab_cursor before processing event.
     pub(crate) prev_grab_cursor: bool,
+
+    /// Where the focused text input's cursor was drawn, so the
+    /// IME can place its candidate window nearby.
+    pub(crate) ime_cursor_area: Option<WorldRect>,
+
+    /// Opacity applied to everything drawn, set by fading views.
+    pub(crate) opacity: f32,
+
+    /// Animations started with `animate`.
+    pub(crate) animations: Vec<Box<dyn ScheduledAnimation>>,
+
+    /// Did the last animation frame change anything?
+    pub(crate) animating: bool,
+
+    /// When the last animation frame ran, if anything was animating.
+    pub(crate) last_frame: Option<Instant>,
+
+    /// Seconds between the last two animation frames.
+    pub(crate) frame_time: f32,
+
+    /// Did a view ask for another frame without changing anything?
+    pub(crate) frame_requested: bool,
+
+    /// Wheel delta not yet taken by a scroll view. Nested scroll views
+    /// offer it to their content before taking what they can.
+    pub(crate) scroll_remaining: LocalOffset,
+
+    /// Windows declared with `window`, keyed by the window view's id.
+    pub(crate) windows: HashMap<ViewId, WindowInfo>,
+
+    /// Set by a focused view which used the key being processed, so
+    /// Tab doesn't also move the focus.
+    pub(crate) key_handled: bool,
+
+    /// Popovers, menus and tooltips shown during the last render.
+    pub(crate) overlays: Vec<Overlay>,
+
+    /// Root of the window being laid out, whose overlays are being shown.
+    pub(crate) overlay_root: IdPath,
+
+    /// Time to report instead of the system clock, for tests.
+    pub(crate) fake_now: Option<Instant>,
 }
 
 impl Default for Context {
@@ -135,14 +205,23 @@ impl Context {
         Self {
             layout: HashMap::new(),
             view_ids: HashMap::new(),
+            id_paths: HashMap::new(),
             next_id: ViewId { id: 0 },
             touches: [ViewId::default(); 16],
+            #[cfg(any(feature = "winit", test))]
+            touch_ids: [None; 16],
             starts: [LocalPoint::zero(); 16],
             previous_position: [LocalPoint::zero(); 16],
             mouse_button: None,
             mouse_buttons: Default::default(),
+            hovered: HashMap::new(),
             key_mods: Default::default(),
             focused_id: None,
+            focus_chain: None,
+            focus_order: 0,
+            access_queue: AccessActionQueue::default(),
+            access_handlers: HashMap::new(),
+            fonts: Fonts::default(),
             window_title: "rui".into(),
             fullscreen: false,
             state_map: HashMap::new(),
@@ -153,10 +232,23 @@ impl Context {
             deps: HashMap::new(),
             id_stack: vec![],
             window_size: Size2D::default(),
-            root_offset: LocalOffset::zero(),
+            root_offsets: HashMap::new(),
             render_dirty: false,
             grab_cursor: false,
             prev_grab_cursor: false,
+            ime_cursor_area: None,
+            opacity: 1.0,
+            animations: vec![],
+            animating: false,
+            last_frame: None,
+            frame_time: ANIMATION_FRAME,
+            frame_requested: false,
+            scroll_remaining: LocalOffset::zero(),
+            windows: HashMap::new(),
+            key_handled: false,
+            overlays: vec![],
+            overlay_root: vec![0],
+            fake_now: None,
         }
     }
 
@@ -176,13 +268,14 @@ impl Context {
 
         let mut path = vec![0];
 
+        self.process_access_actions(view);
+
         // Run any animations.
-        let mut actions = vec![];
-        view.process(&Event::Anim, &mut path, self, &mut actions);
-        assert!(path.len() == 1);
+        self.run_animations(view);
 
         if self.dirty {
-            // Clean up state and layout.
+            // Clean up state and layout. Windows are declared again during gc.
+            self.windows.clear();
             let mut keep = vec![];
             view.gc(&mut path, self, &mut keep);
             assert!(path.len() == 1);
@@ -193,11 +286,16 @@ impl Context {
             new_layout.retain(|k, _| keep_set.contains(&self.view_id(k)));
             self.layout = new_layout;
 
-            // Get a new accesskit tree.
-            let mut nodes = vec![];
+            // XXX: we're doing layout both here and in rendering.
+            self.layout_root(
+                view,
+                &path,
+                [window_size.width, window_size.height].into(),
+                &mut |str, size, max_width| vger.text_bounds(str, size, max_width),
+            );
 
-            view.access(&mut path, self, &mut nodes);
-            assert_eq!(path.len(), 1);
+            // Get a new accesskit tree, with bounds from the new layout.
+            let nodes = self.access_nodes(view, &path, window_size.height);
 
             if nodes != *access_nodes {
                 log::debug!("access nodes:");
@@ -214,17 +312,6 @@ impl Context {
                 // log::debug!("access nodes unchanged");
             }
 
-            // XXX: we're doing layout both here and in rendering.
-            view.layout(
-                &mut path,
-                &mut LayoutArgs {
-                    sz: [window_size.width, window_size.height].into(),
-                    cx: self,
-                    text_bounds: &mut |str, size, max_width| vger.text_bounds(str, size, max_width),
-                },
-            );
-            assert_eq!(path.len(), 1);
-
             // Get dirty rectangles.
             view.dirty(&mut path, LocalToWorld::identity(), self);
 
@@ -244,6 +331,19 @@ impl Context {
         vger: &mut Vger,
         window_size: Size2D<f32, WorldSpace>,
         scale: f32,
+    ) {
+        self.render_root(render_info, view, &vec![0], vger, window_size, scale)
+    }
+
+    /// Redraw the window whose view is at `root`.
+    pub(crate) fn render_root(
+        &mut self,
+        render_info: RenderInfo,
+        view: &impl View,
+        root: &IdPath,
+        vger: &mut Vger,
+        window_size: Size2D<f32, WorldSpace>,
+        scale: f32,
     ) {
         let surface = render_info.surface;
         let device = render_info.device;
@@ -260,26 +360,26 @@ impl Context {
 
         vger.begin(window_size.width, window_size.height, scale);
 
-        let mut path = vec![0];
+        let mut path = root.clone();
         // Disable dirtying the state during layout and rendering
         // to avoid constantly re-rendering if some state is saved.
         self.enable_dirty = false;
         let local_window_size = window_size.cast_unit::<LocalSpace>();
-        let sz = view.layout(
-            &mut path,
-            &mut LayoutArgs {
-                sz: local_window_size,
-                cx: self,
-                text_bounds: &mut |str, size, max_width| vger.text_bounds(str, size, max_width),
-            },
+        let sz = self.layout_root(
+            view,
+            root,
+            local_window_size,
+            &mut |str, size, max_width| vger.text_bounds(str, size, max_width),
         );
-        assert!(path.len() == 1);
 
         // Center the root view in the window.
-        self.root_offset = ((local_window_size - sz) / 2.0).into();
+        let root_offset: LocalOffset = ((local_window_size - sz) / 2.0).into();
+        self.root_offsets.insert(root.clone(), root_offset);
 
-        vger.translate(self.root_offset);
+        vger.translate(root_offset);
+        self.ime_cursor_area = None;
         view.draw(&mut path, &mut DrawArgs { cx: self, vger });
+        self.draw_overlays(root, vger, local_window_size, root_offset);
         self.enable_dirty = true;
 
         if self.render_dirty {
@@ -321,20 +421,120 @@ impl Context {
 
     /// Process a UI event.
     pub fn process(&mut self, view: &impl View, event: &Event) {
-        let mut actions = vec![];
-        let mut path = vec![0];
-        view.process(
-            &event.offset(-self.root_offset),
-            &mut path,
-            self,
-            &mut actions,
-        );
+        self.process_root(view, &vec![0], event)
+    }
+
+    /// Process a UI event in the window whose view is at `root`.
+    pub(crate) fn process_root(&mut self, view: &impl View, root: &IdPath, event: &Event) {
+        match event {
+            Event::PointerMove { position } => self.update_hover(view, root, Some(*position)),
+            Event::MouseLeftWindow => self.update_hover(view, root, None),
+            _ => (),
+        }
 
-        for action in actions {
-            if !action.is::<()>() {
-                log::debug!("unhandled action: {:?}", (*action).type_id());
+        self.key_handled = false;
+        if !self.process_overlays(root, event) {
+            let mut actions = vec![];
+            let mut path = root.clone();
+            let root_offset = self.root_offsets.get(root).cloned().unwrap_or_default();
+            view.process(&event.offset(-root_offset), &mut path, self, &mut actions);
+
+            for action in actions {
+                if !action.is::<()>() {
+                    log::debug!("unhandled action: {:?}", (*action).type_id());
+                }
             }
         }
+
+        // Tab moves the focus, unless the focused view used it.
+        if let (Event::Key(Key::Tab), false) = (event, self.key_handled) {
+            let forward = !self.key_mods.shift;
+            self.move_focus(view, root, forward);
+        }
+    }
+
+    /// The current time. Gestures time themselves with this rather than
+    /// the system clock, so tests can fake it.
+    pub fn now(&self) -> Instant {
+        self.fake_now.unwrap_or_else(Instant::now)
+    }
+
+    /// Gives the keyboard focus to a view.
+    pub fn focus(&mut self, id: ViewId) {
+        self.focused_id = Some(id);
+        self.set_dirty();
+    }
+
+    /// The view with the keyboard focus, if any.
+    pub fn focused(&self) -> Option<ViewId> {
+        self.focused_id
+    }
+
+    /// Views in the window which can take the focus, in tab order. Only
+    /// the topmost modal overlay is included if there is one.
+    pub(crate) fn focus_chain(&mut self, view: &impl View, root: &IdPath) -> Vec<ViewId> {
+        let modal = self
+            .overlays
+            .iter()
+            .rev()
+            .find(|o| o.root == *root && o.modal)
+            .cloned();
+
+        match modal {
+            Some(modal) => self.focusable_views(&modal.view, &modal.path),
+            None => self.focusable_views(view, root),
+        }
+    }
+
+    /// Views within `view` which can take the focus, in tab order: by
+    /// `focus_order`, then in the order they appear in the accessibility
+    /// tree.
+    pub(crate) fn focusable_views(&mut self, view: &impl View, path: &IdPath) -> Vec<ViewId> {
+        self.focus_chain = Some(vec![]);
+        self.focus_order = 0;
+        let mut nodes = vec![];
+        view.access(&mut path.clone(), self, &mut nodes);
+
+        let mut chain = self.focus_chain.take().unwrap_or_default();
+        chain.sort_by_key(|(_, order)| *order);
+        chain.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Moves the focus to the next view in the focus chain, or the
+    /// previous one, wrapping around.
+    pub(crate) fn move_focus(&mut self, view: &impl View, root: &IdPath, forward: bool) {
+        let chain = self.focus_chain(view, root);
+        let n = chain.len();
+        if n == 0 {
+            return;
+        }
+        let current = self
+            .focused_id
+            .and_then(|id| chain.iter().position(|c| *c == id));
+        let next = match current {
+            Some(i) if forward => (i + 1) % n,
+            Some(i) => (i + n - 1) % n,
+            None if forward => 0,
+            None => n - 1,
+        };
+        self.focus(chain[next]);
+    }
+
+    /// Returns where the focused text input drew its cursor during the last
+    /// render, in window coordinates.
+    pub fn ime_cursor_area(&self) -> Option<WorldRect> {
+        self.ime_cursor_area
+    }
+
+    /// Opacity of the view being drawn. Canvases should apply it
+    /// to their colors with `apply_opacity`.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Scales the alpha of a color by the current opacity.
+    pub fn apply_opacity(&self, color: Color) -> Color {
+        color.alpha(color.a * self.opacity)
     }
 
     /// Get menu commands.
@@ -349,12 +549,18 @@ impl Context {
             None => {
                 let id = self.next_id;
                 self.view_ids.insert(path.clone(), id);
+                self.id_paths.insert(id, path.clone());
                 self.next_id.id += 1;
                 id
             }
         }
     }
 
+    /// The path of the view with `id`.
+    pub(crate) fn id_path(&self, id: ViewId) -> Option<&IdPath> {
+        self.id_paths.get(&id)
+    }
+
     pub(crate) fn get_layout(&self, path: &IdPath) -> LayoutBox {
         match self.layout.get(path) {
             Some(b) => *b,
@@ -387,7 +593,7 @@ impl Context {
     }
 
     /// Returns all layout entries sorted by path, for use in snapshot tests.
-    #[cfg(test)]
+    #[cfg(any(test, feature = "testing"))]
     pub(crate) fn layout_entries(&self) -> Vec<(IdPath, LayoutBox)> {
         let mut entries: Vec<_> = self.layout.iter().map(|(k, v)| (k.clone(), *v)).collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -447,6 +653,19 @@ impl Context {
         old_value
     }
 
+    /// Puts back the value `set_env` replaced, or removes the value if
+    /// there wasn't one, so it doesn't leak out of a subtree.
+    pub(crate) fn restore_env<S: Clone + 'static>(&mut self, old: Option<S>) {
+        match old {
+            Some(value) => {
+                self.set_env(&value);
+            }
+            None => {
+                self.env.remove(&TypeId::of::<S>());
+            }
+        }
+    }
+
     pub fn get<S>(&self, id: StateHandle<S>) -> &S
     where
         S: 'static,
@@ -27,9 +27,53 @@ pub enum Event {
         position: LocalPoint,
     },
 
+    /// The system took a touch away, for example for one of its own
+    /// gestures. Views should let go of it without acting.
+    TouchCancel {
+        /// Identifies a touch so we can track it.
+        id: usize,
+        position: LocalPoint,
+    },
+
     /// Called when the mouse gets outside the window
     MouseLeftWindow,
 
+    /// Mouse moved, whether or not a button is down.
+    PointerMove { position: LocalPoint },
+
+    /// The pointer moved onto these views: the one under it, and those
+    /// containing that one.
+    PointerEnter(Vec<ViewId>),
+
+    /// The pointer moved off these views.
+    PointerLeave(Vec<ViewId>),
+
+    /// Mouse wheel or trackpad scroll.
+    Scroll {
+        /// How far the content should move.
+        delta: LocalOffset,
+        /// Where the pointer is.
+        position: LocalPoint,
+    },
+
+    /// Trackpad pinch.
+    Magnify {
+        /// Change in magnification, positive when zooming in.
+        delta: f32,
+        /// Where the pointer is.
+        position: LocalPoint,
+        state: GestureState,
+    },
+
+    /// Trackpad rotation.
+    Rotate {
+        /// Change in angle, in radians counter-clockwise.
+        delta: f32,
+        /// Where the pointer is.
+        position: LocalPoint,
+        state: GestureState,
+    },
+
     /// Menu command.
     Command(Arc<str>),
 
@@ -39,8 +83,23 @@ pub enum Event {
     /// Key released.
     KeyReleased(Key),
 
+    /// Input method (IME) composition in progress. An empty `text`
+    /// means composition was cancelled.
+    ImePreedit {
+        text: String,
+        /// Byte range of the cursor within `text`, if it should be shown.
+        cursor: Option<(usize, usize)>,
+    },
+
+    /// Input method (IME) composition finished with text to insert.
+    ImeCommit(String),
+
     /// Animation.
     Anim,
+
+    /// An assistive technology, such as a screen reader, asks for an
+    /// action on an accessibility node.
+    Access(accesskit::ActionRequest),
 }
 
 impl Event {
@@ -50,6 +109,34 @@ impl Event {
             Event::TouchBegin { position, .. } => *position += offset,
             Event::TouchMove { position, .. } => *position += offset,
             Event::TouchEnd { position, .. } => *position += offset,
+            Event::TouchCancel { position, .. } => *position += offset,
+            Event::Scroll { position, .. } => *position += offset,
+            Event::PointerMove { position } => *position += offset,
+            Event::Magnify { position, .. } | Event::Rotate { position, .. } => *position += offset,
+            _ => (),
+        }
+        event
+    }
+
+    /// Maps positions and movements through a transform.
+    pub fn transform(&self, xform: &LocalToLocal) -> Event {
+        let mut event = self.clone();
+        match &mut event {
+            Event::TouchBegin { position, .. }
+            | Event::TouchEnd { position, .. }
+            | Event::TouchCancel { position, .. }
+            | Event::PointerMove { position }
+            | Event::Magnify { position, .. }
+            | Event::Rotate { position, .. } => *position = xform.transform_point(*position),
+            Event::TouchMove {
+                position, delta, ..
+            }
+            | Event::Scroll {
+                position, delta, ..
+            } => {
+                *position = xform.transform_point(*position);
+                *delta = xform.transform_vector(*delta);
+            }
             _ => (),
         }
         event
@@ -77,6 +164,14 @@ pub struct KeyboardModifiers {
     pub command: bool,
 }
 
+impl KeyboardModifiers {
+    /// Is the modifier used for shortcuts like undo held down?
+    /// That's Command on macOS and Control elsewhere, so we accept either.
+    pub fn shortcut(&self) -> bool {
+        self.command || self.control
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ElementState {
     Pressed,
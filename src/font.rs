@@ -0,0 +1,559 @@
+use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use ttf_parser::{name_id, GlyphId, OutlineBuilder};
+
+/// A font family registered with `Context::register_font`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FontId(usize);
+
+impl FontId {
+    /// vger's built-in font.
+    pub const DEFAULT: FontId = FontId(0);
+}
+
+/// How heavy a font is, from 100 (thin) to 900 (black).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: FontWeight = FontWeight(100);
+    pub const LIGHT: FontWeight = FontWeight(300);
+    pub const NORMAL: FontWeight = FontWeight(400);
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    pub const SEMIBOLD: FontWeight = FontWeight(600);
+    pub const BOLD: FontWeight = FontWeight(700);
+    pub const BLACK: FontWeight = FontWeight(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FontStyle {
+    #[default]
+    Normal,
+    Italic,
+}
+
+/// A family, weight and style, as chosen by the `font`, `font_weight` and
+/// `font_style` modifiers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Font {
+    pub id: FontId,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+}
+
+/// Why font data couldn't be registered.
+#[derive(Debug)]
+pub enum FontError {
+    Parse(ttf_parser::FaceParsingError),
+}
+
+impl std::fmt::Display for FontError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontError::Parse(err) => write!(f, "couldn't parse font: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+/// A TTF or OTF face.
+#[derive(Clone)]
+pub(crate) struct FontFace {
+    /// Parsed when registered. Fonts are kept for the life of the app,
+    /// so the data is leaked for the face to borrow.
+    face: ttf_parser::Face<'static>,
+    /// Hash of the data, to spot the same font being registered again.
+    hash: u64,
+    weight: FontWeight,
+    style: FontStyle,
+}
+
+/// A glyph placed by `FontFace::layout`.
+struct PlacedGlyph {
+    /// None for a newline, which has nothing to draw.
+    glyph: Option<GlyphId>,
+    /// Where the glyph's baseline starts.
+    origin: LocalPoint,
+    line: usize,
+}
+
+impl FontFace {
+    fn scale(&self, size: u32) -> f32 {
+        size as f32 / self.face.units_per_em() as f32
+    }
+
+    /// Places a glyph for each char, breaking lines between words to fit
+    /// `max_width`. Like vger, the top of the first line is at zero, and
+    /// y is up.
+    fn layout(&self, text: &str, size: u32, max_width: Option<f32>) -> Vec<PlacedGlyph> {
+        let face = &self.face;
+        let scale = self.scale(size);
+        let line_height = (face.ascender() - face.descender() + face.line_gap()) as f32 * scale;
+        let advance = |c: char| {
+            let glyph = face.glyph_index(c).unwrap_or_default();
+            let width = face.glyph_hor_advance(glyph).unwrap_or(0) as f32 * scale;
+            (glyph, width)
+        };
+
+        let mut glyphs = vec![];
+        let mut x = 0.0;
+        let mut y = -face.ascender() as f32 * scale;
+        let mut line = 0;
+        for (i, text_line) in text.split('\n').enumerate() {
+            if i > 0 {
+                // The newline ends the line before.
+                glyphs.push(PlacedGlyph {
+                    glyph: None,
+                    origin: LocalPoint::new(x, y),
+                    line,
+                });
+                x = 0.0;
+                y -= line_height;
+                line += 1;
+            }
+            for word in text_line.split_inclusive(' ') {
+                if let Some(max_width) = max_width {
+                    let width: f32 = word.trim_end().chars().map(|c| advance(c).1).sum();
+                    if x > 0.0 && x + width > max_width {
+                        x = 0.0;
+                        y -= line_height;
+                        line += 1;
+                    }
+                }
+                for c in word.chars() {
+                    let (glyph, width) = advance(c);
+                    glyphs.push(PlacedGlyph {
+                        glyph: Some(glyph),
+                        origin: LocalPoint::new(x, y),
+                        line,
+                    });
+                    x += width;
+                }
+            }
+        }
+        glyphs
+    }
+
+    /// The glyph's bounding box, or an empty rect at its origin if it
+    /// has none.
+    fn glyph_rect(&self, placed: &PlacedGlyph, scale: f32) -> LocalRect {
+        placed
+            .glyph
+            .and_then(|glyph| self.face.glyph_bounding_box(glyph))
+            .map(|b| {
+                LocalRect::new(
+                    placed.origin + LocalOffset::new(b.x_min as f32, b.y_min as f32) * scale,
+                    LocalSize::new(b.width() as f32, b.height() as f32) * scale,
+                )
+            })
+            .unwrap_or(LocalRect::new(placed.origin, LocalSize::zero()))
+    }
+
+    /// The union of the glyphs' bounding boxes, like vger's `text_bounds`.
+    pub(crate) fn text_bounds(&self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect {
+        let scale = self.scale(size);
+        self.layout(text, size, max_width)
+            .iter()
+            .filter(|placed| placed.glyph.is_some())
+            .map(|placed| self.glyph_rect(placed, scale))
+            .filter(|rect| !rect.is_empty())
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or_default()
+    }
+
+    /// A rect for each char, like vger's `glyph_positions`.
+    pub(crate) fn glyph_positions(
+        &self,
+        text: &str,
+        size: u32,
+        max_width: Option<f32>,
+    ) -> Vec<LocalRect> {
+        let scale = self.scale(size);
+        self.layout(text, size, max_width)
+            .iter()
+            .map(|placed| self.glyph_rect(placed, scale))
+            .collect()
+    }
+
+    /// The glyphs on each line and their bounds, like vger's `line_metrics`.
+    pub(crate) fn line_metrics(
+        &self,
+        text: &str,
+        size: u32,
+        max_width: Option<f32>,
+    ) -> Vec<LineMetrics> {
+        let scale = self.scale(size);
+        let mut lines: Vec<LineMetrics> = vec![];
+        for (i, placed) in self.layout(text, size, max_width).iter().enumerate() {
+            let rect = self.glyph_rect(placed, scale);
+            match lines.get_mut(placed.line) {
+                Some(line) => {
+                    line.glyph_end = i + 1;
+                    line.bounds = line.bounds.union(&rect);
+                }
+                None => lines.push(LineMetrics {
+                    glyph_start: i,
+                    glyph_end: i + 1,
+                    bounds: rect,
+                }),
+            }
+        }
+        lines
+    }
+
+    /// Fills the glyphs' outlines.
+    pub(crate) fn draw(
+        &self,
+        vger: &mut Vger,
+        text: &str,
+        size: u32,
+        color: Color,
+        max_width: Option<f32>,
+    ) {
+        let face = &self.face;
+        let scale = self.scale(size);
+        let paint = vger.color_paint(color);
+        for placed in self.layout(text, size, max_width) {
+            let (glyph, origin) = match placed.glyph {
+                Some(glyph) => (glyph, placed.origin),
+                None => continue,
+            };
+            let mut outline = VgerOutline {
+                vger,
+                origin,
+                scale,
+                start: LocalPoint::zero(),
+                pen: LocalPoint::zero(),
+            };
+            if face.outline_glyph(glyph, &mut outline).is_some() {
+                vger.fill(paint);
+            }
+        }
+    }
+}
+
+/// Sends glyph outlines to vger's path fill, which only takes quadratic
+/// curves.
+struct VgerOutline<'a> {
+    vger: &'a mut Vger,
+    origin: LocalPoint,
+    scale: f32,
+    start: LocalPoint,
+    pen: LocalPoint,
+}
+
+impl VgerOutline<'_> {
+    fn point(&self, x: f32, y: f32) -> LocalPoint {
+        self.origin + LocalOffset::new(x, y) * self.scale
+    }
+
+    fn quad(&mut self, b: LocalPoint, c: LocalPoint) {
+        self.vger.quad_to(b, c);
+        self.pen = c;
+    }
+
+    fn line(&mut self, p: LocalPoint) {
+        self.quad(self.pen.lerp(p, 0.5), p);
+    }
+}
+
+impl OutlineBuilder for VgerOutline<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.point(x, y);
+        self.pen = self.start;
+        self.vger.move_to(self.start);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.line(self.point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.quad(self.point(x1, y1), self.point(x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // Split the cubic in half and fit a quadratic to each half.
+        let (p0, p1, p2, p3) = (
+            self.pen,
+            self.point(x1, y1),
+            self.point(x2, y2),
+            self.point(x, y),
+        );
+        let (a, b, c) = (p0.lerp(p1, 0.5), p1.lerp(p2, 0.5), p2.lerp(p3, 0.5));
+        let (d, e) = (a.lerp(b, 0.5), b.lerp(c, 0.5));
+        let mid = d.lerp(e, 0.5);
+        let fit = |p0: LocalPoint, p1: LocalPoint, p2: LocalPoint, p3: LocalPoint| {
+            ((p1.to_vector() + p2.to_vector()) * 0.75 - (p0.to_vector() + p3.to_vector()) * 0.25)
+                .to_point()
+        };
+        self.quad(fit(p0, a, d, mid), mid);
+        self.quad(fit(mid, e, c, p3), p3);
+    }
+
+    fn close(&mut self) {
+        if self.pen != self.start {
+            self.line(self.start);
+        }
+    }
+}
+
+/// Registered font families. The first is vger's built-in font, which
+/// has no faces here.
+#[derive(Clone)]
+pub(crate) struct Fonts {
+    families: Vec<(String, Vec<FontFace>)>,
+}
+
+impl Default for Fonts {
+    fn default() -> Self {
+        Self {
+            families: vec![(String::new(), vec![])],
+        }
+    }
+}
+
+impl Fonts {
+    /// The face closest to `font`, or None for the built-in font.
+    pub(crate) fn face(&self, font: Font) -> Option<&FontFace> {
+        let (_, faces) = self.families.get(font.id.0)?;
+        faces.iter().min_by_key(|face| {
+            (
+                face.style != font.style,
+                (face.weight.0 as i32 - font.weight.0 as i32).abs(),
+            )
+        })
+    }
+}
+
+impl Context {
+    /// Loads a TTF or OTF font, returning its family. Faces with the same
+    /// family name share an id, and are picked between by weight and style.
+    /// Registering the same data again just returns the id.
+    pub fn register_font(&mut self, data: &[u8]) -> Result<FontId, FontError> {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        let hash = hasher.finish();
+        for (id, (_, faces)) in self.fonts.families.iter().enumerate() {
+            if faces.iter().any(|face| face.hash == hash) {
+                return Ok(FontId(id));
+            }
+        }
+
+        // Check the data before keeping a copy.
+        ttf_parser::Face::parse(data, 0).map_err(FontError::Parse)?;
+        let data: &'static [u8] = Box::leak(data.into());
+        let face = ttf_parser::Face::parse(data, 0).unwrap();
+        let name = |id| {
+            face.names()
+                .into_iter()
+                .find(|name| name.name_id == id)
+                .and_then(|name| name.to_string())
+        };
+        let family = name(name_id::TYPOGRAPHIC_FAMILY)
+            .or_else(|| name(name_id::FAMILY))
+            .unwrap_or_default();
+        let font_face = FontFace {
+            weight: FontWeight(face.weight().to_number()),
+            style: match face.style() {
+                ttf_parser::Style::Normal => FontStyle::Normal,
+                _ => FontStyle::Italic,
+            },
+            face,
+            hash,
+        };
+
+        let families = &mut self.fonts.families;
+        let id = match families.iter().skip(1).position(|(f, _)| *f == family) {
+            Some(i) => i + 1,
+            None => {
+                families.push((family, vec![]));
+                families.len() - 1
+            }
+        };
+        families[id].1.push(font_face);
+        self.set_dirty();
+        Ok(FontId(id))
+    }
+
+    /// The font chosen for views here, from the environment.
+    pub fn font(&mut self) -> Font {
+        Font {
+            id: self.init_env(&FontId::default),
+            weight: self.init_env(&FontWeight::default),
+            style: self.init_env(&FontStyle::default),
+        }
+    }
+}
+
+impl LayoutArgs<'_> {
+    /// Measures text in the font chosen in the environment.
+    pub fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect {
+        let font = self.cx.font();
+        self.font_text_bounds(font, text, size, max_width)
+    }
+
+    pub(crate) fn font_text_bounds(
+        &mut self,
+        font: Font,
+        text: &str,
+        size: u32,
+        max_width: Option<f32>,
+    ) -> LocalRect {
+        match self.cx.fonts.face(font) {
+            Some(face) => face.text_bounds(text, size, max_width),
+            None => (self.text_bounds)(text, size, max_width),
+        }
+    }
+}
+
+impl DrawArgs<'_> {
+    /// Measures text in the font chosen in the environment.
+    pub fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect {
+        let font = self.cx.font();
+        self.font_text_bounds(font, text, size, max_width)
+    }
+
+    /// Draws text in the font chosen in the environment.
+    pub fn text(&mut self, text: &str, size: u32, color: Color, max_width: Option<f32>) {
+        let font = self.cx.font();
+        self.font_text(font, text, size, color, max_width)
+    }
+
+    pub(crate) fn font_text_bounds(
+        &mut self,
+        font: Font,
+        text: &str,
+        size: u32,
+        max_width: Option<f32>,
+    ) -> LocalRect {
+        match self.cx.fonts.face(font) {
+            Some(face) => face.text_bounds(text, size, max_width),
+            None => self.vger.text_bounds(text, size, max_width),
+        }
+    }
+
+    pub(crate) fn font_glyph_positions(
+        &mut self,
+        font: Font,
+        text: &str,
+        size: u32,
+        max_width: Option<f32>,
+    ) -> Vec<LocalRect> {
+        match self.cx.fonts.face(font) {
+            Some(face) => face.glyph_positions(text, size, max_width),
+            None => self.vger.glyph_positions(text, size, max_width),
+        }
+    }
+
+    pub(crate) fn font_line_metrics(
+        &mut self,
+        font: Font,
+        text: &str,
+        size: u32,
+        max_width: Option<f32>,
+    ) -> Vec<LineMetrics> {
+        match self.cx.fonts.face(font) {
+            Some(face) => face.line_metrics(text, size, max_width),
+            None => self.vger.line_metrics(text, size, max_width),
+        }
+    }
+
+    pub(crate) fn font_text(
+        &mut self,
+        font: Font,
+        text: &str,
+        size: u32,
+        color: Color,
+        max_width: Option<f32>,
+    ) {
+        match self.cx.fonts.face(font) {
+            Some(face) => face.draw(self.vger, text, size, color, max_width),
+            None => self.vger.text(text, size, color, max_width),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FONT: &[u8] = include_bytes!("../assets/Atkinson-Hyperlegible-Regular-102.ttf");
+
+    #[test]
+    fn test_register_font() {
+        let mut cx = Context::new();
+        let id = cx.register_font(FONT).unwrap();
+        assert_ne!(id, FontId::DEFAULT);
+        assert_eq!(cx.register_font(FONT).unwrap(), id);
+        assert!(cx.register_font(&[1, 2, 3]).is_err());
+
+        // Any weight or style falls back to the one face.
+        let font = Font {
+            id,
+            weight: FontWeight::BOLD,
+            style: FontStyle::Italic,
+        };
+        assert!(cx.fonts.face(font).is_some());
+        assert!(cx.fonts.face(Font::default()).is_none());
+    }
+
+    #[test]
+    fn test_font_measures_text() {
+        let mut cx = Context::new();
+        let id = cx.register_font(FONT).unwrap();
+        let mut args = LayoutArgs {
+            sz: [400.0, 100.0].into(),
+            cx: &mut cx,
+            text_bounds: &mut |t, s, w| ApproxTextMeasure.text_bounds(t, s, w),
+        };
+
+        // The built-in font is measured by the `text_bounds` closure.
+        assert_eq!(args.text_bounds("Hello world", 18, None).width(), 99.0);
+
+        args.cx.set_env(&id);
+        let bounds = args.text_bounds("Hello world", 18, None);
+        assert!(bounds.width() > 50.0 && bounds.width() < 150.0);
+        assert!(bounds.height() > 10.0 && bounds.height() < 20.0);
+
+        // Wrapping makes two lines.
+        let wrapped = args.text_bounds("Hello world", 18, Some(60.0));
+        assert!(wrapped.width() < bounds.width());
+        assert!(wrapped.height() > 2.0 * bounds.height());
+    }
+
+    #[test]
+    fn test_font_glyph_positions() {
+        let mut cx = Context::new();
+        let id = cx.register_font(FONT).unwrap();
+        let font = Font {
+            id,
+            ..Font::default()
+        };
+        let face = cx.fonts.face(font).unwrap();
+
+        // A rect for every char, newlines included, as text editing
+        // expects.
+        let rects = face.glyph_positions("ab\ncd", 18, None);
+        assert_eq!(rects.len(), 5);
+        assert!(rects[1].min_x() > rects[0].min_x());
+        assert!(rects[3].max_y() < rects[0].min_y());
+
+        let lines = face.line_metrics("ab\ncd", 18, None);
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[0].glyph_start, lines[0].glyph_end), (0, 3));
+        assert_eq!((lines[1].glyph_start, lines[1].glyph_end), (3, 5));
+
+        // The first line's top is at zero, like vger's.
+        let bounds = face.text_bounds("Hb", 18, None);
+        assert!(bounds.max_y() <= 0.0 && bounds.max_y() > -5.0);
+    }
+}
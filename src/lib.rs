@@ -47,6 +47,23 @@ pub use align::*;
 mod region;
 pub use region::*;
 
+mod clipboard;
+pub use clipboard::*;
+
+mod animation;
+pub use animation::*;
+
+mod overlay;
+pub(crate) use overlay::*;
+
+mod access;
+pub use access::*;
+
+mod font;
+pub use font::*;
+
+mod pointer;
+
 #[cfg(feature = "winit")]
 mod winit_event_loop;
 
@@ -64,9 +81,12 @@ mod snapshot_tests;
 #[cfg(test)]
 mod screenshot_tests;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
 mod test_harness;
 
+#[cfg(any(test, feature = "testing"))]
+pub use test_harness::*;
+
 #[cfg(test)]
 mod tests {
 
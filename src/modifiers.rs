@@ -4,11 +4,62 @@ use std::marker::PhantomData;
 
 /// Modifiers common to all views.
 pub trait Modifiers: View + Sized {
+    /// Calls a function when an assistive technology asks for `action`
+    /// on the view's accessibility node.
+    fn access_action<F: Fn(&mut Context, Option<&accesskit::ActionData>) + Clone + 'static>(
+        self,
+        action: accesskit::Action,
+        f: F,
+    ) -> AccessActionView<Self, F> {
+        AccessActionView::new(self, action, f)
+    }
+
+    /// Describes what the view does, beyond its label, for assistive
+    /// technologies.
+    fn access_description(self, description: &str) -> AccessNodeView<Self, AccessDescription> {
+        AccessNodeView::new(self, AccessDescription(description.into()))
+    }
+
+    /// Hides the view from assistive technologies.
+    fn access_hidden(self) -> AccessNodeView<Self, AccessHidden> {
+        AccessNodeView::new(self, AccessHidden)
+    }
+
+    /// Sets the label assistive technologies read for the view.
+    fn access_label(self, label: &str) -> AccessNodeView<Self, AccessLabel> {
+        AccessNodeView::new(self, AccessLabel(label.into()))
+    }
+
+    /// Calls a function to fill in the view's accessibility node.
+    fn access_node<F: Fn(&Context, &mut accesskit::NodeBuilder) + Clone + 'static>(
+        self,
+        f: F,
+    ) -> AccessNodeView<Self, AccessNodeFunc<F>> {
+        AccessNodeView::new(self, AccessNodeFunc { f })
+    }
+
     /// Calls a closure after rendering with context and delta time.
     fn anim<F: Fn(&mut Context, f32) + 'static + Clone>(self, func: F) -> AnimView<Self, F> {
         AnimView::new(self, func)
     }
 
+    /// Animates changes to the view's size and position along a curve.
+    fn animation(self, curve: Curve) -> AnimatedLayout<Self> {
+        AnimatedLayout::new(self, curve)
+    }
+
+    /// Shows an alert over the whole window while `shown` is true. Any of
+    /// the buttons closes it; with none there's an "OK" button.
+    fn alert<B: Binding<bool>>(
+        self,
+        shown: B,
+        title: &str,
+        message: &str,
+        buttons: Vec<MenuItem>,
+    ) -> Sheet<Self, B> {
+        Sheet::new(self, shown, alert_view(shown, title, message, buttons))
+    }
+
     /// Puts a view behind another. The background view inherits the size of the view.
     fn background<BG: View + Clone>(self, background: BG) -> Background<Self, BG> {
         Background::new(self, background)
@@ -29,6 +80,20 @@ pub trait Modifiers: View + Sized {
         CommandGroup::new(self, cmds)
     }
 
+    /// Shows a menu where the view is right-clicked.
+    fn context_menu(self, items: Vec<MenuItem>) -> ContextMenu<Self> {
+        ContextMenu::new(self, items)
+    }
+
+    /// Calls a function in response to a double tap. A tap beneath is held
+    /// back until it's clear it isn't the first of two.
+    fn double_tap<A: 'static, F: Fn(&mut Context) -> A + Clone + 'static>(
+        self,
+        f: F,
+    ) -> DoubleTap<Self, TapAdapter<F>> {
+        DoubleTap::new(self, TapAdapter { f })
+    }
+
     /// Calls a function in response to a drag.
     fn drag<
         F: Fn(&mut Context, LocalOffset, GestureState, Option<MouseButton>) + Clone + 'static,
@@ -95,6 +160,33 @@ pub trait Modifiers: View + Sized {
         Flex::new(self)
     }
 
+    /// Lets the view take the keyboard focus, by clicking or with Tab.
+    /// Keys only reach it while it's focused.
+    fn focusable(self) -> Focusable<Self> {
+        Focusable::new(self)
+    }
+
+    /// Sets where focusable views within come in the Tab order. Lower
+    /// orders come first; ties, at 0 by default, follow the view tree.
+    fn focus_order(self, order: i32) -> FocusOrder<Self> {
+        FocusOrder::new(self, order)
+    }
+
+    /// Sets the font family for text in the view.
+    fn font(self, id: FontId) -> SetenvView<Self, FontId> {
+        self.env(id)
+    }
+
+    /// Picks italic or upright faces of the font for text in the view.
+    fn font_style(self, style: FontStyle) -> SetenvView<Self, FontStyle> {
+        self.env(style)
+    }
+
+    /// Picks the face of the font closest to `weight` for text in the view.
+    fn font_weight(self, weight: FontWeight) -> SetenvView<Self, FontWeight> {
+        self.env(weight)
+    }
+
     /// Make the window full screen.
     fn fullscreen(self) -> FullscreenView<Self> {
         FullscreenView::new(self)
@@ -109,6 +201,12 @@ pub trait Modifiers: View + Sized {
         Geom::new(self, f)
     }
 
+    /// Responds to input method (IME) events, `Event::ImePreedit`
+    /// and `Event::ImeCommit`.
+    fn ime<F: Fn(&mut Context, &Event) + Clone + 'static>(self, f: F) -> ImeView<Self, F> {
+        ImeView::new(self, f)
+    }
+
     /// Responds to keyboard events
     fn key<F: Fn(&mut Context, Key) + Clone + 'static>(self, f: F) -> KeyView<Self, F> {
         KeyView::new_pressed(self, f)
@@ -119,6 +217,25 @@ pub trait Modifiers: View + Sized {
         KeyView::new_released(self, f)
     }
 
+    /// Calls a function once the view has been touched and held for
+    /// `duration`. Views beneath don't get the touch's release.
+    fn long_press<A: 'static, F: Fn(&mut Context) -> A + Clone + 'static>(
+        self,
+        duration: std::time::Duration,
+        f: F,
+    ) -> LongPress<Self, TapAdapter<F>> {
+        LongPress::new(self, duration, TapAdapter { f })
+    }
+
+    /// Like `pinch`, but passes the magnification, which starts at zero,
+    /// as trackpads report it.
+    fn magnify<A: 'static, F: Fn(&mut Context, f32, GestureState) -> A + Clone + 'static>(
+        self,
+        f: F,
+    ) -> Pinch<Self, MagnifyFunc<F>> {
+        Pinch::new(self, MagnifyFunc { f })
+    }
+
     /// Applies an offset to the view in local space.
     fn offset<Off: Into<LocalOffset>>(self, offset: Off) -> Offset<Self> {
         Offset::new(self, offset.into())
@@ -129,6 +246,50 @@ pub trait Modifiers: View + Sized {
         Padding::new(self, param.into())
     }
 
+    /// Calls a function as two fingers on the view, or a trackpad, pinch.
+    /// Passes the scale, which starts at one.
+    fn pinch<A: 'static, F: Fn(&mut Context, f32, GestureState) -> A + Clone + 'static>(
+        self,
+        f: F,
+    ) -> Pinch<Self, PinchFunc<F>> {
+        Pinch::new(self, PinchFunc { f })
+    }
+
+    /// Shows `content` next to the view, above everything else, while
+    /// `shown` is true. Clicking outside or pressing Escape sets it to false.
+    fn popover<B: Binding<bool>>(self, shown: B, content: impl View) -> Popover<Self, B> {
+        Popover::new(self, shown, content)
+    }
+
+    /// Calls a function as two fingers on the view, or a trackpad, turn.
+    /// Passes the angle turned in radians, counter-clockwise.
+    fn rotate_gesture<A: 'static, F: Fn(&mut Context, f32, GestureState) -> A + Clone + 'static>(
+        self,
+        f: F,
+    ) -> Pinch<Self, RotateFunc<F>> {
+        Pinch::new(self, RotateFunc { f })
+    }
+
+    /// Rotates the view about its center by `angle` radians, counter-clockwise.
+    fn rotation(self, angle: f32) -> TransformView<Self> {
+        TransformView::new(
+            self,
+            LocalToLocal::rotation(euclid::Angle::radians(angle)),
+            true,
+        )
+    }
+
+    /// Scales the view about its center.
+    fn scale(self, scale: f32) -> TransformView<Self> {
+        TransformView::new(self, LocalToLocal::scale(scale, scale), true)
+    }
+
+    /// Shows `content` over the whole window while `shown` is true,
+    /// dimming everything beneath and keeping clicks and keys from it.
+    fn sheet<B: Binding<bool>>(self, shown: B, content: impl View) -> Sheet<Self, B> {
+        Sheet::new(self, shown, content)
+    }
+
     /// Specify an accessiblity role.
     fn role(self, role: Role) -> RoleView<Self> {
         RoleView::new(self, role)
@@ -176,6 +337,7 @@ pub trait Modifiers: View + Sized {
     ///     .touch(move |_, info| match info.state {
     ///         TouchState::Begin => { println!("Touched") }
     ///         TouchState::End => { println!("Released") }
+    ///         TouchState::Cancel => { println!("Cancelled") }
     ///     });
     ///     //.run();
     /// ```
@@ -186,6 +348,16 @@ pub trait Modifiers: View + Sized {
         Touch::new(self, TouchFunc { f })
     }
 
+    /// Shows a tooltip once the mouse rests on the view.
+    fn tooltip(self, text: &str) -> Tooltip<Self> {
+        Tooltip::new(self, text)
+    }
+
+    /// Applies an affine transform to the view in local space.
+    fn transform(self, transform: LocalToLocal) -> TransformView<Self> {
+        TransformView::new(self, transform, false)
+    }
+
     /// Specify the title of the window.
     fn window_title(self, title: &str) -> TitleView<Self> {
         TitleView::new(self, title)
@@ -0,0 +1,503 @@
+use crate::*;
+use std::sync::Arc;
+
+/// A view drawn above everything else in its window, such as a popover,
+/// context menu or tooltip.
+#[derive(Clone)]
+pub(crate) struct Overlay {
+    /// Root of the window the overlay is shown in.
+    pub root: IdPath,
+
+    /// Path of the overlay's content.
+    pub path: IdPath,
+
+    pub view: AnyView,
+
+    /// The rect the overlay is placed next to, in the local space of the
+    /// view showing it.
+    pub local_anchor: LocalRect,
+
+    /// The rect the overlay is placed next to, in window coordinates.
+    /// Found from the layout offsets until the view showing it is drawn.
+    pub anchor: WorldRect,
+
+    /// Has `anchor` been set while drawing?
+    pub drawn: bool,
+
+    /// Where the overlay is placed, in window coordinates.
+    pub rect: WorldRect,
+
+    /// Hides the overlay.
+    pub dismiss: Arc<dyn Fn(&mut Context)>,
+
+    /// Does a click outside the overlay only dismiss it, rather than
+    /// also reaching the views beneath?
+    pub blocks_outside: bool,
+
+    /// Is this a sheet, centered over a backdrop which keeps clicks and
+    /// keys from the views beneath?
+    pub modal: bool,
+
+    /// Was the overlay shown during the last layout?
+    pub shown: bool,
+}
+
+/// Places a box of `size` below `anchor`, left edges aligned. If it
+/// doesn't fit it flips above, or to the anchor's right edge, and is
+/// finally clamped to the window.
+pub(crate) fn place_overlay(
+    anchor: WorldRect,
+    size: LocalSize,
+    window_size: LocalSize,
+) -> WorldPoint {
+    let mut x = anchor.min_x();
+    if x + size.width > window_size.width {
+        x = anchor.max_x() - size.width;
+    }
+    let mut y = anchor.min_y() - size.height;
+    if y < 0.0 {
+        y = anchor.max_y();
+    }
+    [
+        x.min(window_size.width - size.width).max(0.0),
+        y.min(window_size.height - size.height).max(0.0),
+    ]
+    .into()
+}
+
+impl Overlay {
+    /// Where the overlay's content goes, given its size.
+    fn origin(&self, sz: LocalSize, window_size: LocalSize) -> WorldPoint {
+        if self.modal {
+            ((window_size - sz) / 2.0)
+                .to_vector()
+                .to_point()
+                .cast_unit()
+        } else {
+            place_overlay(self.anchor, sz, window_size)
+        }
+    }
+}
+
+impl Context {
+    /// Shows `view` above everything else in the current window, next to
+    /// `anchor` in the local space of the view whose child is at `path`.
+    /// Call this during layout, every time the overlay is shown.
+    pub(crate) fn show_overlay(
+        &mut self,
+        path: &IdPath,
+        view: AnyView,
+        anchor: LocalRect,
+        dismiss: Arc<dyn Fn(&mut Context)>,
+        blocks_outside: bool,
+    ) {
+        self.add_overlay(Overlay {
+            root: self.overlay_root.clone(),
+            path: path.clone(),
+            view,
+            local_anchor: anchor,
+            anchor: WorldRect::zero(),
+            drawn: false,
+            rect: WorldRect::zero(),
+            dismiss,
+            blocks_outside,
+            modal: false,
+            shown: true,
+        })
+    }
+
+    /// Shows `view` centered over the whole of the current window, which
+    /// is dimmed. Nothing beneath gets clicks or keys until it's dismissed.
+    /// Call this during layout, every time the sheet is shown.
+    pub(crate) fn show_modal(
+        &mut self,
+        path: &IdPath,
+        view: AnyView,
+        dismiss: Arc<dyn Fn(&mut Context)>,
+    ) {
+        self.add_overlay(Overlay {
+            root: self.overlay_root.clone(),
+            path: path.clone(),
+            view,
+            local_anchor: LocalRect::zero(),
+            anchor: WorldRect::zero(),
+            drawn: false,
+            rect: WorldRect::zero(),
+            dismiss,
+            blocks_outside: true,
+            modal: true,
+            shown: true,
+        })
+    }
+
+    /// Adds an overlay, or updates it if it was already shown, keeping
+    /// its place in the stack and where it was drawn.
+    fn add_overlay(&mut self, overlay: Overlay) {
+        match self.overlays.iter_mut().find(|o| o.path == overlay.path) {
+            Some(o) => {
+                *o = Overlay {
+                    anchor: o.anchor,
+                    drawn: o.drawn,
+                    rect: o.rect,
+                    ..overlay
+                }
+            }
+            None => self.overlays.push(overlay),
+        }
+    }
+
+    /// Keeps the overlays shown from within the view at `path`, whose
+    /// layout was skipped since nothing it depends on changed.
+    pub(crate) fn keep_overlays(&mut self, path: &IdPath) {
+        for o in self
+            .overlays
+            .iter_mut()
+            .filter(|o| o.path.starts_with(path))
+        {
+            o.shown = true;
+        }
+    }
+
+    /// Places the overlay whose content is at `path` next to `anchor`, in
+    /// window coordinates. Call this while drawing the view showing it.
+    pub(crate) fn anchor_overlay(&mut self, path: &IdPath, anchor: WorldRect) {
+        if let Some(o) = self.overlays.iter_mut().find(|o| o.path == *path) {
+            o.anchor = anchor;
+            o.drawn = true;
+        }
+    }
+
+    /// Lays out the window whose view is at `root`, then the overlays
+    /// shown by its views. Overlays no longer shown are dropped.
+    pub(crate) fn layout_root(
+        &mut self,
+        view: &impl View,
+        root: &IdPath,
+        window_size: LocalSize,
+        text_bounds: &mut dyn FnMut(&str, u32, Option<f32>) -> LocalRect,
+    ) -> LocalSize {
+        for o in self.overlays.iter_mut().filter(|o| o.root == *root) {
+            o.shown = false;
+        }
+        self.overlay_root = root.clone();
+
+        let mut path = root.clone();
+        let sz = view.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: window_size,
+                cx: self,
+                text_bounds: &mut *text_bounds,
+            },
+        );
+        assert!(path == *root);
+
+        self.layout_overlays(root, window_size, text_bounds);
+        self.overlays.retain(|o| o.root != *root || o.shown);
+        sz
+    }
+
+    /// Lays out and places the overlays in the window at `root`. Overlays
+    /// shown by overlays are laid out after them.
+    fn layout_overlays(
+        &mut self,
+        root: &IdPath,
+        window_size: LocalSize,
+        text_bounds: &mut dyn FnMut(&str, u32, Option<f32>) -> LocalRect,
+    ) {
+        let mut i = 0;
+        while i < self.overlays.len() {
+            if self.overlays[i].root != *root || !self.overlays[i].shown {
+                i += 1;
+                continue;
+            }
+            let view = self.overlays[i].view.clone();
+            let mut path = self.overlays[i].path.clone();
+            let sz = view.layout(
+                &mut path,
+                &mut LayoutArgs {
+                    sz: window_size,
+                    cx: self,
+                    text_bounds: &mut *text_bounds,
+                },
+            );
+            if !self.overlays[i].drawn {
+                self.overlays[i].anchor = self.anchor_from_layout(&self.overlays[i]);
+            }
+            let o = &mut self.overlays[i];
+            o.rect = WorldRect::new(o.origin(sz, window_size), sz.cast_unit());
+            i += 1;
+        }
+    }
+
+    /// Where an overlay's anchor is, going by the layout offsets of the
+    /// views above the one showing it.
+    fn anchor_from_layout(&self, overlay: &Overlay) -> WorldRect {
+        let anchor_path = &overlay.path[..overlay.path.len() - 1];
+
+        // The window, or the overlay which the anchor is in.
+        let (base, origin) = match self
+            .overlays
+            .iter()
+            .find(|o| o.path != overlay.path && anchor_path.starts_with(&o.path))
+        {
+            Some(o) => (o.path.len(), o.rect.origin.to_vector().cast_unit()),
+            None => (
+                overlay.root.len(),
+                self.root_offsets
+                    .get(&overlay.root)
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+        };
+        let offset = (base..=anchor_path.len())
+            .map(|n| self.get_layout(&anchor_path[..n].to_vec()).offset)
+            .fold(origin, |acc, offset| acc + offset);
+        overlay.local_anchor.translate(offset).cast_unit()
+    }
+
+    /// Draws the overlays shown in the window at `root`, placed next to
+    /// where their anchors were drawn. Overlays shown by overlays are
+    /// drawn after them.
+    pub(crate) fn draw_overlays(
+        &mut self,
+        root: &IdPath,
+        vger: &mut Vger,
+        window_size: LocalSize,
+        root_offset: LocalOffset,
+    ) {
+        let mut i = 0;
+        while i < self.overlays.len() {
+            if self.overlays[i].root != *root || !self.overlays[i].shown {
+                i += 1;
+                continue;
+            }
+            let o = &mut self.overlays[i];
+            let sz = o.rect.size.cast_unit();
+            o.rect.origin = o.origin(sz, window_size);
+            let (view, mut path, modal, origin) =
+                (o.view.clone(), o.path.clone(), o.modal, o.rect.origin);
+
+            vger.save();
+            if modal {
+                let paint = vger.color_paint(BACKDROP_COLOR);
+                vger.fill_rect(
+                    LocalRect::new((-root_offset).to_point(), window_size),
+                    0.0,
+                    paint,
+                );
+            }
+            vger.translate(origin.to_vector().cast_unit() - root_offset);
+            view.draw(&mut path, &mut DrawArgs { cx: self, vger });
+            vger.restore();
+            i += 1;
+        }
+    }
+
+    /// Gives the overlays in the window at `root` the first look at an
+    /// event. Returns true if the event shouldn't reach the views beneath.
+    pub(crate) fn process_overlays(&mut self, root: &IdPath, event: &Event) -> bool {
+        let overlays: Vec<Overlay> = self
+            .overlays
+            .iter()
+            .filter(|o| o.root == *root)
+            .cloned()
+            .collect();
+        if overlays.is_empty() {
+            return false;
+        }
+
+        // Views beneath a modal overlay get no clicks or keys.
+        let modal = overlays.iter().rposition(|o| o.modal);
+        let above_modal = modal.map_or(0, |m| m + 1);
+
+        match event {
+            Event::Key(Key::Escape) => {
+                let top = overlays.last().unwrap();
+                (top.dismiss)(self);
+                self.overlays.retain(|o| o.path != top.path);
+                true
+            }
+            Event::TouchBegin { position, .. } | Event::Scroll { position, .. } => {
+                let pt = position.cast_unit();
+                if let Some(o) = overlays.iter().rev().find(|o| o.rect.contains(pt)) {
+                    self.process_overlay(o, event);
+                    return true;
+                }
+                if let Event::Scroll { .. } = event {
+                    return modal.is_some();
+                }
+
+                // A click outside closes every overlay in the window,
+                // apart from modal ones and those beneath them.
+                let closed = &overlays[above_modal..];
+                for o in closed.iter().rev() {
+                    (o.dismiss)(self);
+                }
+                self.overlays
+                    .retain(|o| !closed.iter().any(|c| c.path == o.path));
+                modal.is_some() || closed.iter().any(|o| o.blocks_outside)
+            }
+            Event::TouchMove { .. }
+            | Event::TouchEnd { .. }
+            | Event::TouchCancel { .. }
+            | Event::PointerMove { .. }
+            | Event::PointerEnter(_)
+            | Event::PointerLeave(_)
+            | Event::Access(_) => {
+                // Views which captured a touch, are hovered, or handle the
+                // action, check for themselves, so everyone gets these.
+                for o in &overlays {
+                    self.process_overlay(o, event);
+                }
+                false
+            }
+            Event::Key(_) => {
+                let reachable = &overlays[modal.unwrap_or(0)..];
+                if let Some(m) = modal {
+                    self.trap_focus(&overlays[m]);
+                }
+                for o in reachable {
+                    self.process_overlay(o, event);
+                }
+                modal.is_some()
+            }
+            _ => false,
+        }
+    }
+
+    /// Unfocuses any view outside a modal overlay.
+    fn trap_focus(&mut self, modal: &Overlay) {
+        if let Some(focused) = self.focused_id {
+            if !self
+                .focusable_views(&modal.view, &modal.path)
+                .contains(&focused)
+            {
+                self.focused_id = None;
+            }
+        }
+    }
+
+    fn process_overlay(&mut self, overlay: &Overlay, event: &Event) {
+        let mut actions = vec![];
+        let mut path = overlay.path.clone();
+        let offset: LocalOffset = overlay.rect.origin.to_vector().cast_unit();
+        overlay
+            .view
+            .process(&event.offset(-offset), &mut path, self, &mut actions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, w: f32, h: f32) -> WorldRect {
+        WorldRect::new([x, y].into(), [w, h].into())
+    }
+
+    #[test]
+    fn test_place_overlay() {
+        let window = LocalSize::new(200.0, 200.0);
+        let size = LocalSize::new(50.0, 30.0);
+
+        // Below the anchor, left edges aligned.
+        let p = place_overlay(rect(10.0, 100.0, 20.0, 20.0), size, window);
+        assert_eq!(p, [10.0, 70.0].into());
+
+        // No room below, so above.
+        let p = place_overlay(rect(10.0, 10.0, 20.0, 20.0), size, window);
+        assert_eq!(p, [10.0, 30.0].into());
+
+        // No room to the right, so right edges aligned.
+        let p = place_overlay(rect(170.0, 100.0, 20.0, 20.0), size, window);
+        assert_eq!(p, [140.0, 70.0].into());
+
+        // A point in the top right corner.
+        let p = place_overlay(rect(200.0, 200.0, 0.0, 0.0), size, window);
+        assert_eq!(p, [150.0, 170.0].into());
+    }
+
+    #[test]
+    fn test_outside_click_dismisses() {
+        let ui = state(
+            || 0,
+            |count, _| {
+                state(
+                    || true,
+                    move |shown, _| {
+                        hstack((
+                            rectangle()
+                                .size([20.0, 20.0])
+                                .popover(shown, rectangle().size([10.0, 10.0])),
+                            rectangle().size([20.0, 20.0]).tooltip("Tip"),
+                            rectangle().tap(move |cx| cx[count] += 1),
+                        ))
+                    },
+                )
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let count = h.root_state::<i32>();
+        let shown = h.state::<bool>(&[0, 0]);
+        let outside = [55.0, 50.0];
+
+        // Inside the popover.
+        assert_eq!(h.cx.overlays.len(), 1);
+        h.tap(h.cx.overlays[0].rect.center().cast_unit());
+        assert!(*h.get(shown));
+
+        // A popover keeps the click which closes it from the views beneath.
+        h.tap(outside);
+        assert!(!*h.get(shown));
+        assert!(h.cx.overlays.is_empty());
+        assert_eq!(*h.get(count), 0);
+
+        // A tooltip doesn't.
+        let tip = h.root_rect(&[0, 0, 0, 1]).unwrap();
+        h.pointer_move(tip.center());
+        h.advance_time(std::time::Duration::from_secs_f32(TOOLTIP_DELAY));
+        assert_eq!(h.cx.overlays.len(), 1);
+        h.tap(outside);
+        assert!(h.cx.overlays.is_empty());
+        assert_eq!(*h.get(count), 1);
+    }
+
+    #[test]
+    fn test_escape_dismisses_topmost() {
+        let ui = state(
+            || true,
+            |outer, _| {
+                state(
+                    || true,
+                    move |inner, _| {
+                        rectangle().size([20.0, 20.0]).popover(
+                            outer,
+                            rectangle()
+                                .size([30.0, 30.0])
+                                .popover(inner, rectangle().size([10.0, 10.0])),
+                        )
+                    },
+                )
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let outer = h.root_state::<bool>();
+        let inner = h.state::<bool>(&[0, 0]);
+
+        // The inner popover is placed next to the outer one's content.
+        assert_eq!(h.cx.overlays.len(), 2);
+        let (a, b) = (h.cx.overlays[0].rect, h.cx.overlays[1].rect);
+        assert_eq!(b.min_x(), a.min_x());
+        assert!(b.max_y() <= a.min_y() || b.min_y() >= a.max_y());
+
+        h.key(Key::Escape);
+        assert!(*h.get(outer));
+        assert!(!*h.get(inner));
+        assert_eq!(h.cx.overlays.len(), 1);
+
+        h.key(Key::Escape);
+        assert!(!*h.get(outer));
+        assert!(h.cx.overlays.is_empty());
+    }
+}
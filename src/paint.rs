@@ -17,14 +17,20 @@ pub enum Paint {
 
 impl Paint {
     pub fn vger_paint(&self, vger: &mut Vger) -> PaintIndex {
+        self.vger_paint_with_opacity(vger, 1.0)
+    }
+
+    /// Like `vger_paint`, but with color alphas scaled by `opacity`.
+    pub fn vger_paint_with_opacity(&self, vger: &mut Vger, opacity: f32) -> PaintIndex {
+        let fade = |c: &Color| c.alpha(c.a * opacity);
         match self {
-            Paint::Color(color) => vger.color_paint(*color),
+            Paint::Color(color) => vger.color_paint(fade(color)),
             Paint::Gradient {
                 start,
                 end,
                 inner_color,
                 outer_color,
-            } => vger.linear_gradient(*start, *end, *inner_color, *outer_color, 0.0),
+            } => vger.linear_gradient(*start, *end, fade(inner_color), fade(outer_color), 0.0),
         }
     }
 }
@@ -0,0 +1,151 @@
+use crate::*;
+
+impl Context {
+    /// Gives a new platform touch a slot in `touches`, so each finger can
+    /// drive its own gesture. Slot 0 is kept for the mouse. Returns `None`
+    /// if every slot is taken.
+    #[cfg(any(feature = "winit", test))]
+    pub(crate) fn begin_touch(&mut self, touch_id: u64) -> Option<usize> {
+        self.end_touch(touch_id);
+        let slot = (1..self.touch_ids.len()).find(|i| self.touch_ids[*i].is_none())?;
+        self.touch_ids[slot] = Some(touch_id);
+        Some(slot)
+    }
+
+    /// The slot given to a platform touch by `begin_touch`.
+    #[cfg(any(feature = "winit", test))]
+    pub(crate) fn touch_slot(&self, touch_id: u64) -> Option<usize> {
+        self.touch_ids.iter().position(|id| *id == Some(touch_id))
+    }
+
+    /// Frees the slot of a platform touch which ended or was cancelled,
+    /// returning it.
+    #[cfg(any(feature = "winit", test))]
+    pub(crate) fn end_touch(&mut self, touch_id: u64) -> Option<usize> {
+        let slot = self.touch_slot(touch_id)?;
+        self.touch_ids[slot] = None;
+        Some(slot)
+    }
+
+    /// Is the pointer over the view, or a view it contains?
+    pub fn hovered(&self, id: ViewId) -> bool {
+        self.hovered.values().any(|ids| ids.contains(&id))
+    }
+
+    /// Works out which views in the window at `root` are under the
+    /// pointer, then sends `PointerLeave` to those it moved off and
+    /// `PointerEnter` to those it moved onto. A `position` of `None`
+    /// means the pointer left the window.
+    pub(crate) fn update_hover(
+        &mut self,
+        view: &impl View,
+        root: &IdPath,
+        position: Option<LocalPoint>,
+    ) {
+        let hovered = match position {
+            Some(pt) => self.hovered_ids(view, root, pt),
+            None => vec![],
+        };
+        let old = self
+            .hovered
+            .insert(root.clone(), hovered.clone())
+            .unwrap_or_default();
+
+        let left: Vec<ViewId> = old
+            .iter()
+            .filter(|id| !hovered.contains(id))
+            .cloned()
+            .collect();
+        let entered: Vec<ViewId> = hovered
+            .iter()
+            .filter(|id| !old.contains(id))
+            .cloned()
+            .collect();
+        if !left.is_empty() {
+            self.process_root(view, root, &Event::PointerLeave(left));
+        }
+        if !entered.is_empty() {
+            self.process_root(view, root, &Event::PointerEnter(entered));
+        }
+    }
+
+    /// The view hit at `pt`, then each view containing it, up to the root.
+    /// Overlays are above the window's views.
+    fn hovered_ids(&mut self, view: &impl View, root: &IdPath, pt: LocalPoint) -> Vec<ViewId> {
+        let overlay = self
+            .overlays
+            .iter()
+            .rev()
+            .find(|o| o.root == *root && o.rect.contains(pt.cast_unit()))
+            .cloned();
+        let hit = match overlay {
+            Some(o) => {
+                let mut path = o.path.clone();
+                let offset: LocalOffset = o.rect.origin.to_vector().cast_unit();
+                o.view.hittest(&mut path, pt - offset, self)
+            }
+            None => {
+                let mut path = root.clone();
+                let root_offset = self.root_offsets.get(root).cloned().unwrap_or_default();
+                view.hittest(&mut path, pt - root_offset, self)
+            }
+        };
+
+        let path = match hit.and_then(|hit| self.id_path(hit)) {
+            Some(path) => path.clone(),
+            None => return vec![],
+        };
+        (1..=path.len())
+            .rev()
+            .map(|n| self.view_id(&path[..n].to_vec()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_touch_slots() {
+        let mut cx = Context::new();
+        assert_eq!(cx.begin_touch(7), Some(1));
+        assert_eq!(cx.begin_touch(3), Some(2));
+        assert_eq!(cx.touch_slot(7), Some(1));
+        assert_eq!(cx.end_touch(7), Some(1));
+        assert_eq!(cx.touch_slot(7), None);
+
+        // Freed slots are reused, and the other touch keeps its slot.
+        assert_eq!(cx.begin_touch(9), Some(1));
+        assert_eq!(cx.touch_slot(3), Some(2));
+
+        for id in 10..23 {
+            assert!(cx.begin_touch(id).is_some());
+        }
+        assert_eq!(cx.begin_touch(99), None);
+    }
+
+    #[test]
+    fn test_enter_leave() {
+        let ui = hstack((
+            rectangle().size([50.0, 50.0]),
+            rectangle().size([50.0, 50.0]),
+        ));
+        let mut h = TestHarness::new(&ui, [100.0, 50.0]);
+        let (stack, left, right) = (h.view_id(&[0]), h.view_id(&[0, 0]), h.view_id(&[0, 1]));
+
+        h.pointer_move([10.0, 10.0]);
+        assert!(h.cx.hovered(left));
+        assert!(h.cx.hovered(stack));
+        assert!(!h.cx.hovered(right));
+
+        h.pointer_move([60.0, 10.0]);
+        assert!(!h.cx.hovered(left));
+        assert!(h.cx.hovered(stack));
+        assert!(h.cx.hovered(right));
+
+        h.event(&Event::MouseLeftWindow);
+        assert!(!h.cx.hovered(stack));
+        assert!(!h.cx.hovered(right));
+    }
+}
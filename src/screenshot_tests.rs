@@ -374,3 +374,17 @@ fn test_screenshot_cond() {
     );
     screenshot_test(&mut renderer, &ui, "cond_true");
 }
+
+#[test]
+fn test_rich_text_styles_draw_differently() {
+    let mut renderer = TestRenderer::new();
+    let mut render = |s: Span| renderer.render_view(&rich_text(s.size(48)), WIDTH, HEIGHT);
+    let plain = render(span("Hello"));
+    let bold = render(span("Hello").bold());
+    let italic = render(span("Hello").italic());
+
+    assert!(plain.iter().any(|c| *c != 0));
+    assert!(pixel_diff_fraction(&plain, &bold, 2) > 0.0);
+    assert!(pixel_diff_fraction(&plain, &italic, 2) > 0.0);
+    assert!(pixel_diff_fraction(&bold, &italic, 2) > 0.0);
+}
@@ -1,63 +1,114 @@
 use crate::*;
+use std::time::Duration;
+use web_time::Instant;
+
+/// Measures text for layout when there is no GPU to ask.
+///
+/// The harness calls this wherever `LayoutArgs::text_bounds` would
+/// otherwise go to vger. Closures with the same signature implement it.
+pub trait TextMeasure {
+    fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect;
+}
+
+impl<F: FnMut(&str, u32, Option<f32>) -> LocalRect> TextMeasure for F {
+    fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect {
+        (self)(text, size, max_width)
+    }
+}
+
+/// Approximate text metrics: each char is half the font size wide,
+/// and a line is one font size tall.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ApproxTextMeasure;
+
+impl TextMeasure for ApproxTextMeasure {
+    fn text_bounds(&mut self, text: &str, size: u32, _max_width: Option<f32>) -> LocalRect {
+        let w = text.chars().count() as f32 * size as f32 * 0.5;
+        let h = size as f32;
+        LocalRect::new(LocalPoint::zero(), [w, h].into())
+    }
+}
 
 /// A headless test harness for simulating input sequences on views.
 ///
 /// Handles layout, event processing, and state access without
-/// needing a GPU or window.
+/// needing a GPU or window. Enable the `testing` feature to use it
+/// from another crate.
 ///
 /// # Example
-/// ```ignore
+/// ```
+/// use rui::*;
+///
 /// let ui = state(|| 0, |count, _| {
 ///     rectangle().size([100.0, 50.0]).tap(move |cx| cx[count] += 1)
 /// });
 /// let mut h = TestHarness::new(&ui, [100.0, 50.0]);
 /// h.tap([50.0, 25.0]);
-/// let s = StateHandle::<i32>::new(h.cx.view_id(&vec![0]));
-/// assert_eq!(h.cx[s], 1);
+/// let count = h.root_state::<i32>();
+/// assert_eq!(*h.get(count), 1);
 /// ```
-pub(crate) struct TestHarness<'a, V: View> {
+pub struct TestHarness<'a, V: View> {
     view: &'a V,
     pub cx: Context,
     sz: LocalSize,
+    measure: Box<dyn TextMeasure + 'a>,
 }
 
 impl<'a, V: View> TestHarness<'a, V> {
     /// Create a new harness for the given view and window size.
     pub fn new(view: &'a V, sz: impl Into<LocalSize>) -> Self {
+        Self::with_text_measure(view, sz, ApproxTextMeasure)
+    }
+
+    /// Create a new harness which measures text with `measure`.
+    pub fn with_text_measure(
+        view: &'a V,
+        sz: impl Into<LocalSize>,
+        measure: impl TextMeasure + 'a,
+    ) -> Self {
         let sz = sz.into();
         let mut harness = Self {
             view,
             cx: Context::new(),
             sz,
+            measure: Box::new(measure),
         };
+        harness.cx.fake_now = Some(Instant::now());
         harness.layout();
         harness
     }
 
-    /// Run layout, computing sizes and positions for all views.
+    /// Run layout, computing sizes and positions for all views, and
+    /// placing the overlays they show.
     pub fn layout(&mut self) {
-        let mut path = vec![0];
-        self.view.layout(
-            &mut path,
-            &mut LayoutArgs {
-                sz: self.sz,
-                cx: &mut self.cx,
-                // Approximate text bounds: ~half of font size per char.
-                text_bounds: &mut |text, size, _max_width| {
-                    let w = text.len() as f32 * size as f32 * 0.5;
-                    let h = size as f32;
-                    LocalRect::new(LocalPoint::zero(), [w, h].into())
-                },
-            },
+        let measure = &mut self.measure;
+        self.cx.layout_root(
+            self.view,
+            &vec![0],
+            self.sz,
+            &mut |text, size, max_width| measure.text_bounds(text, size, max_width),
         );
     }
 
+    /// Drops state for views which are no longer in the tree,
+    /// as happens after each event in a running app.
+    pub fn gc(&mut self) {
+        let mut keep = vec![];
+        let mut path = vec![0];
+        self.view.gc(&mut path, &mut self.cx, &mut keep);
+        let keep: std::collections::HashSet<ViewId> = keep.into_iter().collect();
+        self.cx.state_map.retain(|id, _| keep.contains(id));
+    }
+
+    /// Changes the window size and re-runs layout.
+    pub fn resize(&mut self, sz: impl Into<LocalSize>) {
+        self.sz = sz.into();
+        self.layout();
+    }
+
     /// Send a single event to the view tree (no re-layout).
     fn send_event(&mut self, event: &Event) {
-        let mut actions = vec![];
-        let mut path = vec![0];
-        self.view
-            .process(event, &mut path, &mut self.cx, &mut actions);
+        self.cx.process(self.view, event);
     }
 
     /// Send a single event to the view tree, then re-layout.
@@ -66,6 +117,20 @@ impl<'a, V: View> TestHarness<'a, V> {
         self.layout();
     }
 
+    /// Moves the clock on by a sixtieth of a second and runs an animation
+    /// frame, as the event loop does before each update, then re-layouts.
+    pub fn tick(&mut self) {
+        self.advance_time(Duration::from_secs_f32(ANIMATION_FRAME));
+    }
+
+    /// Moves the context's clock forward, then runs an animation frame
+    /// so views waiting on it notice.
+    pub fn advance_time(&mut self, dt: Duration) {
+        self.cx.fake_now = self.cx.fake_now.map(|t| t + dt);
+        self.cx.run_animations(self.view);
+        self.layout();
+    }
+
     /// Simulate a tap (touch begin + end) at the given position.
     pub fn tap(&mut self, position: impl Into<LocalPoint>) {
         let position = position.into();
@@ -95,6 +160,14 @@ impl<'a, V: View> TestHarness<'a, V> {
         self.layout();
     }
 
+    /// Simulate moving the mouse, with no button down.
+    pub fn pointer_move(&mut self, position: impl Into<LocalPoint>) {
+        self.send_event(&Event::PointerMove {
+            position: position.into(),
+        });
+        self.layout();
+    }
+
     /// Simulate a key press.
     pub fn key(&mut self, k: Key) {
         self.send_event(&Event::Key(k));
@@ -117,6 +190,112 @@ impl<'a, V: View> TestHarness<'a, V> {
     pub fn set_key_mods(&mut self, mods: KeyboardModifiers) {
         self.cx.key_mods = mods;
     }
+
+    /// Builds the accessibility tree a platform adapter would be sent.
+    pub fn access_nodes(&mut self) -> Vec<(accesskit::NodeId, accesskit::Node)> {
+        self.cx.access_nodes(self.view, &vec![0], self.sz.height)
+    }
+
+    /// Returns the first accessibility node with `role`.
+    pub fn find_access_node(&mut self, role: accesskit::Role) -> Option<accesskit::NodeId> {
+        self.access_nodes()
+            .into_iter()
+            .find(|(_, node)| node.role() == role)
+            .map(|(id, _)| id)
+    }
+
+    /// Acts as a screen reader: sends an action request to the context's
+    /// `access_action_handler`, delivers it, then re-layouts.
+    pub fn access_action(
+        &mut self,
+        target: accesskit::NodeId,
+        action: accesskit::Action,
+        data: Option<accesskit::ActionData>,
+    ) {
+        use accesskit::ActionHandler;
+        self.access_nodes();
+        self.cx
+            .access_action_handler()
+            .do_action(accesskit::ActionRequest {
+                action,
+                target,
+                data,
+            });
+        self.cx.process_access_actions(self.view);
+        self.layout();
+    }
+
+    /// Reads a value through a binding.
+    pub fn get<S>(&self, binding: impl Binding<S>) -> &S {
+        binding.get(&self.cx)
+    }
+
+    /// Writes a value through a binding, then re-layouts.
+    pub fn set<S>(&mut self, binding: impl Binding<S>, value: S) {
+        *binding.get_mut(&mut self.cx) = value;
+        self.layout();
+    }
+
+    /// Returns the `ViewId` of the view at `path`.
+    pub fn view_id(&mut self, path: &[u64]) -> ViewId {
+        self.cx.view_id(&path.to_vec())
+    }
+
+    /// Returns a handle to the state created by a `state` view at `path`.
+    pub fn state<S: 'static>(&mut self, path: &[u64]) -> StateHandle<S> {
+        StateHandle::new(self.view_id(path))
+    }
+
+    /// Returns a handle to the state created by a `state` view at the root.
+    pub fn root_state<S: 'static>(&mut self) -> StateHandle<S> {
+        self.state(&[0])
+    }
+
+    /// Returns the topmost view under a point.
+    pub fn hittest(&mut self, pt: impl Into<LocalPoint>) -> Option<ViewId> {
+        let mut path = vec![0];
+        self.view.hittest(&mut path, pt.into(), &mut self.cx)
+    }
+
+    /// Returns every path which has layout information, sorted.
+    pub fn paths(&self) -> Vec<IdPath> {
+        self.cx
+            .layout_entries()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    /// Returns the rectangle computed for `path` in the space of its container.
+    pub fn rect(&self, path: &[u64]) -> Option<LocalRect> {
+        self.cx
+            .layout_entries()
+            .into_iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, b)| b.rect)
+    }
+
+    /// Returns the rectangle computed for `path` in root space, by applying
+    /// the layout offsets of the path and each of its ancestors.
+    pub fn root_rect(&self, path: &[u64]) -> Option<LocalRect> {
+        let entries = self.cx.layout_entries();
+        let rect = entries.iter().find(|(p, _)| p == path)?.1.rect;
+        let offset = entries
+            .iter()
+            .filter(|(p, _)| path.starts_with(p))
+            .fold(LocalOffset::zero(), |acc, (_, b)| acc + b.offset);
+        Some(rect.translate(offset))
+    }
+
+    /// Returns the paths whose root space rectangle contains `pt`,
+    /// from outermost to innermost.
+    pub fn paths_at(&self, pt: impl Into<LocalPoint>) -> Vec<IdPath> {
+        let pt = pt.into();
+        self.paths()
+            .into_iter()
+            .filter(|p| self.root_rect(p).is_some_and(|r| r.contains(pt)))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -461,9 +640,143 @@ mod tests {
         }
 
         assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].downcast_ref::<Action>(), Some(&Action::Clicked));
+    }
+
+    // --- State lookup through bindings ---
+
+    #[test]
+    fn test_get_set_binding() {
+        let ui = state(
+            || 0i32,
+            |count, _| {
+                rectangle()
+                    .size([100.0, 50.0])
+                    .tap(move |cx| cx[count] += 1)
+            },
+        );
+
+        let mut h = TestHarness::new(&ui, [100.0, 50.0]);
+        let count = h.root_state::<i32>();
+        assert_eq!(*h.get(count), 0);
+
+        h.set(count, 41);
+        h.tap([50.0, 25.0]);
+        assert_eq!(*h.get(count), 42);
+    }
+
+    // --- Layout queries ---
+
+    #[test]
+    fn test_root_rect_in_hstack() {
+        let ui = hstack((
+            rectangle().size([100.0, 50.0]),
+            rectangle().size([100.0, 50.0]),
+        ));
+
+        let h = TestHarness::new(&ui, [200.0, 50.0]);
+
+        // Each rectangle stores its rect under its stack slot.
+        let left = h.root_rect(&[0, 0, 0]).unwrap();
+        let right = h.root_rect(&[0, 1, 0]).unwrap();
         assert_eq!(
-            actions[0].downcast_ref::<Action>(),
-            Some(&Action::Clicked)
+            left,
+            LocalRect::new([0.0, 0.0].into(), [100.0, 50.0].into())
         );
+        assert_eq!(
+            right,
+            LocalRect::new([100.0, 0.0].into(), [100.0, 50.0].into())
+        );
+
+        assert_eq!(h.rect(&[0, 1, 0]).unwrap().origin, LocalPoint::zero());
+        assert!(h.paths_at([150.0, 25.0]).contains(&vec![0, 1, 0]));
+        assert!(!h.paths_at([150.0, 25.0]).contains(&vec![0, 0, 0]));
+    }
+
+    #[test]
+    fn test_hittest() {
+        let ui = rectangle().size([50.0, 50.0]);
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        assert!(h.hittest([25.0, 25.0]).is_some());
+        assert!(h.hittest([75.0, 75.0]).is_none());
+    }
+
+    // --- Pluggable text measurement ---
+
+    #[test]
+    fn test_custom_text_measure() {
+        let ui = text("hello");
+
+        let h = TestHarness::new(&ui, [200.0, 100.0]);
+        assert!(h.paths().is_empty());
+
+        let mut measured = vec![];
+        TestHarness::with_text_measure(&ui, [200.0, 100.0], |s: &str, _size, _max_width| {
+            measured.push(s.to_string());
+            LocalRect::new(LocalPoint::zero(), [10.0, 10.0].into())
+        });
+        assert_eq!(measured, vec!["hello".to_string()]);
+    }
+
+    #[test]
+    fn test_approx_text_measure_counts_chars() {
+        // Widths go by chars rather than bytes, so accented text lays out
+        // like the unaccented text it would look like on screen.
+        let mut measure = ApproxTextMeasure;
+        let accented = measure.text_bounds("héllo", 10, None);
+        let plain = measure.text_bounds("hello", 10, None);
+        assert_eq!(accented, plain);
+        assert_eq!(plain.size, [25.0, 10.0].into());
+    }
+
+    // --- Animation ---
+
+    #[test]
+    fn test_tick_runs_animations() {
+        let ui = state(
+            || 0.0f32,
+            |x, _| {
+                rectangle()
+                    .size([100.0, 50.0])
+                    .tap(move |cx| cx.animate(x, 100.0, Curve::Linear, 0.25))
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 50.0]);
+        let x = h.root_state::<f32>();
+
+        h.tap([50.0, 25.0]);
+        assert!(h.cx.is_animating());
+
+        h.tick();
+        assert!(*h.get(x) > 0.0 && *h.get(x) < 100.0);
+
+        for _ in 0..15 {
+            h.tick();
+        }
+        assert_eq!(*h.get(x), 100.0);
+        h.tick();
+        assert!(!h.cx.is_animating());
+    }
+
+    #[test]
+    fn test_idle_anim_closure_is_not_animating() {
+        let ui = state(
+            || 0,
+            |count, _| {
+                rectangle().anim(move |cx, _dt| {
+                    if cx[count] < 3 {
+                        cx[count] += 1
+                    }
+                })
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 50.0]);
+
+        h.tick();
+        assert!(h.cx.is_animating());
+        for _ in 0..3 {
+            h.tick();
+        }
+        assert!(!h.cx.is_animating());
     }
 }
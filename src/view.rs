@@ -10,7 +10,9 @@ pub struct DrawArgs<'a> {
 pub struct LayoutArgs<'a> {
     pub sz: LocalSize,
     pub cx: &'a mut Context,
-    pub text_bounds: &'a mut dyn FnMut(&str, u32, Option<f32>) -> LocalRect,
+    /// Measures text in the built-in font. Views call
+    /// `LayoutArgs::text_bounds`, which also knows about loaded fonts.
+    pub(crate) text_bounds: &'a mut dyn FnMut(&str, u32, Option<f32>) -> LocalRect,
 }
 
 impl<'a> LayoutArgs<'a> {
@@ -30,7 +32,7 @@ pub trait DynView: private::Sealed + DynClone + 'static {
         &self,
         _path: &mut IdPath,
         _cx: &mut Context,
-        _nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        _nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         None
     }
@@ -0,0 +1,176 @@
+use crate::*;
+use accesskit::NodeBuilder;
+use std::any::Any;
+
+/// Fills in properties of a view's accessibility node.
+pub trait AccessNodeFn: Clone + 'static {
+    fn apply(&self, cx: &Context, node: &mut NodeBuilder);
+}
+
+#[derive(Clone)]
+pub struct AccessLabel(pub String);
+
+impl AccessNodeFn for AccessLabel {
+    fn apply(&self, _cx: &Context, node: &mut NodeBuilder) {
+        node.set_name(self.0.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct AccessDescription(pub String);
+
+impl AccessNodeFn for AccessDescription {
+    fn apply(&self, _cx: &Context, node: &mut NodeBuilder) {
+        node.set_description(self.0.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct AccessHidden;
+
+impl AccessNodeFn for AccessHidden {
+    fn apply(&self, _cx: &Context, node: &mut NodeBuilder) {
+        node.set_hidden()
+    }
+}
+
+#[derive(Clone)]
+pub struct AccessNodeFunc<F> {
+    pub f: F,
+}
+
+impl<F: Fn(&Context, &mut NodeBuilder) + Clone + 'static> AccessNodeFn for AccessNodeFunc<F> {
+    fn apply(&self, cx: &Context, node: &mut NodeBuilder) {
+        (self.f)(cx, node)
+    }
+}
+
+/// Struct for the `access_label`, `access_description` and `access_hidden`
+/// modifiers. Gives the child a node if it doesn't have one.
+#[derive(Clone)]
+pub struct AccessNodeView<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> AccessNodeView<V, F>
+where
+    V: View,
+    F: AccessNodeFn,
+{
+    pub fn new(child: V, func: F) -> Self {
+        Self { child, func }
+    }
+}
+
+impl<V, F> DynView for AccessNodeView<V, F>
+where
+    V: View,
+    F: AccessNodeFn,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let child_id = self.child.access(path, cx, nodes);
+        path.pop();
+
+        let node_id = match child_id {
+            Some(id) => id,
+            None => {
+                let id = cx.view_id(path).access_id();
+                nodes.push((id, NodeBuilder::new(accesskit::Role::GenericContainer)));
+                id
+            }
+        };
+        if let Some((_, builder)) = nodes.iter_mut().rev().find(|(id, _)| *id == node_id) {
+            self.func.apply(cx, builder);
+        }
+        Some(node_id)
+    }
+}
+
+impl<V, F> private::Sealed for AccessNodeView<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesskit::Role;
+
+    #[test]
+    fn test_label_and_description() {
+        let ui = rectangle()
+            .access_label("Close")
+            .access_description("Closes the window");
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let nodes = h.access_nodes();
+        assert_eq!(nodes.len(), 1);
+        let node = &nodes[0].1;
+        assert_eq!(node.role(), Role::GenericContainer);
+        assert_eq!(node.name(), Some("Close"));
+        assert_eq!(node.description(), Some("Closes the window"));
+        assert!(!node.is_hidden());
+    }
+
+    #[test]
+    fn test_hidden() {
+        let ui = text("decoration").access_hidden();
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let nodes = h.access_nodes();
+        let (_, node) = nodes.iter().find(|(_, n)| n.role() == Role::Label).unwrap();
+        assert!(node.is_hidden());
+    }
+}
@@ -81,7 +81,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = self.child.access(path, cx, nodes);
@@ -0,0 +1,224 @@
+use crate::*;
+use std::any::Any;
+
+/// Animated size and position of a view.
+#[derive(Default)]
+struct LayoutTweens {
+    size: Option<Tween<LocalSize>>,
+    offset: Option<Tween<LocalOffset>>,
+}
+
+impl LayoutTweens {
+    /// Where the view is drawn relative to where layout put it.
+    fn delta(&self) -> LocalOffset {
+        self.offset
+            .map(|t| t.value - t.to)
+            .unwrap_or_else(LocalOffset::zero)
+    }
+}
+
+/// Struct for the `animation` modifier.
+#[derive(Clone)]
+pub struct AnimatedLayout<V> {
+    child: V,
+    curve: Curve,
+    duration: f32,
+}
+
+impl<V> AnimatedLayout<V>
+where
+    V: View,
+{
+    pub fn new(child: V, curve: Curve) -> Self {
+        Self {
+            child,
+            curve,
+            duration: DEFAULT_ANIMATION_DURATION,
+        }
+    }
+
+    /// Sets how long timed curves take, in seconds.
+    pub fn duration(self, duration: f32) -> Self {
+        Self { duration, ..self }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<LayoutTweens> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &LayoutTweens::default);
+        StateHandle::new(id)
+    }
+}
+
+impl<V> DynView for AnimatedLayout<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+
+        if let Event::Anim = event {
+            let state = &cx[s];
+            let running = state.size.is_some_and(|t| t.is_running())
+                || state.offset.is_some_and(|t| t.is_running());
+            if running {
+                let dt = cx.frame_time;
+                let state = &mut cx[s];
+                if let Some(t) = &mut state.size {
+                    t.step(dt);
+                }
+                if let Some(t) = &mut state.offset {
+                    t.step(dt);
+                }
+            }
+        }
+
+        let delta = cx[s].delta();
+        path.push(0);
+        self.child.process(&event.offset(-delta), path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        // Our parent sets our offset after layout, so this is the first
+        // chance to see where we're going.
+        let target = args.cx.get_layout(path).offset;
+        let s = self.state(path, args.cx);
+        match args.cx[s].offset {
+            None => args.cx[s].offset = Some(Tween::new(target)),
+            Some(t) if t.to != target => {
+                if let Some(t) = &mut args.cx[s].offset {
+                    t.animate_to(target, self.curve, self.duration);
+                }
+            }
+            _ => (),
+        }
+
+        let delta = args.cx[s].delta();
+        args.vger.save();
+        args.vger.translate(delta);
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        let s = self.state(path, args.cx);
+        match args.cx[s].size {
+            // Nothing animates on first appearance.
+            None => args.cx[s].size = Some(Tween::new(sz)),
+            Some(t) if t.to != sz => {
+                if let Some(t) = &mut args.cx[s].size {
+                    t.animate_to(sz, self.curve, self.duration);
+                }
+            }
+            _ => (),
+        }
+
+        args.cx[s].size.map(|t| t.value).unwrap_or(sz)
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let s = self.state(path, cx);
+        let delta = cx[s].delta();
+        path.push(0);
+        self.child.dirty(path, xform.pre_translate(delta), cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let s = self.state(path, cx);
+        let delta = cx[s].delta();
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt - delta, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for AnimatedLayout<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_animates() {
+        let ui = state(
+            || 20.0f32,
+            |width, cx| rectangle().size([cx[width], 10.0]).animation(Curve::Linear),
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let width = h.root_state::<f32>();
+        let s = StateHandle::<LayoutTweens>::new(h.view_id(&[0, 0]));
+
+        // Nothing animates on first appearance.
+        assert_eq!(h.cx[s].size.unwrap().value, LocalSize::new(20.0, 10.0));
+
+        h.set(width, 40.0);
+        assert_eq!(h.cx[s].size.unwrap().value, LocalSize::new(20.0, 10.0));
+
+        // Halfway through.
+        for _ in 0..8 {
+            h.event(&Event::Anim);
+        }
+        let w = h.cx[s].size.unwrap().value.width;
+        assert!(w > 25.0 && w < 35.0, "{}", w);
+
+        for _ in 0..8 {
+            h.event(&Event::Anim);
+        }
+        assert_eq!(h.cx[s].size.unwrap().value, LocalSize::new(40.0, 10.0));
+        assert!(!h.cx[s].size.unwrap().is_running());
+    }
+
+    #[test]
+    fn test_hittest_follows_animated_offset() {
+        let ui = rectangle().size([10.0, 10.0]).animation(Curve::Linear);
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = StateHandle::<LayoutTweens>::new(h.view_id(&[0]));
+
+        // Pretend the view is still on its way from 50 to the left.
+        let mut offset = Tween::new(LocalOffset::new(-50.0, 0.0));
+        offset.animate_to(LocalOffset::zero(), Curve::Linear, 1.0);
+        h.cx[s].offset = Some(offset);
+
+        assert!(h.hittest([-45.0, 5.0]).is_some());
+        assert!(h.hittest([5.0, 5.0]).is_none());
+    }
+}
@@ -90,7 +90,7 @@ impl DynView for AnyView {
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(self.id_hash());
         let node_id = self.child.access(path, cx, nodes);
@@ -85,7 +85,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         // XXX: if we were to create a node here, what role would it be?
         //      could print a warning if there is an node produced by background.
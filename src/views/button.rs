@@ -18,6 +18,7 @@ pub fn button<A: 'static, F: Fn(&mut Context) -> A + 'static + Clone>(
         ButtonState::default,
         move |s, cx| {
             let f = f.clone();
+            let f2 = f.clone();
             view.clone()
                 .padding(Auto)
                 .background(
@@ -41,11 +42,17 @@ pub fn button<A: 'static, F: Fn(&mut Context) -> A + 'static + Clone>(
                             f(cx);
                         }
                     }
+                    TouchState::Cancel => {
+                        cx[s].down = false;
+                    }
                 })
                 .hover(move |cx, inside| {
                     cx[s].hovered = inside;
                 })
                 .role(Role::Button)
+                .access_action(accesskit::Action::Default, move |cx, _| {
+                    f2(cx);
+                })
         },
     )
 }
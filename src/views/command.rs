@@ -86,7 +86,7 @@ where
         &self,
         _path: &mut IdPath,
         _cx: &mut Context,
-        _nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        _nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         // XXX: how does accesskit handle menu commands?
         None
@@ -324,7 +324,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = self.child.access(path, cx, nodes);
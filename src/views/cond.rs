@@ -7,6 +7,62 @@ pub struct Cond<V0, V1> {
     cond: bool,
     if_true: V0,
     if_false: V1,
+    transition: Option<(Transition, Curve)>,
+}
+
+/// Which branch was last shown and the branches that are on screen.
+#[derive(Default)]
+struct CondState {
+    shown: Option<bool>,
+    branches: [Option<TransitionProgress>; 2],
+}
+
+impl<V0, V1> Cond<V0, V1>
+where
+    V0: View,
+    V1: View,
+{
+    /// Animates branches in and out when the condition changes.
+    pub fn transition(self, transition: Transition, curve: Curve) -> Self {
+        Self {
+            transition: Some((transition, curve)),
+            ..self
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<CondState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &CondState::default);
+        StateHandle::new(id)
+    }
+
+    fn branch(&self) -> usize {
+        if self.cond {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn draw_branch(&self, index: usize, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(index as u64);
+        if index == 0 {
+            self.if_true.draw(path, args);
+        } else {
+            self.if_false.draw(path, args);
+        }
+        path.pop();
+    }
+
+    fn gc_branch(&self, index: usize, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(index as u64);
+        if index == 0 {
+            self.if_true.gc(path, cx, map);
+        } else {
+            self.if_false.gc(path, cx, map);
+        }
+        path.pop();
+    }
 }
 
 impl<V0, V1> DynView for Cond<V0, V1>
@@ -21,6 +77,26 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
+        if let (Event::Anim, Some(_)) = (event, self.transition) {
+            let s = self.state(path, cx);
+            if cx[s]
+                .branches
+                .iter()
+                .flatten()
+                .any(|b| b.progress.is_running())
+            {
+                let dt = cx.frame_time;
+                for branch in cx[s].branches.iter_mut() {
+                    if let Some(b) = branch {
+                        b.progress.step(dt);
+                        if b.is_gone() {
+                            *branch = None;
+                        }
+                    }
+                }
+            }
+        }
+
         if self.cond {
             path.push(0);
             self.if_true.process(event, path, cx, actions);
@@ -33,19 +109,27 @@ where
     }
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
-        if self.cond {
-            path.push(0);
-            self.if_true.draw(path, args);
-            path.pop();
+        if let Some((transition, _)) = self.transition {
+            let s = self.state(path, args.cx);
+            let current = self.branch();
+
+            // Draw the outgoing branch beneath the current one.
+            for index in [1 - current, current] {
+                match args.cx[s].branches[index] {
+                    Some(b) => transition.draw(b.progress.value, b.size, args, |args| {
+                        self.draw_branch(index, path, args)
+                    }),
+                    None if index == current => self.draw_branch(index, path, args),
+                    None => (),
+                }
+            }
         } else {
-            path.push(1);
-            self.if_false.draw(path, args);
-            path.pop();
+            self.draw_branch(self.branch(), path, args);
         }
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
-        if self.cond {
+        let sz = if self.cond {
             path.push(0);
             let sz = self.if_true.layout(path, args);
             path.pop();
@@ -55,7 +139,39 @@ where
             let sz = self.if_false.layout(path, args);
             path.pop();
             sz
+        };
+
+        if let Some((_, curve)) = self.transition {
+            let s = self.state(path, args.cx);
+            let current = self.branch();
+            let state = &args.cx[s];
+
+            if state.shown.is_none() {
+                // Nothing animates on first appearance.
+                let state = &mut args.cx[s];
+                state.shown = Some(self.cond);
+                state.branches[current] = Some(TransitionProgress::present(sz));
+            } else if state.shown != Some(self.cond) {
+                let state = &mut args.cx[s];
+                state.shown = Some(self.cond);
+                match &mut state.branches[current] {
+                    Some(b) => {
+                        b.size = sz;
+                        b.reappear(curve);
+                    }
+                    None => state.branches[current] = Some(TransitionProgress::appear(sz, curve)),
+                }
+                if let Some(b) = &mut state.branches[1 - current] {
+                    b.disappear(curve);
+                }
+            } else if state.branches[current].is_some_and(|b| b.size != sz) {
+                if let Some(b) = &mut args.cx[s].branches[current] {
+                    b.size = sz;
+                }
+            }
         }
+
+        sz
     }
 
     fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
@@ -85,22 +201,27 @@ where
     }
 
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
-        if self.cond {
-            path.push(0);
-            self.if_true.gc(path, cx, map);
-            path.pop();
-        } else {
-            path.push(1);
-            self.if_false.gc(path, cx, map);
-            path.pop();
+        let current = self.branch();
+        if self.transition.is_some() {
+            let s = self.state(path, cx);
+            map.push(s.id);
+
+            // Keep the other branch around while it animates out,
+            // or if it's about to start.
+            let state = &cx[s];
+            let other = 1 - current;
+            if state.branches[other].is_some() || state.shown == Some(other == 0) {
+                self.gc_branch(other, path, cx, map);
+            }
         }
+        self.gc_branch(current, path, cx, map);
     }
 
     fn access(
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         if self.cond {
             path.push(0);
@@ -119,11 +240,12 @@ where
 impl<V0, V1> private::Sealed for Cond<V0, V1> {}
 
 /// Switches between views according to a boolean.
-pub fn cond(cond: bool, if_true: impl View, if_false: impl View) -> impl View {
+pub fn cond<V0: View, V1: View>(cond: bool, if_true: V0, if_false: V1) -> Cond<V0, V1> {
     Cond {
         cond,
         if_true,
         if_false,
+        transition: None,
     }
 }
 
@@ -172,4 +294,40 @@ mod tests {
         );
         assert_eq!(result, [100.0, 100.0].into());
     }
+
+    #[test]
+    fn test_cond_transition() {
+        let ui = state(
+            || true,
+            |shown, cx| {
+                cond(
+                    cx[shown],
+                    rectangle().size([50.0, 50.0]),
+                    rectangle().size([100.0, 100.0]),
+                )
+                .transition(Transition::Fade, Curve::Linear)
+            },
+        );
+        let mut h = TestHarness::new(&ui, [200.0, 200.0]);
+        let shown = h.root_state::<bool>();
+        let s = StateHandle::<CondState>::new(h.view_id(&[0, 0]));
+
+        // Nothing animates on first appearance.
+        assert!(h.cx[s].branches[1].is_none());
+        assert!(!h.cx[s].branches[0].unwrap().progress.is_running());
+
+        h.set(shown, false);
+        h.gc();
+        let outgoing = h.cx[s].branches[0].unwrap();
+        let incoming = h.cx[s].branches[1].unwrap();
+        assert_eq!(outgoing.progress.to, 0.0);
+        assert_eq!(incoming.progress.to, 1.0);
+        assert_eq!(incoming.size, LocalSize::new(100.0, 100.0));
+
+        for _ in 0..20 {
+            h.event(&Event::Anim);
+        }
+        assert!(h.cx[s].branches[0].is_none());
+        assert_eq!(h.cx[s].branches[1].unwrap().progress.value, 1.0);
+    }
 }
@@ -0,0 +1,267 @@
+use crate::*;
+use std::any::Any;
+use web_time::Instant;
+
+#[derive(Clone, Default)]
+struct DoubleTapState {
+    /// The touch on the view: its id and where it began.
+    touch: Option<(usize, LocalPoint)>,
+
+    /// A first tap kept from the views beneath until we know it isn't
+    /// half of a double tap: its id, where it was and when it ended.
+    pending: Option<(usize, LocalPoint, Instant)>,
+}
+
+/// Struct for the `double_tap` gesture.
+#[derive(Clone)]
+pub struct DoubleTap<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> DoubleTap<V, F>
+where
+    V: View,
+    F: TapFn + 'static,
+{
+    pub fn new(child: V, func: F) -> Self {
+        Self { child, func }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<DoubleTapState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &DoubleTapState::default);
+        StateHandle::new(id)
+    }
+
+    /// Lets the views beneath have a held back first tap, as a tap of
+    /// its own, once it's too late or too far for a second.
+    fn release_pending(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        s: StateHandle<DoubleTapState>,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Some((id, position, _)) = cx[s].pending.take() {
+            path.push(0);
+            self.child
+                .process(&Event::TouchBegin { id, position }, path, cx, actions);
+            self.child
+                .process(&Event::TouchEnd { id, position }, path, cx, actions);
+            path.pop();
+        }
+    }
+
+    /// Takes a touch back from the views beneath.
+    fn cancel_child(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        id: usize,
+        position: LocalPoint,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if !cx.touches[id].is_default() {
+            path.push(0);
+            self.child
+                .process(&Event::TouchCancel { id, position }, path, cx, actions);
+            path.pop();
+            cx.touches[id] = ViewId::default();
+        }
+    }
+}
+
+impl<V, F> DynView for DoubleTap<V, F>
+where
+    V: View,
+    F: TapFn + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        let thresholds = cx.init_env(&GestureThresholds::default);
+        if let Some((_, _, end)) = cx[s].pending {
+            if cx.now().duration_since(end) > thresholds.multi_tap_interval {
+                self.release_pending(path, cx, s, actions);
+            } else if let Event::Anim = event {
+                // Keep frames coming until we know.
+                cx.request_frame();
+            }
+        }
+
+        match event {
+            Event::TouchBegin { id, position } if self.hittest(path, *position, cx).is_some() => {
+                if let Some((_, first, _)) = cx[s].pending {
+                    if (*position - first).length() > thresholds.tap_distance {
+                        self.release_pending(path, cx, s, actions);
+                    }
+                }
+                cx[s].touch = Some((*id, *position));
+            }
+            Event::TouchEnd { id, position } if cx[s].touch.is_some_and(|t| t.0 == *id) => {
+                let start = cx[s].touch.take().unwrap().1;
+                if (*position - start).length() <= thresholds.tap_distance {
+                    self.cancel_child(path, cx, *id, *position, actions);
+                    if cx[s].pending.take().is_some() {
+                        self.func.call(
+                            cx,
+                            TapInfo {
+                                pt: *position,
+                                button: cx.mouse_button,
+                                state: TouchState::End,
+                            },
+                            actions,
+                        );
+                    } else {
+                        cx[s].pending = Some((*id, start, cx.now()));
+                    }
+                    return;
+                }
+                self.release_pending(path, cx, s, actions);
+            }
+            Event::TouchCancel { id, .. } if cx[s].touch.is_some_and(|t| t.0 == *id) => {
+                cx[s].touch = None;
+            }
+            _ => (),
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F> private::Sealed for DoubleTap<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_double_tap_is_not_two_taps() {
+        let ui = state(Vec::new, |s, _| {
+            rectangle()
+                .tap(move |cx| cx[s].push("tap"))
+                .double_tap(move |cx| cx[s].push("double"))
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<Vec<&str>>();
+
+        h.tap([50.0, 50.0]);
+        h.advance_time(Duration::from_millis(100));
+        h.tap([52.0, 50.0]);
+        h.advance_time(Duration::from_secs(1));
+        assert_eq!(*h.get(s), vec!["double"]);
+    }
+
+    #[test]
+    fn test_single_tap_waits() {
+        let ui = state(Vec::new, |s, _| {
+            rectangle()
+                .tap(move |cx| cx[s].push("tap"))
+                .double_tap(move |cx| cx[s].push("double"))
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<Vec<&str>>();
+
+        h.tap([50.0, 50.0]);
+        assert!(h.get(s).is_empty());
+
+        // Waiting asks for frames without dirtying the tree.
+        h.cx.dirty = false;
+        h.tick();
+        assert!(h.cx.is_animating());
+        assert!(!h.cx.dirty);
+
+        h.advance_time(Duration::from_secs(1));
+        assert_eq!(*h.get(s), vec!["tap"]);
+
+        // Too slow, or too far apart, for a double tap.
+        h.tap([50.0, 50.0]);
+        h.advance_time(Duration::from_secs(1));
+        h.tap([50.0, 50.0]);
+        h.tap([90.0, 90.0]);
+        h.advance_time(Duration::from_secs(1));
+        assert_eq!(*h.get(s), vec!["tap"; 4]);
+    }
+
+    #[test]
+    fn test_thresholds_from_env() {
+        let ui = state(
+            || 0,
+            |count, _| {
+                rectangle()
+                    .double_tap(move |cx| cx[count] += 1)
+                    .env(GestureThresholds {
+                        multi_tap_interval: Duration::from_secs(2),
+                        tap_distance: 50.0,
+                    })
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let count = h.root_state::<i32>();
+
+        h.tap([20.0, 20.0]);
+        h.advance_time(Duration::from_secs(1));
+        h.tap([50.0, 50.0]);
+        assert_eq!(*h.get(count), 1);
+    }
+}
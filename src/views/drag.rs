@@ -7,6 +7,8 @@ pub enum GestureState {
     Began,
     Changed,
     Ended,
+    /// The touch was cancelled, so the gesture should be undone.
+    Cancelled,
 }
 
 pub trait DragFn: Clone {
@@ -172,16 +174,20 @@ where
                     cx.previous_position[*id] = *position;
                 }
             }
-            Event::TouchEnd { id, position } => {
+            Event::TouchEnd { id, position } | Event::TouchCancel { id, position } => {
                 if cx.touches[*id] == vid {
                     cx.touches[*id] = ViewId::default();
                     cx.grab_cursor = false;
 
+                    let state = match event {
+                        Event::TouchEnd { .. } => GestureState::Ended,
+                        _ => GestureState::Cancelled,
+                    };
                     self.func.call(
                         cx,
                         *position,
                         LocalOffset::zero(),
-                        GestureState::Ended,
+                        state,
                         cx.mouse_button,
                         actions,
                     );
@@ -237,7 +243,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = self.child.access(path, cx, nodes);
@@ -309,4 +315,57 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_two_finger_drags() {
+        let ui = state(Vec::new, |states, _| {
+            hstack((
+                rectangle().drag(move |cx, delta, state, _| cx[states].push((0, delta, state))),
+                rectangle().drag(move |cx, delta, state, _| cx[states].push((1, delta, state))),
+            ))
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 50.0]);
+        let s = h.root_state::<Vec<(i32, LocalOffset, GestureState)>>();
+
+        h.event(&Event::TouchBegin {
+            id: 1,
+            position: [10.0, 10.0].into(),
+        });
+        h.event(&Event::TouchBegin {
+            id: 2,
+            position: [60.0, 10.0].into(),
+        });
+        h.event(&Event::TouchMove {
+            id: 2,
+            position: [70.0, 10.0].into(),
+            delta: [10.0, 0.0].into(),
+        });
+        h.event(&Event::TouchMove {
+            id: 1,
+            position: [10.0, 15.0].into(),
+            delta: [0.0, 5.0].into(),
+        });
+        h.event(&Event::TouchEnd {
+            id: 1,
+            position: [10.0, 15.0].into(),
+        });
+        h.event(&Event::TouchCancel {
+            id: 2,
+            position: [70.0, 10.0].into(),
+        });
+
+        let zero = LocalOffset::zero();
+        assert_eq!(
+            *h.get(s),
+            vec![
+                (0, zero, GestureState::Began),
+                (1, zero, GestureState::Began),
+                (1, [10.0, 0.0].into(), GestureState::Changed),
+                (0, [0.0, 5.0].into(), GestureState::Changed),
+                (0, zero, GestureState::Ended),
+                (1, zero, GestureState::Cancelled),
+            ]
+        );
+        assert!(h.cx.touches.iter().all(|t| t.is_default()));
+    }
 }
@@ -69,7 +69,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = (self.func)(cx.init_env(&S::default), cx).access(path, cx, nodes);
@@ -124,7 +124,7 @@ where
         path.push(0);
         self.child.process(event, path, cx, actions);
         path.pop();
-        old.and_then(|s| cx.set_env(&s));
+        cx.restore_env(old);
     }
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
@@ -132,7 +132,7 @@ where
         path.push(0);
         self.child.draw(path, args);
         path.pop();
-        old.and_then(|s| args.cx.set_env(&s));
+        args.cx.restore_env(old);
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
@@ -140,7 +140,7 @@ where
         path.push(0);
         let sz = self.child.layout(path, args);
         path.pop();
-        old.and_then(|s| args.cx.set_env(&s));
+        args.cx.restore_env(old);
         sz
     }
 
@@ -149,7 +149,7 @@ where
         path.push(0);
         self.child.dirty(path, xform, cx);
         path.pop();
-        old.and_then(|s| cx.set_env(&s));
+        cx.restore_env(old);
     }
 
     fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
@@ -157,7 +157,7 @@ where
         path.push(0);
         let r = self.child.hittest(path, pt, cx);
         path.pop();
-        old.and_then(|s| cx.set_env(&s));
+        cx.restore_env(old);
         r
     }
 
@@ -166,7 +166,7 @@ where
         path.push(0);
         self.child.commands(path, cx, cmds);
         path.pop();
-        old.and_then(|s| cx.set_env(&s));
+        cx.restore_env(old);
     }
 
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
@@ -174,20 +174,20 @@ where
         path.push(0);
         self.child.gc(path, cx, map);
         path.pop();
-        old.and_then(|s| cx.set_env(&s));
+        cx.restore_env(old);
     }
 
     fn access(
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         let old = cx.set_env(&self.env_val);
         path.push(0);
         let r = self.child.access(path, cx, nodes);
         path.pop();
-        old.and_then(|s| cx.set_env(&s));
+        cx.restore_env(old);
         r
     }
 }
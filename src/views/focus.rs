@@ -1,6 +1,44 @@
 use crate::*;
 use std::any::Any;
 
+/// Width of the ring drawn around the focused view.
+pub const FOCUS_RING_WIDTH: f32 = 2.0;
+
+fn draw_focus_ring(path: &IdPath, args: &mut DrawArgs) {
+    let rect = args
+        .cx
+        .get_layout(path)
+        .rect
+        .inflate(FOCUS_RING_WIDTH, FOCUS_RING_WIDTH);
+    let paint = args
+        .vger
+        .color_paint(args.cx.apply_opacity(FOCUS_RING_COLOR));
+    args.vger
+        .stroke_rect(rect.min(), rect.max(), 4.0, FOCUS_RING_WIDTH, paint);
+}
+
+/// Records a size for drawing the focus ring, keeping the offset our
+/// parent gave us.
+fn store_size(path: &IdPath, sz: LocalSize, cx: &mut Context) {
+    let offset = cx.get_layout(path).offset;
+    cx.update_layout(
+        path,
+        LayoutBox {
+            rect: LocalRect::new(LocalPoint::origin(), sz),
+            offset,
+        },
+    );
+}
+
+/// Adds a view to the focus chain, if it's being collected.
+fn add_to_focus_chain(path: &IdPath, cx: &mut Context) {
+    let id = cx.view_id(path);
+    let order = cx.focus_order;
+    if let Some(chain) = &mut cx.focus_chain {
+        chain.push((id, order));
+    }
+}
+
 /// Struct for the `focus` modifier.
 #[derive(Clone)]
 pub struct Focus<F> {
@@ -27,6 +65,12 @@ where
                     cx.set_dirty();
                 }
             }
+            Event::Access(request)
+                if request.action == accesskit::Action::Focus
+                    && cx.handles_access_action(path, request) =>
+            {
+                cx.focus(vid);
+            }
             Event::Key(Key::Escape) => {
                 if cx.focused_id == Some(vid) {
                     cx.focused_id = None;
@@ -42,9 +86,13 @@ where
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
         let id = args.cx.view_id(path);
+        let focused = Some(id) == args.cx.focused_id;
         path.push(0);
-        (self.func)(Some(id) == args.cx.focused_id).draw(path, args);
+        (self.func)(focused).draw(path, args);
         path.pop();
+        if focused {
+            draw_focus_ring(path, args);
+        }
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
@@ -52,6 +100,7 @@ where
         path.push(0);
         let sz = (self.func)(Some(id) == args.cx.focused_id).layout(path, args);
         path.pop();
+        store_size(path, sz, args.cx);
         sz
     }
 
@@ -88,12 +137,16 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
+        add_to_focus_chain(path, cx);
         let id = cx.view_id(path);
         path.push(0);
         let node_id = (self.func)(Some(id) == cx.focused_id).access(path, cx, nodes);
         path.pop();
+        if let Some(node_id) = node_id {
+            cx.handle_access_actions(path, node_id, &[accesskit::Action::Focus], nodes);
+        }
         node_id
     }
 }
@@ -105,3 +158,287 @@ impl<F> private::Sealed for Focus<F> {}
 pub fn focus<V: View, F: Fn(bool) -> V + Clone + 'static>(f: F) -> impl View {
     Focus { func: f }
 }
+
+/// Struct for the `focusable` modifier.
+#[derive(Clone)]
+pub struct Focusable<V> {
+    child: V,
+}
+
+impl<V> Focusable<V>
+where
+    V: View,
+{
+    pub fn new(child: V) -> Self {
+        Self { child }
+    }
+}
+
+impl<V> DynView for Focusable<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        match &event {
+            Event::TouchBegin { id: _, position }
+                if self.hittest(path, *position, cx).is_some() =>
+            {
+                cx.focus(vid);
+            }
+            Event::Access(request)
+                if request.action == accesskit::Action::Focus
+                    && cx.handles_access_action(path, request) =>
+            {
+                cx.focus(vid);
+            }
+            Event::Key(Key::Escape) if cx.focused_id == Some(vid) => {
+                cx.focused_id = None;
+                cx.set_dirty();
+            }
+            // Keys only go to the focused view.
+            Event::Key(_) if cx.focused_id != Some(vid) => return,
+            _ => (),
+        }
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        let id = args.cx.view_id(path);
+        if args.cx.focused_id == Some(id) {
+            draw_focus_ring(path, args);
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        store_size(path, sz, args.cx);
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.child.hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        add_to_focus_chain(path, cx);
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        if let Some(node_id) = node_id {
+            cx.handle_access_actions(path, node_id, &[accesskit::Action::Focus], nodes);
+        }
+        node_id
+    }
+}
+
+impl<V> private::Sealed for Focusable<V> {}
+
+/// Struct for the `focus_order` modifier.
+#[derive(Clone)]
+pub struct FocusOrder<V> {
+    child: V,
+    order: i32,
+}
+
+impl<V> FocusOrder<V>
+where
+    V: View,
+{
+    pub fn new(child: V, order: i32) -> Self {
+        Self { child, order }
+    }
+}
+
+impl<V> DynView for FocusOrder<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.child.hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        let order = cx.focus_order;
+        cx.focus_order = self.order;
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        cx.focus_order = order;
+        node_id
+    }
+}
+
+impl<V> private::Sealed for FocusOrder<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field() -> impl View {
+        rectangle().size([20.0, 20.0]).focusable()
+    }
+
+    #[test]
+    fn test_tab_cycles_in_tree_order() {
+        let ui = hstack((field(), field(), field()));
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let ids: Vec<ViewId> = (0..3).map(|i| h.view_id(&[0, i])).collect();
+
+        h.key(Key::Tab);
+        assert_eq!(h.cx.focused(), Some(ids[0]));
+        h.key(Key::Tab);
+        assert_eq!(h.cx.focused(), Some(ids[1]));
+
+        h.set_key_mods(KeyboardModifiers {
+            shift: true,
+            ..Default::default()
+        });
+        h.key(Key::Tab);
+        h.key(Key::Tab);
+        assert_eq!(h.cx.focused(), Some(ids[2]));
+    }
+
+    #[test]
+    fn test_focus_order() {
+        let ui = hstack((field(), field().focus_order(-1), field().focus_order(1)));
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let chain = h.cx.focus_chain(&ui, &vec![0]);
+        let expected: Vec<ViewId> = [vec![0, 1, 0], vec![0, 0], vec![0, 2, 0]]
+            .iter()
+            .map(|p| h.view_id(p))
+            .collect();
+        assert_eq!(chain, expected);
+    }
+
+    #[test]
+    fn test_keys_only_reach_focused_view() {
+        let ui = state(
+            || 0,
+            |count, _| {
+                rectangle()
+                    .size([20.0, 20.0])
+                    .key(move |cx, _| cx[count] += 1)
+                    .focusable()
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let count = h.root_state::<i32>();
+
+        h.key(Key::Character('a'));
+        assert_eq!(*h.get(count), 0);
+
+        let id = h.view_id(&[0, 0]);
+        h.cx.focus(id);
+        h.key(Key::Character('a'));
+        assert_eq!(*h.get(count), 1);
+    }
+
+    #[test]
+    fn test_tab_stays_in_modal() {
+        let ui = state(
+            || true,
+            |shown, _| hstack((field(), field())).sheet(shown, field()),
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+
+        h.key(Key::Tab);
+        let inside = h.cx.focused();
+        assert!(inside.is_some());
+        h.key(Key::Tab);
+        assert_eq!(h.cx.focused(), inside);
+        assert_ne!(inside, Some(h.view_id(&[0, 0, 0, 0])));
+        assert_ne!(inside, Some(h.view_id(&[0, 0, 0, 1])));
+    }
+}
@@ -2,7 +2,11 @@ use crate::*;
 use std::any::Any;
 
 pub trait HoverFn: Clone {
-    fn call(&self, cx: &mut Context, pt: LocalPoint, inside: bool, actions: &mut Vec<Box<dyn Any>>);
+    /// Called when the pointer moves onto or off the view.
+    fn hover(&self, cx: &mut Context, inside: bool, actions: &mut Vec<Box<dyn Any>>);
+
+    /// Called as the pointer moves over the view.
+    fn moved(&self, _cx: &mut Context, _pt: LocalPoint, _actions: &mut Vec<Box<dyn Any>>) {}
 }
 
 #[derive(Clone)]
@@ -11,16 +15,10 @@ pub struct HoverFuncP<F> {
 }
 
 impl<A: 'static, F: Fn(&mut Context, LocalPoint) -> A + Clone + 'static> HoverFn for HoverFuncP<F> {
-    fn call(
-        &self,
-        cx: &mut Context,
-        pt: LocalPoint,
-        inside: bool,
-        actions: &mut Vec<Box<dyn Any>>,
-    ) {
-        if inside {
-            actions.push(Box::new((self.f)(cx, pt)))
-        }
+    fn hover(&self, _cx: &mut Context, _inside: bool, _actions: &mut Vec<Box<dyn Any>>) {}
+
+    fn moved(&self, cx: &mut Context, pt: LocalPoint, actions: &mut Vec<Box<dyn Any>>) {
+        actions.push(Box::new((self.f)(cx, pt)))
     }
 }
 
@@ -30,13 +28,7 @@ pub struct HoverFunc<F> {
 }
 
 impl<A: 'static, F: Fn(&mut Context, bool) -> A + Clone + 'static> HoverFn for HoverFunc<F> {
-    fn call(
-        &self,
-        cx: &mut Context,
-        _pt: LocalPoint,
-        inside: bool,
-        actions: &mut Vec<Box<dyn Any>>,
-    ) {
+    fn hover(&self, cx: &mut Context, inside: bool, actions: &mut Vec<Box<dyn Any>>) {
         actions.push(Box::new((self.f)(cx, inside)))
     }
 }
@@ -70,15 +62,22 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        if let Event::TouchMove { position, .. } = &event {
-            if cx.mouse_button.is_none() {
+        let vid = cx.view_id(path);
+        match event {
+            Event::PointerEnter(ids) if ids.contains(&vid) => self.func.hover(cx, true, actions),
+            Event::PointerLeave(ids) if ids.contains(&vid) => self.func.hover(cx, false, actions),
+            Event::PointerMove { position } if cx.hovered(vid) => {
+                self.func.moved(cx, *position, actions)
+            }
+            Event::TouchEnd { position, .. } => {
+                // Touch screens have no pointer to hover with.
                 let inside = self.hittest(path, *position, cx).is_some();
-                self.func.call(cx, *position, inside, actions);
+                self.func.hover(cx, inside, actions);
+                if inside {
+                    self.func.moved(cx, *position, actions)
+                }
             }
-        }
-        if let Event::TouchEnd { position, .. } = &event {
-            let inside = self.hittest(path, *position, cx).is_some();
-            self.func.call(cx, *position, inside, actions);
+            _ => (),
         }
         path.push(0);
         self.child.process(event, path, cx, actions);
@@ -127,7 +126,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = self.child.access(path, cx, nodes);
@@ -137,3 +136,36 @@ where
 }
 
 impl<V, F> private::Sealed for Hover<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hover_without_button() {
+        let ui = state(
+            || (false, LocalPoint::zero()),
+            |s, _| {
+                rectangle()
+                    .size([50.0, 50.0])
+                    .hover(move |cx, inside| cx[s].0 = inside)
+                    .hover_p(move |cx, pt| cx[s].1 = pt)
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<(bool, LocalPoint)>();
+
+        h.pointer_move([10.0, 20.0]);
+        assert_eq!(*h.get(s), (true, [10.0, 20.0].into()));
+
+        h.pointer_move([30.0, 40.0]);
+        assert_eq!(h.get(s).1, [30.0, 40.0].into());
+
+        h.pointer_move([80.0, 80.0]);
+        assert_eq!(*h.get(s), (false, [30.0, 40.0].into()));
+
+        h.pointer_move([10.0, 10.0]);
+        h.event(&Event::MouseLeftWindow);
+        assert!(!h.get(s).0);
+    }
+}
@@ -9,7 +9,7 @@ fn lerp(x: f32, a: f32, b: f32) -> f32 {
 
 /// Knob for controlling a 0 to 1 floating point parameter.
 pub fn knob(value: impl Binding<f32>) -> impl View {
-    zstack((
+    let knob = zstack((
         circle()
             .color(CLEAR_COLOR)
             .drag_s(value, move |v, delta, _, _| {
@@ -20,11 +20,11 @@ pub fn knob(value: impl Binding<f32>) -> impl View {
             let c = sz.center();
             let r = sz.width().min(sz.height()) / 2.0;
 
-            let paint = vger.color_paint(CONTROL_BACKGROUND);
+            let paint = vger.color_paint(cx.apply_opacity(CONTROL_BACKGROUND));
 
             vger.stroke_arc(c, r, 2.0, 0.0, std::f32::consts::PI, paint);
 
-            let paint = vger.color_paint(AZURE_HIGHLIGHT);
+            let paint = vger.color_paint(cx.apply_opacity(AZURE_HIGHLIGHT));
             let a0 = lerp(*value.get(cx), THETA_MAX, THETA_MIN);
             let a1 = THETA_MAX;
 
@@ -33,7 +33,8 @@ pub fn knob(value: impl Binding<f32>) -> impl View {
 
             vger.stroke_arc(c, r, 2.0, theta, ap, paint);
         }),
-    ))
+    ));
+    adjustable(knob.role(accesskit::Role::Slider), value)
 }
 
 #[cfg(test)]
@@ -0,0 +1,485 @@
+use crate::*;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Range;
+
+/// How tall the rows of a `lazy_vlist` are.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowHeight {
+    /// Every row has this height.
+    Fixed(f32),
+    /// Rows are measured when they're laid out. Rows which haven't been
+    /// seen yet are assumed to have this height.
+    Estimated(f32),
+}
+
+#[derive(Default)]
+struct LazyListState {
+    /// Index of the first row laid out in the last pass.
+    first: usize,
+    /// Rows laid out in the last pass, by id hash.
+    visible: Vec<u64>,
+    /// Measured heights of rows, by id hash, for `RowHeight::Estimated`.
+    heights: HashMap<u64, f32>,
+    /// Rows which have been visible, by id hash, for `keep_offscreen_state`.
+    seen: HashSet<u64>,
+}
+
+/// Struct for `lazy_vlist`.
+#[derive(Clone)]
+pub struct LazyList<ID, F> {
+    ids: Vec<ID>,
+    row_height: RowHeight,
+    keep_offscreen_state: bool,
+    func: F,
+}
+
+impl<ID, V, F> LazyList<ID, F>
+where
+    ID: Hash + Clone + 'static,
+    V: View,
+    F: Fn(&ID) -> V + Clone + 'static,
+{
+    /// Keeps the state of rows after they scroll out of view, so it's still
+    /// there when they come back. By default that state is dropped, which
+    /// keeps memory use proportional to what's on screen.
+    pub fn keep_offscreen_state(mut self, keep: bool) -> Self {
+        self.keep_offscreen_state = keep;
+        self
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<LazyListState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &LazyListState::default);
+        StateHandle::new(id)
+    }
+
+    /// Indices of the rows from the last layout which are still in the
+    /// list. Rows may have been inserted or removed since, so they're
+    /// found by id.
+    fn visible(&self, path: &IdPath, cx: &mut Context) -> Vec<usize> {
+        let s = self.state(path, cx);
+        let state = &cx[s];
+        let rows: Range<usize> = state.first..state.first + state.visible.len();
+
+        // Usually the ids haven't changed.
+        if self
+            .ids
+            .get(rows.clone())
+            .is_some_and(|ids| ids.iter().map(hh).eq(state.visible.iter().copied()))
+        {
+            return rows.collect();
+        }
+
+        let laid_out: HashSet<u64> = state.visible.iter().copied().collect();
+        (0..self.ids.len())
+            .filter(|i| laid_out.contains(&hh(&self.ids[*i])))
+            .collect()
+    }
+}
+
+impl<ID, V, F> DynView for LazyList<ID, F>
+where
+    ID: Hash + Clone + 'static,
+    V: View,
+    F: Fn(&ID) -> V + Clone + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        for i in self.visible(path, cx).into_iter().rev() {
+            let child = &self.ids[i];
+            path.push(hh(child));
+            let offset = cx.get_layout(path).offset;
+            ((self.func)(child)).process(&event.offset(-offset), path, cx, actions);
+            path.pop();
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        for i in self.visible(path, args.cx) {
+            let child = &self.ids[i];
+            path.push(hh(child));
+            let offset = args.cx.get_layout(path).offset;
+
+            args.vger.save();
+            args.vger.translate(offset);
+            ((self.func)(child)).draw(path, args);
+            args.vger.restore();
+
+            path.pop();
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let s = self.state(path, args.cx);
+
+        // Outside of a scroll view, show what fits at the top.
+        let (top, height) = match args.cx.init_env(&ScrollViewport::default).0 {
+            Some((offset, size)) => (offset.y, size.height),
+            None => (0.0, args.sz.height),
+        };
+
+        // Find where each row would start, from the top.
+        let (starts, mut total) = match self.row_height {
+            RowHeight::Fixed(h) => (None, h * self.ids.len() as f32),
+            RowHeight::Estimated(h) => {
+                let heights = &args.cx[s].heights;
+                let mut starts = Vec::with_capacity(self.ids.len());
+                let mut y = 0.0;
+                for id in &self.ids {
+                    starts.push(y);
+                    y += heights.get(&hh(id)).copied().unwrap_or(h);
+                }
+                (Some(starts), y)
+            }
+        };
+
+        let estimated_total = total;
+        let row_start = |i: usize| match (&starts, self.row_height) {
+            (Some(starts), _) => starts.get(i).copied().unwrap_or(estimated_total),
+            (None, RowHeight::Fixed(h)) => i as f32 * h,
+            (None, RowHeight::Estimated(_)) => unreachable!(),
+        };
+
+        // Rows which overlap the viewport.
+        let first = match &starts {
+            Some(starts) => starts.partition_point(|y| *y <= top).saturating_sub(1),
+            None => match self.row_height {
+                RowHeight::Fixed(h) if h > 0.0 => (top / h).floor().max(0.0) as usize,
+                _ => 0,
+            },
+        }
+        .min(self.ids.len());
+        let mut last = first;
+        while last < self.ids.len() && row_start(last) < top + height {
+            last += 1;
+        }
+        let first_start = row_start(first);
+
+        // Rows don't see our viewport. A lazy list inside a row would
+        // otherwise think it's scrolled too.
+        let old = args.cx.set_env(&ScrollViewport(None));
+
+        let width = args.sz.width;
+        let mut sizes = Vec::with_capacity(last - first);
+        for (i, child) in self.ids[first..last].iter().enumerate() {
+            let h = row_start(first + i + 1) - row_start(first + i);
+            path.push(hh(child));
+            let size = ((self.func)(child)).layout(path, &mut args.size([width, h].into()));
+            path.pop();
+
+            if let RowHeight::Estimated(_) = self.row_height {
+                total += size.height - h;
+                args.cx[s].heights.insert(hh(child), size.height);
+                sizes.push(size.height);
+            } else {
+                sizes.push(h);
+            }
+        }
+
+        if let Some(old) = old {
+            args.cx.set_env(&old);
+        }
+
+        // Place rows from the top. Measured rows may have changed the total.
+        let mut y = first_start;
+        for (child, h) in self.ids[first..last].iter().zip(sizes) {
+            path.push(hh(child));
+            args.cx.set_layout_offset(path, [0.0, total - y - h].into());
+            path.pop();
+            y += h;
+        }
+
+        let state = &mut args.cx[s];
+        state.first = first;
+        state.visible = self.ids[first..last].iter().map(hh).collect();
+        if self.keep_offscreen_state {
+            state
+                .seen
+                .extend(self.ids[first..last].iter().map(|id| hh(id)));
+        }
+
+        [width, total].into()
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        for i in self.visible(path, cx) {
+            let child = &self.ids[i];
+            path.push(hh(child));
+            let offset = cx.get_layout(path).offset;
+            ((self.func)(child)).dirty(path, xform.pre_translate(offset), cx);
+            path.pop();
+        }
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let mut hit = None;
+        for i in self.visible(path, cx) {
+            let child = &self.ids[i];
+            path.push(hh(child));
+            let offset = cx.get_layout(path).offset;
+            if let Some(h) = ((self.func)(child)).hittest(path, pt - offset, cx) {
+                hit = Some(h)
+            }
+            path.pop();
+        }
+        hit
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        for i in self.visible(path, cx) {
+            let child = &self.ids[i];
+            path.push(hh(child));
+            ((self.func)(child)).commands(path, cx, cmds);
+            path.pop();
+        }
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        let s = self.state(path, cx);
+        map.push(s.id);
+
+        let visible = self.visible(path, cx);
+        let mut keep = |child: &ID, path: &mut IdPath, cx: &mut Context| {
+            path.push(hh(child));
+            map.push(cx.view_id(path));
+            ((self.func)(child)).gc(path, cx, map);
+            path.pop();
+        };
+
+        if self.keep_offscreen_state {
+            let seen = std::mem::take(&mut cx[s].seen);
+            let mut still_seen = HashSet::new();
+            for (i, child) in self.ids.iter().enumerate() {
+                let h = hh(child);
+                if visible.contains(&i) || seen.contains(&h) {
+                    keep(child, path, cx);
+                    still_seen.insert(h);
+                }
+            }
+            cx[s].seen = still_seen;
+        } else {
+            // Offscreen rows are dropped, along with their state.
+            for i in visible {
+                keep(&self.ids[i], path, cx);
+            }
+        }
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::List);
+
+        let children: Vec<accesskit::NodeId> = self
+            .visible(path, cx)
+            .into_iter()
+            .filter_map(|i| {
+                let child = &self.ids[i];
+                path.push(hh(child));
+                let node_id = ((self.func)(child)).access(path, cx, nodes);
+                path.pop();
+                node_id
+            })
+            .collect();
+
+        builder.set_children(children);
+        nodes.push((cx.view_id(path).access_id(), builder));
+        Some(cx.view_id(path).access_id())
+    }
+}
+
+impl<ID, F> private::Sealed for LazyList<ID, F> {}
+
+/// A vertical list which only builds, lays out and draws the rows that are
+/// visible. Put it directly inside a `vscroll`.
+///
+/// Use this instead of `list` for very large collections. Rows which scroll
+/// out of view are dropped along with their state, unless
+/// `keep_offscreen_state` is set.
+///
+/// ```no_run
+/// # use rui::*;
+/// let ids = (0..100_000).collect();
+/// rui(vscroll(lazy_vlist(ids, RowHeight::Fixed(30.0), |i| {
+///     text(&format!("row {}", i))
+/// })));
+/// ```
+pub fn lazy_vlist<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(
+    ids: Vec<ID>,
+    row_height: RowHeight,
+    f: F,
+) -> LazyList<ID, F> {
+    LazyList {
+        ids,
+        row_height,
+        keep_offscreen_state: false,
+        func: f,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    fn layout(view: &impl View, cx: &mut Context, sz: [f32; 2]) -> LocalSize {
+        view.layout(
+            &mut vec![0],
+            &mut LayoutArgs {
+                sz: sz.into(),
+                cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_only_visible_rows_are_built() {
+        let built = Rc::new(Cell::new(0));
+        let b = built.clone();
+        let ui = lazy_vlist((0..100_000).collect(), RowHeight::Fixed(20.0), move |_| {
+            b.set(b.get() + 1);
+            rectangle()
+        });
+
+        let mut cx = Context::new();
+        let sz = layout(&ui, &mut cx, [100.0, 100.0]);
+        assert_eq!(sz, LocalSize::new(100.0, 2_000_000.0));
+        assert_eq!(built.get(), 5);
+    }
+
+    #[test]
+    fn test_rows_follow_scroll_offset() {
+        let ui = state(LocalOffset::zero, |offset, _| {
+            vscroll(lazy_vlist(
+                (0..1000).collect(),
+                RowHeight::Fixed(20.0),
+                move |i| {
+                    let i = *i;
+                    rectangle().tap(move |cx| cx[offset].x = i as f32)
+                },
+            ))
+            .scroll_offset(offset)
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let offset = h.root_state::<LocalOffset>();
+
+        h.set(offset, LocalOffset::new(0.0, 5000.0));
+
+        // Row 250 is at the top of the viewport.
+        h.tap([50.0, 95.0]);
+        assert_eq!(h.get(offset).x, 250.0);
+
+        // Row 254 at the bottom.
+        h.tap([50.0, 5.0]);
+        assert_eq!(h.get(offset).x, 254.0);
+    }
+
+    #[test]
+    fn test_events_reach_rows_where_they_were_laid_out() {
+        let ui = state(
+            || ((0..100).collect::<Vec<i32>>(), None),
+            |s, cx| {
+                vscroll(lazy_vlist(
+                    cx[s].0.clone(),
+                    RowHeight::Fixed(20.0),
+                    move |i| {
+                        let i = *i;
+                        rectangle().tap(move |cx| cx[s].1 = Some(i))
+                    },
+                ))
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<(Vec<i32>, Option<i32>)>();
+
+        // A row is inserted before the next layout. The bottom row is
+        // still the one drawn there.
+        h.cx[s].0.insert(0, -1);
+        h.tap([50.0, 5.0]);
+        assert_eq!(h.get(s).1, Some(4));
+
+        // Once laid out, the new row is at the top.
+        h.tap([50.0, 95.0]);
+        assert_eq!(h.get(s).1, Some(-1));
+    }
+
+    #[test]
+    fn test_estimated_heights_are_measured() {
+        let built = Rc::new(RefCell::new(vec![]));
+        let b = built.clone();
+        let ui = lazy_vlist((0..100).collect(), RowHeight::Estimated(10.0), move |i| {
+            b.borrow_mut().push(*i);
+            rectangle().size([50.0, 30.0])
+        });
+        let mut cx = Context::new();
+
+        // The first six rows fill the viewport by estimate, and are
+        // measured. The rest are still estimates.
+        let sz = layout(&ui, &mut cx, [100.0, 60.0]);
+        assert_eq!(sz.height, 6.0 * 30.0 + 94.0 * 10.0);
+        assert_eq!(*built.borrow(), vec![0, 1, 2, 3, 4, 5]);
+
+        // Now only the first two rows fit.
+        built.borrow_mut().clear();
+        let sz = layout(&ui, &mut cx, [100.0, 60.0]);
+        assert_eq!(sz.height, 6.0 * 30.0 + 94.0 * 10.0);
+        assert_eq!(*built.borrow(), vec![0, 1]);
+    }
+
+    fn counter_rows(keep: bool) -> impl View {
+        state(LocalOffset::zero, move |offset, _| {
+            vscroll(
+                lazy_vlist((0..100).collect(), RowHeight::Fixed(20.0), |_| {
+                    state(|| 0, |count, _| rectangle().tap(move |cx| cx[count] += 1))
+                })
+                .keep_offscreen_state(keep),
+            )
+            .scroll_offset(offset)
+        })
+    }
+
+    fn row_count(h: &mut TestHarness<'_, impl View>, row: i32) -> Option<i32> {
+        let id = h.view_id(&[0, 0, 0, hh(&row)]);
+        h.cx.state_map
+            .get(&id)
+            .map(|holder| *holder.state.downcast_ref::<i32>().unwrap())
+    }
+
+    #[test]
+    fn test_offscreen_state_dropped() {
+        let ui = counter_rows(false);
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let offset = h.root_state::<LocalOffset>();
+
+        h.tap([50.0, 95.0]);
+        assert_eq!(row_count(&mut h, 0), Some(1));
+
+        h.set(offset, LocalOffset::new(0.0, 1000.0));
+        h.gc();
+        assert_eq!(row_count(&mut h, 0), None);
+    }
+
+    #[test]
+    fn test_offscreen_state_kept() {
+        let ui = counter_rows(true);
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let offset = h.root_state::<LocalOffset>();
+
+        h.tap([50.0, 95.0]);
+        h.set(offset, LocalOffset::new(0.0, 1000.0));
+        h.gc();
+        assert_eq!(row_count(&mut h, 0), Some(1));
+    }
+}
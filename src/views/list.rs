@@ -1,5 +1,6 @@
 use crate::*;
 use std::any::Any;
+use std::collections::HashMap;
 use std::hash::Hash;
 
 #[derive(Clone, Copy)]
@@ -14,6 +15,152 @@ pub struct List<ID, F> {
     orientation: ListOrientation,
     ids: Vec<ID>,
     func: F,
+    transition: Option<(Transition, Curve)>,
+}
+
+/// Items transitioning in or out of a list.
+struct ListTransitions<ID> {
+    /// Ids at the last layout, or None before the first layout.
+    shown: Option<Vec<ID>>,
+
+    /// Sizes at the last layout, keyed by id hash.
+    sizes: HashMap<u64, LocalSize>,
+
+    /// Items which are animating, keyed by id hash.
+    items: HashMap<u64, TransitionProgress>,
+
+    /// Removed items still animating out, with their last offsets.
+    removed: Vec<(ID, LocalOffset)>,
+}
+
+impl<ID> Default for ListTransitions<ID> {
+    fn default() -> Self {
+        Self {
+            shown: None,
+            sizes: HashMap::new(),
+            items: HashMap::new(),
+            removed: vec![],
+        }
+    }
+}
+
+impl<ID, V, F> List<ID, F>
+where
+    ID: Hash + Clone + 'static,
+    V: View,
+    F: Fn(&ID) -> V + Clone + 'static,
+{
+    /// Animates items in and out when they're inserted or removed.
+    pub fn transition(self, transition: Transition, curve: Curve) -> Self {
+        Self {
+            transition: Some((transition, curve)),
+            ..self
+        }
+    }
+
+    fn transitions(&self, path: &IdPath, cx: &mut Context) -> StateHandle<ListTransitions<ID>> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &ListTransitions::<ID>::default);
+        StateHandle::new(id)
+    }
+
+    /// Compares the ids with those from the last layout and starts
+    /// transitions for the ones which were inserted or removed.
+    fn update_transitions(&self, path: &mut IdPath, cx: &mut Context, sizes: &[LocalSize]) {
+        let curve = match self.transition {
+            Some((_, curve)) => curve,
+            None => return,
+        };
+        let s = self.transitions(path, cx);
+
+        let hashes: Vec<u64> = self.ids.iter().map(hh).collect();
+        let new_sizes: HashMap<u64, LocalSize> =
+            hashes.iter().cloned().zip(sizes.iter().cloned()).collect();
+
+        let shown = match &cx[s].shown {
+            // Nothing animates on first appearance.
+            None => {
+                let state = &mut cx[s];
+                state.shown = Some(self.ids.clone());
+                state.sizes = new_sizes;
+                return;
+            }
+            Some(shown) => shown.clone(),
+        };
+        let shown_hashes: Vec<u64> = shown.iter().map(hh).collect();
+
+        let inserted: Vec<u64> = hashes
+            .iter()
+            .filter(|h| !shown_hashes.contains(h))
+            .cloned()
+            .collect();
+
+        let mut removed = vec![];
+        for (id, h) in shown.iter().zip(shown_hashes.iter()) {
+            if !hashes.contains(h) {
+                path.push(*h);
+                removed.push((id.clone(), cx.get_layout(path).offset));
+                path.pop();
+            }
+        }
+
+        if inserted.is_empty() && removed.is_empty() && cx[s].sizes == new_sizes {
+            return;
+        }
+
+        let state = &mut cx[s];
+        for h in inserted {
+            let size = new_sizes[&h];
+            state.removed.retain(|(id, _)| hh(id) != h);
+            match state.items.get_mut(&h) {
+                Some(item) => {
+                    item.size = size;
+                    item.reappear(curve);
+                }
+                None => {
+                    state
+                        .items
+                        .insert(h, TransitionProgress::appear(size, curve));
+                }
+            }
+        }
+        for (id, offset) in removed {
+            let h = hh(&id);
+            let size = state.sizes.get(&h).cloned().unwrap_or_default();
+            state
+                .items
+                .entry(h)
+                .or_insert_with(|| TransitionProgress::present(size))
+                .disappear(curve);
+            state.removed.push((id, offset));
+        }
+        state.shown = Some(self.ids.clone());
+        state.sizes = new_sizes;
+    }
+
+    fn draw_child(&self, child: &ID, offset: LocalOffset, path: &mut IdPath, args: &mut DrawArgs) {
+        args.vger.save();
+        args.vger.translate(offset);
+
+        let h = hh(child);
+        let item = self.transition.and_then(|(transition, _)| {
+            let s = self.transitions(path, args.cx);
+            args.cx[s].items.get(&h).map(|item| (transition, *item))
+        });
+
+        path.push(h);
+        match item {
+            Some((transition, item)) => {
+                transition.draw(item.progress.value, item.size, args, |args| {
+                    ((self.func)(child)).draw(path, args)
+                })
+            }
+            None => ((self.func)(child)).draw(path, args),
+        }
+        path.pop();
+
+        args.vger.restore();
+    }
 }
 
 impl<ID, V, F> DynView for List<ID, F>
@@ -29,6 +176,20 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
+        if let (Event::Anim, Some(_)) = (event, self.transition) {
+            let s = self.transitions(path, cx);
+            if cx[s].items.values().any(|item| item.progress.is_running()) {
+                let dt = cx.frame_time;
+                let state = &mut cx[s];
+                for item in state.items.values_mut() {
+                    item.progress.step(dt);
+                }
+                state.items.retain(|_, item| item.progress.is_running());
+                let items = &state.items;
+                state.removed.retain(|(id, _)| items.contains_key(&hh(id)));
+            }
+        }
+
         for child in self.ids.iter().rev() {
             path.push(hh(child));
             let offset = cx.get_layout(path).offset;
@@ -38,29 +199,31 @@ where
     }
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        if self.transition.is_some() {
+            let s = self.transitions(path, args.cx);
+            let removed = args.cx[s].removed.clone();
+            for (child, offset) in &removed {
+                self.draw_child(child, *offset, path, args);
+            }
+        }
+
         for child in &self.ids {
             path.push(hh(child));
             let offset = args.cx.get_layout(path).offset;
-
-            args.vger.save();
-
-            args.vger.translate(offset);
-
-            ((self.func)(child)).draw(path, args);
-
-            args.vger.restore();
             path.pop();
+
+            self.draw_child(child, offset, path, args);
         }
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
-        match self.orientation {
+        let mut sizes = Vec::<LocalSize>::with_capacity(self.ids.len());
+
+        let size = match self.orientation {
             ListOrientation::Horizontal => {
                 let n = self.ids.len() as f32;
                 let proposed_child_size = LocalSize::new(args.sz.width / n, args.sz.height);
 
-                let mut sizes = Vec::<LocalSize>::with_capacity(self.ids.len());
-
                 let mut width_sum = 0.0;
                 for child in &self.ids {
                     path.push(hh(child));
@@ -100,8 +263,6 @@ where
                 let n = self.ids.len() as f32;
                 let proposed_child_size = LocalSize::new(args.sz.width, args.sz.height / n);
 
-                let mut sizes = Vec::<LocalSize>::with_capacity(self.ids.len());
-
                 let mut height_sum = 0.0;
                 for child in &self.ids {
                     path.push(hh(child));
@@ -142,12 +303,18 @@ where
             ListOrientation::Z => {
                 for child in &self.ids {
                     path.push(hh(child));
-                    ((self.func)(child)).layout(path, args);
+                    sizes.push(((self.func)(child)).layout(path, args));
                     path.pop();
                 }
                 args.sz
             }
+        };
+
+        if self.transition.is_some() {
+            self.update_transitions(path, args.cx, &sizes);
         }
+
+        size
     }
 
     fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
@@ -184,7 +351,20 @@ where
 
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         map.push(cx.view_id(path));
-        for child in &self.ids {
+
+        // Keep items which are animating out, or are about to.
+        let mut leaving = vec![];
+        if self.transition.is_some() {
+            let s = self.transitions(path, cx);
+            let state = &cx[s];
+            leaving.extend(state.removed.iter().map(|(id, _)| id.clone()));
+            if let Some(shown) = &state.shown {
+                let hashes: Vec<u64> = self.ids.iter().map(hh).collect();
+                leaving.extend(shown.iter().filter(|id| !hashes.contains(&hh(id))).cloned());
+            }
+        }
+
+        for child in self.ids.iter().chain(leaving.iter()) {
             path.push(hh(child));
             map.push(cx.view_id(path));
             ((self.func)(child)).gc(path, cx, map);
@@ -196,7 +376,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         let mut builder = accesskit::NodeBuilder::new(accesskit::Role::List);
 
@@ -212,7 +392,7 @@ where
             .collect();
 
         builder.set_children(children);
-        nodes.push((cx.view_id(path).access_id(), builder.build()));
+        nodes.push((cx.view_id(path).access_id(), builder));
         Some(cx.view_id(path).access_id())
     }
 }
@@ -244,6 +424,7 @@ pub fn list<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(
         orientation: ListOrientation::Vertical,
         ids,
         func: f,
+        transition: None,
     }
 }
 
@@ -255,6 +436,7 @@ pub fn hlist<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(
         orientation: ListOrientation::Horizontal,
         ids,
         func: f,
+        transition: None,
     }
 }
 
@@ -266,6 +448,7 @@ pub fn zlist<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(
         orientation: ListOrientation::Z,
         ids,
         func: f,
+        transition: None,
     }
 }
 
@@ -368,4 +551,38 @@ mod tests {
         assert_eq!(result.height, 60.0);
         assert_eq!(result.width, 50.0);
     }
+
+    #[test]
+    fn test_list_transitions() {
+        let ui = state(
+            || vec![1, 2, 3],
+            |ids, cx| {
+                list(cx[ids].clone(), |_| rectangle().size([40.0, 20.0]))
+                    .transition(Transition::Slide([-40.0, 0.0].into()), Curve::Linear)
+            },
+        );
+        let mut h = TestHarness::new(&ui, [200.0, 200.0]);
+        let ids = h.root_state::<Vec<i32>>();
+        let s = StateHandle::<ListTransitions<i32>>::new(h.view_id(&[0, 0]));
+        assert!(h.cx[s].items.is_empty());
+
+        h.set(ids, vec![1, 3, 4]);
+        h.gc();
+        let state = &h.cx[s];
+        assert_eq!(state.removed.len(), 1);
+        assert_eq!(state.removed[0].0, 2);
+        assert_eq!(state.items[&hh(&2)].progress.to, 0.0);
+        assert_eq!(state.items[&hh(&4)].progress.to, 1.0);
+
+        // Putting an item back while it's leaving brings it back.
+        h.event(&Event::Anim);
+        h.set(ids, vec![1, 2, 3, 4]);
+        assert!(h.cx[s].removed.is_empty());
+        assert_eq!(h.cx[s].items[&hh(&2)].progress.to, 1.0);
+
+        for _ in 0..20 {
+            h.event(&Event::Anim);
+        }
+        assert!(h.cx[s].items.is_empty());
+    }
 }
@@ -0,0 +1,231 @@
+use crate::*;
+use std::any::Any;
+use std::time::Duration;
+use web_time::Instant;
+
+#[derive(Clone, Default)]
+struct LongPressState {
+    /// The touch being held: its id, where it began and when.
+    touch: Option<(usize, LocalPoint, Instant)>,
+
+    /// Has the press been held long enough?
+    fired: bool,
+}
+
+/// Struct for the `long_press` gesture.
+#[derive(Clone)]
+pub struct LongPress<V, F> {
+    child: V,
+    duration: Duration,
+    func: F,
+}
+
+impl<V, F> LongPress<V, F>
+where
+    V: View,
+    F: TapFn + 'static,
+{
+    pub fn new(child: V, duration: Duration, func: F) -> Self {
+        Self {
+            child,
+            duration,
+            func,
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<LongPressState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &LongPressState::default);
+        StateHandle::new(id)
+    }
+
+    /// Calls the function once the touch has been held for `duration`.
+    /// Views beneath which took the touch see it cancelled, so a held
+    /// button doesn't also click.
+    fn check(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        s: StateHandle<LongPressState>,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let (id, position, start) = match cx[s].touch {
+            Some(touch) if !cx[s].fired => touch,
+            _ => return,
+        };
+        if cx.now().duration_since(start) < self.duration {
+            // Keep frames coming until then.
+            cx.request_frame();
+            return;
+        }
+        cx[s].fired = true;
+        if !cx.touches[id].is_default() {
+            path.push(0);
+            self.child
+                .process(&Event::TouchCancel { id, position }, path, cx, actions);
+            path.pop();
+            cx.touches[id] = ViewId::default();
+        }
+        self.func.call(
+            cx,
+            TapInfo {
+                pt: position,
+                button: cx.mouse_button,
+                state: TouchState::Begin,
+            },
+            actions,
+        );
+    }
+}
+
+impl<V, F> DynView for LongPress<V, F>
+where
+    V: View,
+    F: TapFn + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        let held = |id: &usize, cx: &Context| cx[s].touch.is_some_and(|t| t.0 == *id);
+        match event {
+            Event::TouchBegin { id, position }
+                if cx[s].touch.is_none() && self.hittest(path, *position, cx).is_some() =>
+            {
+                cx[s].touch = Some((*id, *position, cx.now()));
+                cx[s].fired = false;
+            }
+            Event::TouchMove { id, position, .. } if held(id, cx) && !cx[s].fired => {
+                let thresholds = cx.init_env(&GestureThresholds::default);
+                let start = cx[s].touch.unwrap().1;
+                if (*position - start).length() > thresholds.tap_distance {
+                    cx[s].touch = None;
+                }
+            }
+            Event::TouchEnd { id, .. } | Event::TouchCancel { id, .. } if held(id, cx) => {
+                if let Event::TouchEnd { .. } = event {
+                    self.check(path, cx, s, actions);
+                }
+                cx[s].touch = None;
+            }
+            Event::Anim => self.check(path, cx, s, actions),
+            _ => (),
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F> private::Sealed for LongPress<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_press_beats_tap() {
+        let ui = state(Vec::new, |s, _| {
+            rectangle()
+                .tap(move |cx| cx[s].push("tap"))
+                .long_press(Duration::from_millis(500), move |cx| cx[s].push("long"))
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<Vec<&str>>();
+        let position = [50.0, 50.0].into();
+
+        // A quick tap is just a tap.
+        h.tap(position);
+        assert_eq!(*h.get(s), vec!["tap"]);
+
+        // Held long enough, it's only a long press, even before release.
+        h.event(&Event::TouchBegin { id: 0, position });
+        h.cx.dirty = false;
+        h.advance_time(Duration::from_millis(300));
+        assert_eq!(*h.get(s), vec!["tap"]);
+        assert!(h.cx.is_animating());
+        assert!(!h.cx.dirty);
+        h.advance_time(Duration::from_millis(300));
+        assert_eq!(*h.get(s), vec!["tap", "long"]);
+        h.event(&Event::TouchEnd { id: 0, position });
+        assert_eq!(*h.get(s), vec!["tap", "long"]);
+    }
+
+    #[test]
+    fn test_moving_cancels_long_press() {
+        let ui = state(
+            || 0,
+            |count, _| rectangle().long_press(Duration::from_secs(1), move |cx| cx[count] += 1),
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let count = h.root_state::<i32>();
+
+        h.event(&Event::TouchBegin {
+            id: 0,
+            position: [50.0, 50.0].into(),
+        });
+        h.event(&Event::TouchMove {
+            id: 0,
+            position: [80.0, 50.0].into(),
+            delta: [30.0, 0.0].into(),
+        });
+        h.advance_time(Duration::from_secs(2));
+        assert_eq!(*h.get(count), 0);
+    }
+}
@@ -1,5 +1,11 @@
+mod access_action;
+pub use access_action::*;
+mod access_node;
+pub use access_node::*;
 mod anim;
 pub use anim::*;
+mod animated_layout;
+pub use animated_layout::*;
 mod anyview;
 pub use anyview::*;
 mod background;
@@ -14,6 +20,8 @@ mod command;
 pub use command::*;
 mod cond;
 pub use cond::*;
+mod double_tap;
+pub use double_tap::*;
 mod drag;
 pub use drag::*;
 mod emptyview;
@@ -30,12 +38,18 @@ mod handle;
 pub use handle::*;
 mod hover;
 pub use hover::*;
+mod ime;
+pub use ime::*;
 mod key;
 pub use key::*;
 mod knob;
 pub use knob::*;
+mod lazy_list;
+pub use lazy_list::*;
 mod list;
 pub use list::*;
+mod long_press;
+pub use long_press::*;
 mod map;
 pub use map::*;
 mod modview;
@@ -44,12 +58,22 @@ mod offset;
 pub use offset::*;
 mod padding;
 pub use padding::*;
+mod pinch;
+pub use pinch::*;
+mod popover;
+pub use popover::*;
 mod redux;
 pub use redux::*;
+mod rich_text;
+pub use rich_text::*;
 mod role;
 pub use role::*;
+mod scroll;
+pub use scroll::*;
 mod shapes;
 pub use shapes::*;
+mod sheet;
+pub use sheet::*;
 mod size;
 pub use size::*;
 mod slider;
@@ -71,5 +95,9 @@ mod text;
 pub use text::*;
 mod toggle;
 pub use toggle::*;
+mod transform;
+pub use transform::*;
+mod transition;
+pub use transition::*;
 mod window;
 pub use window::*;
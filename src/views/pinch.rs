@@ -0,0 +1,476 @@
+use crate::*;
+use std::any::Any;
+use std::f32::consts::PI;
+
+pub trait PinchFn: Clone {
+    /// Does this follow the rotation, rather than the scale?
+    fn rotation(&self) -> bool {
+        false
+    }
+
+    fn call(
+        &self,
+        cx: &mut Context,
+        scale: f32,
+        angle: f32,
+        state: GestureState,
+        actions: &mut Vec<Box<dyn Any>>,
+    );
+}
+
+#[derive(Clone)]
+pub struct PinchFunc<F> {
+    pub f: F,
+}
+
+impl<A: 'static, F: Fn(&mut Context, f32, GestureState) -> A + Clone + 'static> PinchFn
+    for PinchFunc<F>
+{
+    fn call(
+        &self,
+        cx: &mut Context,
+        scale: f32,
+        _angle: f32,
+        state: GestureState,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        actions.push(Box::new((self.f)(cx, scale, state)))
+    }
+}
+
+#[derive(Clone)]
+pub struct MagnifyFunc<F> {
+    pub f: F,
+}
+
+impl<A: 'static, F: Fn(&mut Context, f32, GestureState) -> A + Clone + 'static> PinchFn
+    for MagnifyFunc<F>
+{
+    fn call(
+        &self,
+        cx: &mut Context,
+        scale: f32,
+        _angle: f32,
+        state: GestureState,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        actions.push(Box::new((self.f)(cx, scale - 1.0, state)))
+    }
+}
+
+#[derive(Clone)]
+pub struct RotateFunc<F> {
+    pub f: F,
+}
+
+impl<A: 'static, F: Fn(&mut Context, f32, GestureState) -> A + Clone + 'static> PinchFn
+    for RotateFunc<F>
+{
+    fn rotation(&self) -> bool {
+        true
+    }
+
+    fn call(
+        &self,
+        cx: &mut Context,
+        _scale: f32,
+        angle: f32,
+        state: GestureState,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        actions.push(Box::new((self.f)(cx, angle, state)))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct PinchState {
+    /// Touches which began on the view, at most two.
+    fingers: Vec<(usize, LocalPoint)>,
+
+    /// Is a gesture under way?
+    active: bool,
+
+    /// Distance between the fingers when the gesture began.
+    start_distance: f32,
+
+    /// Direction from the first finger to the second, last we looked.
+    direction: f32,
+
+    scale: f32,
+    angle: f32,
+}
+
+impl Default for PinchState {
+    fn default() -> Self {
+        Self {
+            fingers: vec![],
+            active: false,
+            start_distance: 0.0,
+            direction: 0.0,
+            scale: 1.0,
+            angle: 0.0,
+        }
+    }
+}
+
+impl PinchState {
+    fn begin(&mut self) {
+        let v = self.fingers[1].1 - self.fingers[0].1;
+        self.active = true;
+        self.start_distance = v.length();
+        self.direction = v.y.atan2(v.x);
+        self.scale = 1.0;
+        self.angle = 0.0;
+    }
+
+    /// Follows the fingers, keeping the angle continuous past half a turn.
+    fn update(&mut self) {
+        let v = self.fingers[1].1 - self.fingers[0].1;
+        if self.start_distance > 0.0 {
+            self.scale = v.length() / self.start_distance;
+        }
+        let direction = v.y.atan2(v.x);
+        let mut turn = direction - self.direction;
+        if turn > PI {
+            turn -= 2.0 * PI;
+        } else if turn < -PI {
+            turn += 2.0 * PI;
+        }
+        self.angle += turn;
+        self.direction = direction;
+    }
+}
+
+/// Struct for the `pinch`, `magnify` and `rotate_gesture` gestures. They
+/// follow two fingers on the view, or a trackpad gesture over it.
+#[derive(Clone)]
+pub struct Pinch<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> Pinch<V, F>
+where
+    V: View,
+    F: PinchFn + 'static,
+{
+    pub fn new(child: V, func: F) -> Self {
+        Self { child, func }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<PinchState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &PinchState::default);
+        StateHandle::new(id)
+    }
+
+    fn call(
+        &self,
+        cx: &mut Context,
+        s: StateHandle<PinchState>,
+        state: GestureState,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let (scale, angle) = (cx[s].scale, cx[s].angle);
+        self.func.call(cx, scale, angle, state, actions)
+    }
+
+    /// Starts a gesture once a second finger is down. Views beneath which
+    /// took either finger see it cancelled.
+    fn begin_touches(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        s: StateHandle<PinchState>,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        for (id, position) in cx[s].fingers.clone() {
+            if !cx.touches[id].is_default() {
+                path.push(0);
+                self.child
+                    .process(&Event::TouchCancel { id, position }, path, cx, actions);
+                path.pop();
+                cx.touches[id] = ViewId::default();
+            }
+        }
+        cx[s].begin();
+        self.call(cx, s, GestureState::Began, actions);
+    }
+
+    /// Does a trackpad gesture event belong to this view? One that begins
+    /// over the view starts a gesture, which gets the rest of the events.
+    fn trackpad(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        s: StateHandle<PinchState>,
+        position: LocalPoint,
+        state: GestureState,
+    ) -> bool {
+        if state == GestureState::Began {
+            if cx[s].active || self.hittest(path, position, cx).is_none() {
+                return false;
+            }
+            cx[s].active = true;
+            cx[s].scale = 1.0;
+            cx[s].angle = 0.0;
+            return true;
+        }
+        cx[s].active && cx[s].fingers.is_empty()
+    }
+}
+
+impl<V, F> DynView for Pinch<V, F>
+where
+    V: View,
+    F: PinchFn + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        let finger = |id: usize, cx: &Context| cx[s].fingers.iter().position(|f| f.0 == id);
+        match event {
+            Event::TouchBegin { id, position } if self.hittest(path, *position, cx).is_some() => {
+                path.push(0);
+                self.child.process(event, path, cx, actions);
+                path.pop();
+
+                if !cx[s].active && cx[s].fingers.len() < 2 {
+                    cx[s].fingers.push((*id, *position));
+                    if cx[s].fingers.len() == 2 {
+                        self.begin_touches(path, cx, s, actions);
+                    }
+                }
+                return;
+            }
+            Event::TouchMove { id, position, .. } => {
+                if let Some(i) = finger(*id, cx) {
+                    cx[s].fingers[i].1 = *position;
+                    if cx[s].active {
+                        cx[s].update();
+                        self.call(cx, s, GestureState::Changed, actions);
+                    }
+                }
+            }
+            Event::TouchEnd { id, .. } | Event::TouchCancel { id, .. } => {
+                if let Some(i) = finger(*id, cx) {
+                    cx[s].fingers.remove(i);
+                    if cx[s].active {
+                        // Lifting either finger ends the gesture.
+                        cx[s].active = false;
+                        cx[s].fingers.clear();
+                        let state = match event {
+                            Event::TouchEnd { .. } => GestureState::Ended,
+                            _ => GestureState::Cancelled,
+                        };
+                        self.call(cx, s, state, actions);
+                    }
+                }
+            }
+            Event::Magnify {
+                delta,
+                position,
+                state,
+            }
+            | Event::Rotate {
+                delta,
+                position,
+                state,
+            } if matches!(event, Event::Rotate { .. }) == self.func.rotation()
+                && self.trackpad(path, cx, s, *position, *state) =>
+            {
+                if self.func.rotation() {
+                    cx[s].angle += delta;
+                } else {
+                    cx[s].scale *= 1.0 + delta;
+                }
+                self.call(cx, s, *state, actions);
+                cx[s].active = *state == GestureState::Began || *state == GestureState::Changed;
+            }
+            _ => (),
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F> private::Sealed for Pinch<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch_begin(h: &mut TestHarness<'_, impl View>, id: usize, position: [f32; 2]) {
+        h.event(&Event::TouchBegin {
+            id,
+            position: position.into(),
+        });
+    }
+
+    fn touch_move(h: &mut TestHarness<'_, impl View>, id: usize, position: [f32; 2]) {
+        h.event(&Event::TouchMove {
+            id,
+            position: position.into(),
+            delta: LocalOffset::zero(),
+        });
+    }
+
+    fn touch_end(h: &mut TestHarness<'_, impl View>, id: usize, position: [f32; 2]) {
+        h.event(&Event::TouchEnd {
+            id,
+            position: position.into(),
+        });
+    }
+
+    #[test]
+    fn test_two_finger_pinch_and_rotate() {
+        let ui = state(Vec::new, |s, _| {
+            rectangle()
+                .pinch(move |cx, scale, state| cx[s].push(("pinch", scale, state)))
+                .rotate_gesture(move |cx, angle, state| cx[s].push(("rotate", angle, state)))
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<Vec<(&str, f32, GestureState)>>();
+
+        // One finger isn't a pinch.
+        touch_begin(&mut h, 1, [40.0, 50.0]);
+        touch_move(&mut h, 1, [30.0, 50.0]);
+        assert!(h.get(s).is_empty());
+
+        // Spread the fingers to twice as far apart, then turn them a
+        // quarter turn counter-clockwise.
+        touch_begin(&mut h, 2, [70.0, 50.0]);
+        touch_move(&mut h, 2, [110.0, 50.0]);
+        touch_move(&mut h, 2, [30.0, 130.0]);
+        touch_end(&mut h, 1, [30.0, 50.0]);
+
+        let events = h.get(s).clone();
+        let find = |name, state| {
+            events
+                .iter()
+                .filter(|e| e.0 == name && e.2 == state)
+                .map(|e| e.1)
+                .collect::<Vec<f32>>()
+        };
+        assert_eq!(find("pinch", GestureState::Began), vec![1.0]);
+        assert_eq!(find("pinch", GestureState::Changed), vec![2.0, 2.0]);
+        assert_eq!(find("pinch", GestureState::Ended), vec![2.0]);
+
+        let angles = find("rotate", GestureState::Changed);
+        assert_eq!(angles[0], 0.0);
+        assert!((angles[1] - PI / 2.0).abs() < 1e-5);
+        assert_eq!(find("rotate", GestureState::Ended).len(), 1);
+    }
+
+    #[test]
+    fn test_pinch_cancels_drag() {
+        let ui = state(Vec::new, |s, _| {
+            rectangle()
+                .drag(move |cx, _, state, _| cx[s].push(state))
+                .magnify(|_, _, _| ())
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<Vec<GestureState>>();
+
+        // Each finger starts a drag, until the second makes it a pinch.
+        touch_begin(&mut h, 1, [40.0, 50.0]);
+        touch_begin(&mut h, 2, [60.0, 50.0]);
+        touch_move(&mut h, 2, [70.0, 50.0]);
+        use GestureState::*;
+        assert_eq!(*h.get(s), vec![Began, Began, Cancelled, Cancelled]);
+        assert!(h.cx.touches.iter().all(|t| t.is_default()));
+    }
+
+    #[test]
+    fn test_trackpad_magnify() {
+        let ui = state(
+            || 0.0,
+            |m, _| {
+                rectangle()
+                    .size([50.0, 50.0])
+                    .magnify(move |cx, magnification, _| cx[m] = magnification)
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let m = h.root_state::<f32>();
+
+        let magnify = |h: &mut TestHarness<_>, delta, x, state| {
+            h.event(&Event::Magnify {
+                delta,
+                position: [x, 10.0].into(),
+                state,
+            })
+        };
+
+        // Gestures which begin elsewhere are ignored.
+        magnify(&mut h, 0.0, 80.0, GestureState::Began);
+        magnify(&mut h, 0.5, 80.0, GestureState::Changed);
+        assert_eq!(*h.get(m), 0.0);
+
+        magnify(&mut h, 0.0, 10.0, GestureState::Began);
+        magnify(&mut h, 0.5, 80.0, GestureState::Changed);
+        magnify(&mut h, 1.0, 80.0, GestureState::Changed);
+        assert_eq!(*h.get(m), 2.0);
+        magnify(&mut h, 0.0, 80.0, GestureState::Ended);
+        magnify(&mut h, 1.0, 80.0, GestureState::Changed);
+        assert_eq!(*h.get(m), 2.0);
+    }
+}
@@ -0,0 +1,616 @@
+use crate::*;
+use std::any::Any;
+use std::sync::Arc;
+use web_time::Instant;
+
+/// Where a view drew itself, in window coordinates, for anchoring overlays.
+fn anchor_rect(path: &IdPath, args: &mut DrawArgs) -> WorldRect {
+    let rect = args.cx.get_layout(path).rect;
+    args.vger.current_transform().outer_transformed_rect(&rect)
+}
+
+/// Lays out a child and records its rect, so overlays can be anchored to it.
+fn layout_anchor(child: &impl View, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+    path.push(0);
+    let sz = child.layout(path, args);
+    path.pop();
+
+    let offset = args.cx.get_layout(path).offset;
+    args.cx.update_layout(
+        path,
+        LayoutBox {
+            rect: LocalRect::new(LocalPoint::origin(), sz),
+            offset,
+        },
+    );
+    sz
+}
+
+/// Struct for the `popover` modifier.
+#[derive(Clone)]
+pub struct Popover<V, B> {
+    child: V,
+    shown: B,
+    content: AnyView,
+}
+
+impl<V, B> Popover<V, B>
+where
+    V: View,
+    B: Binding<bool>,
+{
+    pub fn new(child: V, shown: B, content: impl View) -> Self {
+        Self {
+            child,
+            shown,
+            content: any_view(content),
+        }
+    }
+}
+
+impl<V, B> DynView for Popover<V, B>
+where
+    V: View,
+    B: Binding<bool>,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+
+        // Everything else reaches the content through the overlay layer.
+        if matches!(event, Event::Anim | Event::Command(_)) && *self.shown.get(cx) {
+            path.push(1);
+            self.content.process(event, path, cx, actions);
+            path.pop();
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+
+        if *self.shown.get(args.cx) {
+            let anchor = anchor_rect(path, args);
+            path.push(1);
+            args.cx.anchor_overlay(path, anchor);
+            path.pop();
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let sz = layout_anchor(&self.child, path, args);
+        if *self.shown.get(args.cx) {
+            let shown = self.shown;
+            path.push(1);
+            args.cx.show_overlay(
+                path,
+                self.content.clone(),
+                LocalRect::new(LocalPoint::origin(), sz),
+                Arc::new(move |cx| *shown.get_mut(cx) = false),
+                true,
+            );
+            path.pop();
+        }
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+        if *self.shown.get(cx) {
+            path.push(1);
+            self.content.gc(path, cx, map);
+            path.pop();
+        }
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, B> private::Sealed for Popover<V, B> {}
+
+/// An entry in a context menu, or a button in an alert.
+#[derive(Clone)]
+pub struct MenuItem {
+    pub(crate) title: String,
+    pub(crate) action: Arc<dyn Fn(&mut Context)>,
+}
+
+impl MenuItem {
+    pub fn new(title: &str, action: impl Fn(&mut Context) + 'static) -> Self {
+        Self {
+            title: title.into(),
+            action: Arc::new(action),
+        }
+    }
+}
+
+/// Struct for the `context_menu` modifier.
+#[derive(Clone)]
+pub struct ContextMenu<V> {
+    child: V,
+    items: Vec<MenuItem>,
+}
+
+impl<V> ContextMenu<V>
+where
+    V: View,
+{
+    pub fn new(child: V, items: Vec<MenuItem>) -> Self {
+        Self { child, items }
+    }
+
+    /// Where the menu was opened, if it's open.
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<Option<LocalPoint>> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &|| None::<LocalPoint>);
+        StateHandle::new(id)
+    }
+
+    fn menu(&self, s: StateHandle<Option<LocalPoint>>) -> AnyView {
+        let items = self.items.clone();
+        any_view(
+            list((0..items.len()).collect(), move |i| {
+                let item = items[*i].clone();
+                text(&item.title)
+                    .padding(Auto)
+                    .background(rectangle().color(CLEAR_COLOR))
+                    .tap(move |cx| {
+                        cx[s] = None;
+                        (item.action)(cx)
+                    })
+            })
+            .background(
+                rectangle()
+                    .color(BUTTON_BACKGROUND_COLOR)
+                    .corner_radius(5.0),
+            ),
+        )
+    }
+}
+
+impl<V> DynView for ContextMenu<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        if let Event::TouchBegin { position, .. } = event {
+            if cx.mouse_button == Some(MouseButton::Right)
+                && self.hittest(path, *position, cx).is_some()
+            {
+                cx[s] = Some(*position);
+                return;
+            }
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+
+        if matches!(event, Event::Anim | Event::Command(_)) && cx[s].is_some() {
+            path.push(1);
+            self.menu(s).process(event, path, cx, actions);
+            path.pop();
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+
+        let s = self.state(path, args.cx);
+        if let Some(pt) = args.cx[s] {
+            let anchor = WorldRect::new(
+                args.vger.current_transform().transform_point(pt),
+                [0.0, 0.0].into(),
+            );
+            path.push(1);
+            args.cx.anchor_overlay(path, anchor);
+            path.pop();
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let sz = layout_anchor(&self.child, path, args);
+        let s = self.state(path, args.cx);
+        if let Some(pt) = args.cx[s] {
+            path.push(1);
+            args.cx.show_overlay(
+                path,
+                self.menu(s),
+                LocalRect::new(pt, [0.0, 0.0].into()),
+                Arc::new(move |cx| cx[s] = None),
+                true,
+            );
+            path.pop();
+        }
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        let s = self.state(path, cx);
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+        if cx[s].is_some() {
+            path.push(1);
+            self.menu(s).gc(path, cx, map);
+            path.pop();
+        }
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for ContextMenu<V> {}
+
+/// How long the mouse has to rest on a view before its tooltip shows,
+/// in seconds.
+pub const TOOLTIP_DELAY: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TooltipState {
+    Idle,
+    /// The mouse has been over the view since then.
+    Hovering(Instant),
+    Shown,
+    /// Hidden by a click or Escape, until the mouse leaves.
+    Dismissed,
+}
+
+/// Struct for the `tooltip` modifier.
+#[derive(Clone)]
+pub struct Tooltip<V> {
+    child: V,
+    text: String,
+}
+
+impl<V> Tooltip<V>
+where
+    V: View,
+{
+    pub fn new(child: V, text: &str) -> Self {
+        Self {
+            child,
+            text: text.into(),
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<TooltipState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &|| TooltipState::Idle);
+        StateHandle::new(id)
+    }
+
+    fn bubble(&self) -> AnyView {
+        any_view(
+            text(&self.text)
+                .font_size(14)
+                .padding(5.0)
+                .background(rectangle().color(CONTROL_BACKGROUND).corner_radius(3.0)),
+        )
+    }
+}
+
+impl<V> DynView for Tooltip<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        match event {
+            Event::PointerEnter(ids)
+                if ids.contains(&cx.view_id(path)) && cx[s] == TooltipState::Idle =>
+            {
+                cx[s] = TooltipState::Hovering(cx.now())
+            }
+            Event::PointerLeave(ids) if ids.contains(&cx.view_id(path)) => {
+                cx[s] = TooltipState::Idle
+            }
+            Event::Anim => {
+                if let TooltipState::Hovering(start) = cx[s] {
+                    if cx.now().duration_since(start).as_secs_f32() >= TOOLTIP_DELAY {
+                        cx[s] = TooltipState::Shown;
+                    } else {
+                        // Keep frames coming until then.
+                        cx.request_frame();
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+
+        let s = self.state(path, args.cx);
+        if args.cx[s] == TooltipState::Shown {
+            let anchor = anchor_rect(path, args);
+            path.push(1);
+            args.cx.anchor_overlay(path, anchor);
+            path.pop();
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let sz = layout_anchor(&self.child, path, args);
+        let s = self.state(path, args.cx);
+        if args.cx[s] == TooltipState::Shown {
+            path.push(1);
+            args.cx.show_overlay(
+                path,
+                self.bubble(),
+                LocalRect::new(LocalPoint::origin(), sz),
+                Arc::new(move |cx| cx[s] = TooltipState::Dismissed),
+                false,
+            );
+            path.pop();
+        }
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        let s = self.state(path, cx);
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+        if cx[s] == TooltipState::Shown {
+            path.push(1);
+            self.bubble().gc(path, cx, map);
+            path.pop();
+        }
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for Tooltip<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_context_menu_opens_on_right_click() {
+        let ui = state(
+            || 0,
+            |s, _| {
+                rectangle()
+                    .size([50.0, 50.0])
+                    .context_menu(vec![MenuItem::new("Bump", move |cx| cx[s] += 1)])
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let open = StateHandle::<Option<LocalPoint>>::new(h.view_id(&[0, 0]));
+
+        h.tap([10.0, 10.0]);
+        assert_eq!(*h.get(open), None);
+
+        h.cx.mouse_button = Some(MouseButton::Right);
+        h.tap([10.0, 20.0]);
+        assert_eq!(*h.get(open), Some([10.0, 20.0].into()));
+    }
+
+    #[test]
+    fn test_menu_item_runs_action_and_closes() {
+        let ui = state(
+            || 0,
+            |s, _| {
+                rectangle()
+                    .size([100.0, 100.0])
+                    .context_menu(vec![MenuItem::new("Bump", move |cx| cx[s] += 1)])
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let count = h.root_state::<i32>();
+
+        h.cx.mouse_button = Some(MouseButton::Right);
+        h.tap([10.0, 90.0]);
+        h.cx.mouse_button = None;
+
+        // The menu opens below the click.
+        assert_eq!(h.cx.overlays.len(), 1);
+        let menu = h.cx.overlays[0].rect;
+        assert_eq!(menu.min_x(), 10.0);
+        assert_eq!(menu.max_y(), 90.0);
+
+        h.tap(menu.center().cast_unit());
+        assert_eq!(*h.get(count), 1);
+        assert!(h.cx.overlays.is_empty());
+    }
+
+    #[test]
+    fn test_popover_placement() {
+        let ui = state(
+            || false,
+            |top, _| {
+                state(
+                    || false,
+                    move |bottom, _| {
+                        vstack((
+                            rectangle()
+                                .size([20.0, 20.0])
+                                .popover(top, rectangle().size([30.0, 10.0])),
+                            spacer(),
+                            rectangle()
+                                .size([20.0, 20.0])
+                                .popover(bottom, rectangle().size([30.0, 10.0])),
+                        ))
+                    },
+                )
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let top = h.root_state::<bool>();
+        let bottom = h.state::<bool>(&[0, 0]);
+
+        // Below the anchor, left edges aligned.
+        h.set(top, true);
+        h.layout();
+        let anchor = h.root_rect(&[0, 0, 0, 0]).unwrap();
+        assert_eq!(h.cx.overlays.len(), 1);
+        let rect = h.cx.overlays[0].rect;
+        assert_eq!(rect.size, [30.0, 10.0].into());
+        assert_eq!(rect.min_x(), anchor.min_x());
+        assert_eq!(rect.max_y(), anchor.min_y());
+
+        // Clicks inside go to the popover, and outside close it.
+        h.tap(rect.center().cast_unit());
+        assert!(*h.get(top));
+        h.tap([90.0, 50.0]);
+        assert!(!*h.get(top));
+        assert!(h.cx.overlays.is_empty());
+
+        // No room below the bottom one, so it flips above.
+        h.set(bottom, true);
+        h.layout();
+        let anchor = h.root_rect(&[0, 0, 0, 2]).unwrap();
+        let rect = h.cx.overlays[0].rect;
+        assert_eq!(rect.min_x(), anchor.min_x());
+        assert_eq!(rect.min_y(), anchor.max_y());
+    }
+
+    #[test]
+    fn test_tooltip_shows_after_delay() {
+        let ui = rectangle().size([50.0, 50.0]).tooltip("Hello");
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = StateHandle::<TooltipState>::new(h.view_id(&[0]));
+
+        h.pointer_move([10.0, 10.0]);
+        assert!(matches!(*h.get(s), TooltipState::Hovering(_)));
+
+        // Waiting asks for frames without dirtying the tree.
+        h.cx.dirty = false;
+        h.advance_time(Duration::from_secs_f32(TOOLTIP_DELAY / 2.0));
+        assert!(matches!(*h.get(s), TooltipState::Hovering(_)));
+        assert!(h.cx.is_animating());
+        assert!(!h.cx.dirty);
+
+        h.advance_time(Duration::from_secs_f32(TOOLTIP_DELAY / 2.0));
+        assert_eq!(*h.get(s), TooltipState::Shown);
+
+        h.pointer_move([80.0, 80.0]);
+        assert_eq!(*h.get(s), TooltipState::Idle);
+    }
+}
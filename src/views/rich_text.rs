@@ -0,0 +1,535 @@
+use crate::*;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// How far italic glyphs lean when faked: the sideways shift per unit of
+/// height, about 11 degrees.
+const ITALIC_SLANT: f32 = 0.2;
+
+/// A run of text sharing the same attributes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub color: Option<Color>,
+    pub size: Option<u32>,
+    pub underline: bool,
+    pub strikethrough: bool,
+    /// Data handed to the `on_link` callback when the span is tapped.
+    pub link: Option<String>,
+}
+
+/// Creates a span of plain text.
+pub fn span(text: &str) -> Span {
+    Span {
+        text: text.into(),
+        bold: false,
+        italic: false,
+        color: None,
+        size: None,
+        underline: false,
+        strikethrough: false,
+        link: None,
+    }
+}
+
+impl Span {
+    pub fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    pub fn italic(self) -> Self {
+        Self {
+            italic: true,
+            ..self
+        }
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    pub fn size(self, size: u32) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    pub fn underline(self) -> Self {
+        Self {
+            underline: true,
+            ..self
+        }
+    }
+
+    pub fn strikethrough(self) -> Self {
+        Self {
+            strikethrough: true,
+            ..self
+        }
+    }
+
+    /// Makes the span a link. Links are underlined and drawn in
+    /// `LINK_COLOR` unless given a color.
+    pub fn link(self, data: &str) -> Self {
+        Self {
+            link: Some(data.into()),
+            ..self
+        }
+    }
+
+    fn font_size(&self) -> u32 {
+        self.size.unwrap_or(Text::DEFAULT_SIZE)
+    }
+
+    /// The face of `font` to draw the span in.
+    fn font(&self, font: Font) -> Font {
+        Font {
+            weight: if self.bold {
+                FontWeight::BOLD
+            } else {
+                font.weight
+            },
+            style: if self.italic {
+                FontStyle::Italic
+            } else {
+                font.style
+            },
+            ..font
+        }
+    }
+
+    fn text_color(&self) -> Color {
+        match (self.color, &self.link) {
+            (Some(color), _) => color,
+            (None, Some(_)) => LINK_COLOR,
+            (None, None) => TEXT_COLOR,
+        }
+    }
+}
+
+/// Text made of spans with different attributes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttributedString {
+    pub spans: Vec<Span>,
+}
+
+impl AttributedString {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a span.
+    pub fn push(mut self, span: Span) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    /// The text without attributes.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|s| s.text.as_str()).collect()
+    }
+}
+
+impl From<Vec<Span>> for AttributedString {
+    fn from(spans: Vec<Span>) -> Self {
+        Self { spans }
+    }
+}
+
+impl From<Span> for AttributedString {
+    fn from(span: Span) -> Self {
+        Self { spans: vec![span] }
+    }
+}
+
+impl From<&str> for AttributedString {
+    fn from(text: &str) -> Self {
+        span(text).into()
+    }
+}
+
+/// A word of a span, placed on a line.
+#[derive(Clone, Debug, Default)]
+struct Run {
+    span: usize,
+    line: usize,
+    text: String,
+    /// Where to translate before drawing the text.
+    offset: LocalOffset,
+    /// The word's width, then with its trailing spaces.
+    width: f32,
+    advance: f32,
+    baseline: f32,
+    /// Covers the run's advance and the height of its line.
+    rect: LocalRect,
+}
+
+#[derive(Clone, Debug, Default)]
+struct RichTextState {
+    runs: Vec<Run>,
+    pressed: Option<usize>,
+}
+
+type LinkFn = Arc<dyn Fn(&mut Context, &str)>;
+type SpanTapFn = Arc<dyn Fn(&mut Context, usize)>;
+
+/// Struct for `rich_text`.
+#[derive(Clone)]
+pub struct RichText {
+    string: AttributedString,
+    max_width: Option<f32>,
+    on_link: Option<LinkFn>,
+    on_span_tap: Option<SpanTapFn>,
+}
+
+impl RichText {
+    /// Wraps lines between words so they're no wider than `max_width`.
+    pub fn max_width(self, max_width: f32) -> Self {
+        Self {
+            max_width: Some(max_width),
+            ..self
+        }
+    }
+
+    /// Calls a function with the link's data when a link is tapped.
+    pub fn on_link(self, f: impl Fn(&mut Context, &str) + 'static) -> Self {
+        Self {
+            on_link: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
+    /// Calls a function with the index of the span which was tapped.
+    pub fn on_span_tap(self, f: impl Fn(&mut Context, usize) + 'static) -> Self {
+        Self {
+            on_span_tap: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<RichTextState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &RichTextState::default);
+        StateHandle::new(id)
+    }
+
+    /// Returns the index of the span at `pt`, as of the last layout.
+    pub fn span_at(&self, path: &IdPath, pt: LocalPoint, cx: &mut Context) -> Option<usize> {
+        let s = self.state(path, cx);
+        cx[s]
+            .runs
+            .iter()
+            .find(|run| run.rect.contains(pt))
+            .map(|run| run.span)
+    }
+
+    /// Is there a callback for a tap on span `i`?
+    fn tappable(&self, i: usize) -> bool {
+        self.on_span_tap.is_some()
+            || (self.on_link.is_some() && self.string.spans[i].link.is_some())
+    }
+
+    /// Breaks the spans into words and places them on lines, top down.
+    fn layout_runs(
+        &self,
+        font: Font,
+        text_bounds: &mut dyn FnMut(Font, &str, u32) -> LocalRect,
+    ) -> (Vec<Run>, LocalSize) {
+        // Glyph bounds leave out spaces, and start at the lowest descender,
+        // so measure a space and where the baseline is for each face and size.
+        let mut metrics = HashMap::new();
+        let mut metrics_for =
+            |font: Font, size: u32, text_bounds: &mut dyn FnMut(Font, &str, u32) -> LocalRect| {
+                *metrics.entry((font, size)).or_insert_with(|| {
+                    let space = text_bounds(font, "x x", size).width()
+                        - text_bounds(font, "xx", size).width();
+                    (space, text_bounds(font, "x", size).min_y())
+                })
+            };
+
+        let mut runs = vec![];
+        // Ascent and descent of each line.
+        let mut lines = vec![(0.0f32, 0.0f32)];
+        let mut x = 0.0;
+        let mut width = 0.0f32;
+
+        for (i, span) in self.string.spans.iter().enumerate() {
+            let size = span.font_size();
+            let font = span.font(font);
+            let (space, baseline) = metrics_for(font, size, text_bounds);
+
+            for (n, segment) in span.text.split('\n').enumerate() {
+                if n > 0 {
+                    lines.push((0.0, 0.0));
+                    x = 0.0;
+                }
+                for token in segment.split_inclusive(' ') {
+                    let word = token.trim_end_matches(' ');
+                    let spaces = (token.len() - word.len()) as f32 * space;
+                    let (bounds, ascent, descent) = if word.is_empty() {
+                        (LocalRect::zero(), 0.0, 0.0)
+                    } else {
+                        let b = text_bounds(font, word, size);
+                        (b, b.max_y() - baseline, baseline - b.min_y())
+                    };
+
+                    if let Some(max_width) = self.max_width {
+                        if x > 0.0 && x + bounds.width() > max_width {
+                            lines.push((0.0, 0.0));
+                            x = 0.0;
+                        }
+                    }
+
+                    let line = lines.len() - 1;
+                    lines[line].0 = lines[line].0.max(ascent).max(size as f32 * 0.5);
+                    lines[line].1 = lines[line].1.max(descent);
+                    runs.push(Run {
+                        span: i,
+                        line,
+                        text: word.into(),
+                        offset: [x - bounds.origin.x, -baseline].into(),
+                        width: bounds.width(),
+                        advance: bounds.width() + spaces,
+                        ..Run::default()
+                    });
+                    width = width.max(x + bounds.width());
+                    x += bounds.width() + spaces;
+                }
+            }
+        }
+
+        // Now that line heights are known, find each baseline.
+        let height: f32 = lines.iter().map(|(a, d)| a + d).sum();
+        let mut top = height;
+        let mut baselines = vec![];
+        for (ascent, descent) in &lines {
+            baselines.push(top - ascent);
+            top -= ascent + descent;
+        }
+        for run in &mut runs {
+            let (ascent, descent) = lines[run.line];
+            run.baseline = baselines[run.line];
+            run.offset.y += run.baseline;
+            run.rect = LocalRect::new(
+                [run.offset.x, run.baseline - descent].into(),
+                [run.advance, ascent + descent].into(),
+            );
+        }
+
+        (runs, [width, height].into())
+    }
+}
+
+/// Shows text made of styled spans.
+///
+/// Bold and italic spans use those faces of the font from the environment.
+/// vger's built-in font has neither, so there bold is drawn by overstriking
+/// and italic by slanting the upright glyphs.
+pub fn rich_text(string: impl Into<AttributedString>) -> RichText {
+    RichText {
+        string: string.into(),
+        max_width: None,
+        on_link: None,
+        on_span_tap: None,
+    }
+}
+
+impl DynView for RichText {
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        _actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        let s = self.state(path, cx);
+        match &event {
+            Event::TouchBegin { id, position } => {
+                if let Some(i) = self.span_at(path, *position, cx) {
+                    if self.tappable(i) {
+                        cx.touches[*id] = vid;
+                        cx[s].pressed = Some(i);
+                    }
+                }
+            }
+            Event::TouchEnd { id, position } if cx.touches[*id] == vid => {
+                cx.touches[*id] = ViewId::default();
+                let pressed = cx[s].pressed.take();
+                if let Some(i) = pressed.filter(|i| self.span_at(path, *position, cx) == Some(*i)) {
+                    if let Some(f) = &self.on_span_tap {
+                        f(cx, i)
+                    }
+                    if let (Some(f), Some(link)) = (&self.on_link, &self.string.spans[i].link) {
+                        f(cx, link)
+                    }
+                }
+            }
+            Event::TouchCancel { id, .. } if cx.touches[*id] == vid => {
+                cx.touches[*id] = ViewId::default();
+                cx[s].pressed = None;
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let s = self.state(path, args.cx);
+        let runs = args.cx[s].runs.clone();
+        let font = args.cx.font();
+
+        for (n, run) in runs.iter().enumerate() {
+            let span = &self.string.spans[run.span];
+            let size = span.font_size();
+            let font = span.font(font);
+            let color = args.cx.apply_opacity(span.text_color());
+
+            let builtin = args.cx.fonts.face(font).is_none();
+
+            args.vger.save();
+            args.vger.translate(run.offset);
+            if span.italic && builtin {
+                // Lean the glyphs right about the baseline.
+                let baseline = run.baseline - run.offset.y;
+                let slant = LocalToLocal::translation(0.0, -baseline)
+                    .then(&LocalToLocal::new(1.0, 0.0, ITALIC_SLANT, 1.0, 0.0, 0.0))
+                    .then_translate([0.0, baseline].into());
+                apply_transform(args.vger, &slant);
+            }
+            args.font_text(font, &run.text, size, color, None);
+            if span.bold && builtin {
+                args.vger.translate([size as f32 / 24.0, 0.0]);
+                args.font_text(font, &run.text, size, color, None);
+            }
+            args.vger.restore();
+            let vger = &mut args.vger;
+
+            // Decorations run under the spaces between words of a span.
+            let continues = runs
+                .get(n + 1)
+                .is_some_and(|next| next.span == run.span && next.line == run.line);
+            let width = if continues { run.advance } else { run.width };
+            let thickness = (size as f32 / 16.0).max(1.0);
+            let paint = vger.color_paint(color);
+            if span.underline || span.link.is_some() {
+                vger.fill_rect(
+                    LocalRect::new(
+                        [run.rect.min_x(), run.baseline - 2.0 * thickness].into(),
+                        [width, thickness].into(),
+                    ),
+                    0.0,
+                    paint,
+                );
+            }
+            if span.strikethrough {
+                vger.fill_rect(
+                    LocalRect::new(
+                        [run.rect.min_x(), run.baseline + size as f32 * 0.3].into(),
+                        [width, thickness].into(),
+                    ),
+                    0.0,
+                    paint,
+                );
+            }
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let font = args.cx.font();
+        let (runs, size) = self.layout_runs(font, &mut |font, text, size| {
+            args.font_text_bounds(font, text, size, None)
+        });
+        let s = self.state(path, args.cx);
+        args.cx[s].runs = runs;
+        size
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        match self.span_at(path, pt, cx) {
+            Some(i) if self.tappable(i) => Some(cx.view_id(path)),
+            _ => None,
+        }
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = cx.view_id(path).access_id();
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Label);
+        builder.set_name(self.string.text());
+        nodes.push((aid, builder));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for RichText {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string() -> AttributedString {
+        AttributedString::new()
+            .push(span("plain "))
+            .push(span("bold").bold().color(RED_HIGHLIGHT))
+            .push(span(" see "))
+            .push(span("docs").link("https://example.com"))
+    }
+
+    #[test]
+    fn test_layout() {
+        let mut measure = |_, text: &str, size| ApproxTextMeasure.text_bounds(text, size, None);
+        // "plain bold see docs" at 9 per char.
+        let (runs, size) = rich_text(string()).layout_runs(Font::default(), &mut measure);
+        assert_eq!(size, [171.0, 18.0].into());
+        assert_eq!(runs.last().unwrap().rect.min_x(), 135.0);
+
+        let (runs, size) = rich_text(string())
+            .max_width(100.0)
+            .layout_runs(Font::default(), &mut measure);
+        assert_eq!(size, [90.0, 36.0].into());
+        assert_eq!(runs.last().unwrap().line, 1);
+    }
+
+    #[test]
+    fn test_tap_link() {
+        let ui = state(
+            || (String::new(), None),
+            |s, _| {
+                rich_text(string())
+                    .on_link(move |cx, link| cx[s].0 = link.into())
+                    .on_span_tap(move |cx, i| cx[s].1 = Some(i))
+            },
+        );
+        let mut h = TestHarness::new(&ui, [171.0, 18.0]);
+        let s = h.root_state::<(String, Option<usize>)>();
+
+        h.tap([60.0, 9.0]);
+        assert_eq!(*h.get(s), (String::new(), Some(1)));
+
+        h.tap([160.0, 9.0]);
+        assert_eq!(*h.get(s), ("https://example.com".to_string(), Some(3)));
+    }
+
+    #[test]
+    fn test_only_links_are_hit() {
+        let ui = rich_text(string()).on_link(|_, _| ());
+        let mut h = TestHarness::new(&ui, [171.0, 18.0]);
+        assert_eq!(h.hittest([10.0, 9.0]), None);
+        assert!(h.hittest([160.0, 9.0]).is_some());
+    }
+}
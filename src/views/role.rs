@@ -76,7 +76,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let child_aid = self.child.access(path, cx, nodes);
@@ -87,7 +87,7 @@ where
             Some(cid) => vec![cid],
             None => vec![],
         });
-        nodes.push((aid, builder.build()));
+        nodes.push((aid, builder));
         Some(aid)
     }
 }
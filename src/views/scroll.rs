@@ -0,0 +1,479 @@
+use crate::*;
+use std::any::Any;
+
+/// Which directions a `scroll` view can scroll in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollAxes {
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+impl ScrollAxes {
+    fn horizontal(self) -> bool {
+        self != ScrollAxes::Vertical
+    }
+
+    fn vertical(self) -> bool {
+        self != ScrollAxes::Horizontal
+    }
+}
+
+/// Width of the scroll indicators.
+const INDICATOR_WIDTH: f32 = 4.0;
+
+/// Shortest a scroll indicator gets.
+const INDICATOR_MIN_LENGTH: f32 = 16.0;
+
+/// Sizes and offset we keep between layout and event handling.
+#[derive(Clone, Copy, Default, Debug)]
+struct ScrollState {
+    offset: LocalOffset,
+    content_size: LocalSize,
+    viewport_size: LocalSize,
+}
+
+impl ScrollState {
+    /// The furthest the content can be scrolled.
+    fn max_offset(&self) -> LocalOffset {
+        [
+            (self.content_size.width - self.viewport_size.width).max(0.0),
+            (self.content_size.height - self.viewport_size.height).max(0.0),
+        ]
+        .into()
+    }
+}
+
+/// What a scroll view shows of its content: how far the content is scrolled
+/// from its top left corner, and the size of the viewport.
+///
+/// Set while laying out the content so lazy views can skip what's offscreen.
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct ScrollViewport(pub Option<(LocalOffset, LocalSize)>);
+
+/// Struct for the `scroll`, `hscroll` and `vscroll` views.
+#[derive(Clone)]
+pub struct Scroll<V, B> {
+    child: V,
+    axes: ScrollAxes,
+    offset: Option<B>,
+}
+
+impl<V, B> Scroll<V, B>
+where
+    V: View,
+    B: Binding<LocalOffset>,
+{
+    pub fn new(child: V, axes: ScrollAxes) -> Self {
+        Self {
+            child,
+            axes,
+            offset: None,
+        }
+    }
+
+    /// Reads and writes the scroll offset through `binding` so it can be
+    /// set from code.
+    ///
+    /// The offset is how far the content has been scrolled from its top left
+    /// corner, so it's never negative.
+    pub fn scroll_offset<B2: Binding<LocalOffset>>(self, binding: B2) -> Scroll<V, B2> {
+        Scroll {
+            child: self.child,
+            axes: self.axes,
+            offset: Some(binding),
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<ScrollState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &ScrollState::default);
+        StateHandle::new(id)
+    }
+
+    /// Returns the current offset, limited to what the content allows.
+    fn get_offset(&self, s: StateHandle<ScrollState>, cx: &Context) -> LocalOffset {
+        let offset = match &self.offset {
+            Some(binding) => *binding.get(cx),
+            None => cx[s].offset,
+        };
+        self.clamp(cx[s], offset)
+    }
+
+    fn set_offset(&self, s: StateHandle<ScrollState>, cx: &mut Context, offset: LocalOffset) {
+        let offset = self.clamp(cx[s], offset);
+        if offset == self.get_offset(s, cx) {
+            return;
+        }
+        match &self.offset {
+            Some(binding) => binding.with_mut(cx, |o| *o = offset),
+            None => cx[s].offset = offset,
+        }
+    }
+
+    fn clamp(&self, state: ScrollState, offset: LocalOffset) -> LocalOffset {
+        let max = state.max_offset();
+        [
+            if self.axes.horizontal() {
+                offset.x.clamp(0.0, max.x)
+            } else {
+                0.0
+            },
+            if self.axes.vertical() {
+                offset.y.clamp(0.0, max.y)
+            } else {
+                0.0
+            },
+        ]
+        .into()
+    }
+
+    /// Translation from our local space to the content's.
+    fn content_offset(&self, s: StateHandle<ScrollState>, cx: &Context) -> LocalOffset {
+        let offset = self.get_offset(s, cx);
+        let state = cx[s];
+
+        // Keep the top of the content at the top of the viewport.
+        [
+            -offset.x,
+            state.viewport_size.height - state.content_size.height + offset.y,
+        ]
+        .into()
+    }
+
+    /// Moves the content by `delta`, following a finger or the scroll wheel.
+    fn scroll_by(&self, s: StateHandle<ScrollState>, cx: &mut Context, delta: LocalOffset) {
+        let offset = self.get_offset(s, cx);
+        self.set_offset(s, cx, [offset.x - delta.x, offset.y + delta.y].into());
+    }
+}
+
+impl<V, B> DynView for Scroll<V, B>
+where
+    V: View,
+    B: Binding<LocalOffset>,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        let s = self.state(path, cx);
+        let viewport = LocalRect::new(LocalPoint::zero(), cx[s].viewport_size);
+        let content_offset = self.content_offset(s, cx);
+
+        match event {
+            Event::Scroll { delta, position } if viewport.contains(*position) => {
+                // The content gets first go, so nested scroll views work.
+                cx.scroll_remaining = *delta;
+                path.push(0);
+                self.child
+                    .process(&event.offset(-content_offset), path, cx, actions);
+                path.pop();
+
+                // Take what moves us along our axes, leaving the rest for
+                // the scroll views around us.
+                let rest = cx.scroll_remaining;
+                let before = self.get_offset(s, cx);
+                self.scroll_by(
+                    s,
+                    cx,
+                    LocalOffset::new(
+                        if self.axes.horizontal() { rest.x } else { 0.0 },
+                        if self.axes.vertical() { rest.y } else { 0.0 },
+                    ),
+                );
+                let after = self.get_offset(s, cx);
+                cx.scroll_remaining =
+                    rest - LocalOffset::new(before.x - after.x, after.y - before.y);
+            }
+            Event::Scroll { .. } => (),
+            Event::TouchBegin { id, position } => {
+                if !viewport.contains(*position) {
+                    return;
+                }
+                path.push(0);
+                self.child
+                    .process(&event.offset(-content_offset), path, cx, actions);
+                path.pop();
+
+                // Drag to scroll unless the content took the touch.
+                if cx.touches[*id] == ViewId::default() {
+                    cx.touches[*id] = vid;
+                }
+            }
+            Event::TouchMove { id, delta, .. } if cx.touches[*id] == vid => {
+                self.scroll_by(s, cx, *delta);
+            }
+            Event::TouchEnd { id, .. } | Event::TouchCancel { id, .. }
+                if cx.touches[*id] == vid =>
+            {
+                cx.touches[*id] = ViewId::default();
+            }
+            _ => {
+                path.push(0);
+                self.child
+                    .process(&event.offset(-content_offset), path, cx, actions);
+                path.pop();
+            }
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let s = self.state(path, args.cx);
+        let state = args.cx[s];
+        let offset = self.get_offset(s, args.cx);
+        let viewport = LocalRect::new(LocalPoint::zero(), state.viewport_size);
+
+        args.vger.save();
+        args.vger.scissor(viewport);
+        args.vger.translate(self.content_offset(s, args.cx));
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+
+        // Scroll indicators.
+        let paint = args.vger.color_paint(args.cx.apply_opacity(MEDIUM_GRAY));
+        let max = state.max_offset();
+        let viewport = state.viewport_size;
+        let content = state.content_size;
+
+        if self.axes.vertical() && max.y > 0.0 {
+            let length = (viewport.height * viewport.height / content.height)
+                .max(INDICATOR_MIN_LENGTH)
+                .min(viewport.height);
+            let y = (viewport.height - length) * (1.0 - offset.y / max.y);
+            args.vger.fill_rect(
+                LocalRect::new(
+                    [viewport.width - INDICATOR_WIDTH, y].into(),
+                    [INDICATOR_WIDTH, length].into(),
+                ),
+                INDICATOR_WIDTH / 2.0,
+                paint,
+            );
+        }
+
+        if self.axes.horizontal() && max.x > 0.0 {
+            let length = (viewport.width * viewport.width / content.width)
+                .max(INDICATOR_MIN_LENGTH)
+                .min(viewport.width);
+            let x = (viewport.width - length) * (offset.x / max.x);
+            args.vger.fill_rect(
+                LocalRect::new([x, 0.0].into(), [length, INDICATOR_WIDTH].into()),
+                INDICATOR_WIDTH / 2.0,
+                paint,
+            );
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let s = self.state(path, args.cx);
+        let offset = self.get_offset(s, args.cx);
+        let old = args.cx.init_env(&ScrollViewport::default);
+        args.cx.set_env(&ScrollViewport(Some((offset, args.sz))));
+
+        // The content is offered the viewport size, but can be larger
+        // along the scrolling axes.
+        path.push(0);
+        let content_size = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.set_env(&old);
+
+        let state = &mut args.cx[s];
+        state.content_size = content_size;
+        state.viewport_size = args.sz;
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let s = self.state(path, cx);
+        let content_offset = self.content_offset(s, cx);
+        path.push(0);
+        self.child
+            .dirty(path, xform.pre_translate(content_offset), cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let s = self.state(path, cx);
+        let viewport = LocalRect::new(LocalPoint::zero(), cx[s].viewport_size);
+
+        if viewport.contains(pt) {
+            let content_offset = self.content_offset(s, cx);
+            path.push(0);
+            let vid = self.child.hittest(path, pt - content_offset, cx);
+            path.pop();
+            Some(vid.unwrap_or_else(|| cx.view_id(path)))
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(self.state(path, cx).id);
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, B> private::Sealed for Scroll<V, B> {}
+
+/// Scrolls its content vertically and horizontally.
+pub fn scroll<V: View>(child: V) -> Scroll<V, StateHandle<LocalOffset>> {
+    Scroll::new(child, ScrollAxes::Both)
+}
+
+/// Scrolls its content horizontally.
+pub fn hscroll<V: View>(child: V) -> Scroll<V, StateHandle<LocalOffset>> {
+    Scroll::new(child, ScrollAxes::Horizontal)
+}
+
+/// Scrolls its content vertically.
+pub fn vscroll<V: View>(child: V) -> Scroll<V, StateHandle<LocalOffset>> {
+    Scroll::new(child, ScrollAxes::Vertical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(n: usize) -> impl View {
+        list((0..n).collect(), |_| rectangle().size([100.0, 20.0]))
+    }
+
+    fn scroll_at(h: &mut TestHarness<'_, impl View>, position: [f32; 2], delta: [f32; 2]) {
+        h.event(&Event::Scroll {
+            delta: delta.into(),
+            position: position.into(),
+        });
+    }
+
+    #[test]
+    fn test_wheel_scrolls_and_clamps() {
+        let ui = state(LocalOffset::zero, |offset, _| {
+            vscroll(rows(10)).scroll_offset(offset)
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 50.0]);
+        let offset = h.root_state::<LocalOffset>();
+
+        // Content moving up reveals the rows below.
+        scroll_at(&mut h, [50.0, 25.0], [0.0, 30.0]);
+        assert_eq!(*h.get(offset), LocalOffset::new(0.0, 30.0));
+
+        // Can't scroll past the end of the content, or sideways.
+        scroll_at(&mut h, [50.0, 25.0], [-20.0, 1000.0]);
+        assert_eq!(*h.get(offset), LocalOffset::new(0.0, 150.0));
+
+        scroll_at(&mut h, [50.0, 25.0], [0.0, -1000.0]);
+        assert_eq!(*h.get(offset), LocalOffset::zero());
+    }
+
+    #[test]
+    fn test_scroll_outside_is_ignored() {
+        let ui = state(LocalOffset::zero, |offset, _| {
+            vscroll(rows(10)).scroll_offset(offset).size([100.0, 50.0])
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let offset = h.root_state::<LocalOffset>();
+
+        scroll_at(&mut h, [50.0, 75.0], [0.0, 30.0]);
+        assert_eq!(*h.get(offset), LocalOffset::zero());
+    }
+
+    #[test]
+    fn test_set_offset_moves_content() {
+        let ui = state(LocalOffset::zero, |offset, _| {
+            vscroll(list(vec![0, 1, 2], move |i| {
+                let i = *i;
+                rectangle()
+                    .size([100.0, 20.0])
+                    .tap(move |cx| cx[offset].x = i as f32)
+            }))
+            .scroll_offset(offset)
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 40.0]);
+        let offset = h.root_state::<LocalOffset>();
+
+        // Row 0 is at the top.
+        h.tap([50.0, 30.0]);
+        assert_eq!(h.get(offset).x, 0.0);
+
+        // Scroll down by a row so row 1 is at the top and row 2 below it.
+        h.set(offset, LocalOffset::new(0.0, 20.0));
+        h.tap([50.0, 10.0]);
+        assert_eq!(h.get(offset).x, 2.0);
+    }
+
+    #[test]
+    fn test_drag_to_scroll() {
+        let ui = state(LocalOffset::zero, |offset, _| {
+            scroll(rows(10)).scroll_offset(offset)
+        });
+        let mut h = TestHarness::new(&ui, [50.0, 50.0]);
+        let offset = h.root_state::<LocalOffset>();
+
+        // Drag up and to the left.
+        h.drag([40.0, 10.0], [20.0, 30.0]);
+        assert_eq!(*h.get(offset), LocalOffset::new(20.0, 20.0));
+    }
+
+    #[test]
+    fn test_offset_kept_in_state() {
+        let ui = vscroll(rows(10));
+        let mut h = TestHarness::new(&ui, [100.0, 50.0]);
+        scroll_at(&mut h, [50.0, 25.0], [0.0, 30.0]);
+
+        let s = StateHandle::<ScrollState>::new(h.view_id(&[0]));
+        assert_eq!(h.cx[s].offset, LocalOffset::new(0.0, 30.0));
+        assert_eq!(h.cx[s].content_size, LocalSize::new(100.0, 200.0));
+    }
+
+    #[test]
+    fn test_nested_scrolls_hand_on_the_rest() {
+        let ui = state(LocalOffset::zero, |offset, _| {
+            vscroll(vstack((vscroll(rows(10)).size([100.0, 50.0]), rows(5)))).scroll_offset(offset)
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let offset = h.root_state::<LocalOffset>();
+
+        // The inner view, at the top, scrolls first.
+        scroll_at(&mut h, [50.0, 75.0], [0.0, 100.0]);
+        assert_eq!(*h.get(offset), LocalOffset::zero());
+
+        // Once it reaches its end, the outer view takes what's left.
+        scroll_at(&mut h, [50.0, 75.0], [0.0, 80.0]);
+        assert_eq!(*h.get(offset), LocalOffset::new(0.0, 30.0));
+    }
+}
@@ -25,7 +25,7 @@ impl DynView for Circle {
         let (center, radius) = self.geom(path, args.cx);
 
         let vger = &mut args.vger;
-        let paint = self.paint.vger_paint(vger);
+        let paint = self.paint.vger_paint_with_opacity(vger, args.cx.opacity);
         vger.fill_circle(center, radius, paint);
     }
 
@@ -98,7 +98,7 @@ impl DynView for Rectangle {
         let rect = self.geom(path, args.cx);
 
         let vger = &mut args.vger;
-        let paint = self.paint.vger_paint(vger);
+        let paint = self.paint.vger_paint_with_opacity(vger, args.cx.opacity);
         vger.fill_rect(rect, self.corner_radius, paint);
     }
 
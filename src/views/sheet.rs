@@ -0,0 +1,268 @@
+use crate::*;
+use std::any::Any;
+use std::sync::Arc;
+
+/// Struct for the `sheet` and `alert` modifiers.
+#[derive(Clone)]
+pub struct Sheet<V, B> {
+    child: V,
+    shown: B,
+    content: AnyView,
+}
+
+impl<V, B> Sheet<V, B>
+where
+    V: View,
+    B: Binding<bool>,
+{
+    pub fn new(child: V, shown: B, content: impl View) -> Self {
+        Self {
+            child,
+            shown,
+            content: any_view(content),
+        }
+    }
+}
+
+/// The contents of an alert. Any button closes it.
+pub(crate) fn alert_view(
+    shown: impl Binding<bool>,
+    title: &str,
+    message: &str,
+    buttons: Vec<MenuItem>,
+) -> impl View {
+    let buttons = if buttons.is_empty() {
+        vec![MenuItem::new("OK", |_| ())]
+    } else {
+        buttons
+    };
+    vstack((
+        text(title).font_size(20),
+        text(message),
+        hlist((0..buttons.len()).collect(), move |i| {
+            let item = buttons[*i].clone();
+            button(text(&item.title), move |cx| {
+                *shown.get_mut(cx) = false;
+                (item.action)(cx)
+            })
+            .padding(Auto)
+        }),
+    ))
+    .padding(Auto)
+    .background(rectangle().color(CONTROL_BACKGROUND).corner_radius(10.0))
+}
+
+impl<V, B> DynView for Sheet<V, B>
+where
+    V: View,
+    B: Binding<bool>,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+
+        // Everything else reaches the content through the overlay layer.
+        if matches!(event, Event::Anim | Event::Command(_)) && *self.shown.get(cx) {
+            path.push(1);
+            self.content.process(event, path, cx, actions);
+            path.pop();
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        if *self.shown.get(args.cx) {
+            let shown = self.shown;
+            path.push(1);
+            args.cx.show_modal(
+                path,
+                self.content.clone(),
+                Arc::new(move |cx| *shown.get_mut(cx) = false),
+            );
+            path.pop();
+        }
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+        if *self.shown.get(cx) {
+            path.push(1);
+            self.content.gc(path, cx, map);
+            path.pop();
+        }
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let child_id = self.child.access(path, cx, nodes);
+        path.pop();
+
+        if !*self.shown.get(cx) {
+            return child_id;
+        }
+
+        path.push(1);
+        let content_id = self.content.access(path, cx, nodes);
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Dialog);
+        builder.set_modal();
+        builder.set_children(content_id.into_iter().collect::<Vec<_>>());
+        let dialog_id = cx.view_id(path).access_id();
+        nodes.push((dialog_id, builder));
+        path.pop();
+
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::GenericContainer);
+        builder.set_children(child_id.into_iter().chain([dialog_id]).collect::<Vec<_>>());
+        let aid = cx.view_id(path).access_id();
+        nodes.push((aid, builder));
+        Some(aid)
+    }
+}
+
+impl<V, B> private::Sealed for Sheet<V, B> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modal_blocks_views_beneath() {
+        let ui = state(
+            || (0, 0),
+            |s, _| {
+                state(
+                    || true,
+                    move |shown, _| {
+                        rectangle()
+                            .size([100.0, 100.0])
+                            .tap(move |cx| cx[s].0 += 1)
+                            .key(move |cx, _| cx[s].1 += 1)
+                            .sheet(shown, rectangle().size([20.0, 20.0]))
+                    },
+                )
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<(i32, i32)>();
+        let shown = h.state::<bool>(&[0, 0]);
+
+        // Centered in the window.
+        assert_eq!(
+            h.cx.overlays[0].rect,
+            WorldRect::new([40.0, 40.0].into(), [20.0, 20.0].into())
+        );
+
+        // Clicks on the backdrop go nowhere, and don't close the sheet.
+        h.tap([10.0, 10.0]);
+        h.key(Key::Character('a'));
+        assert_eq!(*h.get(s), (0, 0));
+        assert!(*h.get(shown));
+
+        h.key(Key::Escape);
+        assert!(!*h.get(shown));
+        h.tap([10.0, 10.0]);
+        h.key(Key::Character('a'));
+        assert_eq!(*h.get(s), (1, 1));
+    }
+
+    #[test]
+    fn test_modal_traps_focus() {
+        let ui = state(|| false, |shown, _| rectangle().sheet(shown, rectangle()));
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let beneath = h.view_id(&[0, 0, 0]);
+        h.cx.focused_id = Some(beneath);
+
+        let shown = h.root_state::<bool>();
+        h.set(shown, true);
+        h.layout();
+        h.key(Key::Character('a'));
+        assert_eq!(h.cx.focused_id, None);
+    }
+
+    #[test]
+    fn test_modal_keeps_focus_inside() {
+        let ui = state(String::new, |text, _| {
+            state(
+                || true,
+                move |shown, _| rectangle().sheet(shown, text_editor(text).size([50.0, 20.0])),
+            )
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let text = h.root_state::<String>();
+
+        h.key(Key::Tab);
+        let inside = h.cx.focused();
+        assert!(inside.is_some());
+        h.type_text("ab");
+        assert_eq!(h.cx.focused(), inside);
+        assert_eq!(h.get(text), "ab");
+    }
+
+    #[test]
+    fn test_access_dialog() {
+        let ui = state(
+            || true,
+            |shown, _| rectangle().alert(shown, "Title", "Message", vec![]),
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let mut nodes = vec![];
+        ui.access(&mut vec![0], &mut h.cx, &mut nodes);
+        let dialog = nodes
+            .iter()
+            .find(|(_, n)| n.role() == accesskit::Role::Dialog)
+            .unwrap();
+        assert!(dialog.1.is_modal());
+        assert!(!dialog.1.children().is_empty());
+
+        let shown = h.root_state::<bool>();
+        h.set(shown, false);
+        let mut nodes = vec![];
+        ui.access(&mut vec![0], &mut h.cx, &mut nodes);
+        assert!(nodes
+            .iter()
+            .all(|(_, n)| n.role() != accesskit::Role::Dialog));
+    }
+}
@@ -23,7 +23,7 @@ pub trait SliderMods: View + Sized {
 /// Horizontal slider built from other Views.
 pub fn hslider(value: impl Binding<f32>) -> impl SliderMods {
     modview(move |opts: SliderOptions, _| {
-        state(
+        let slider = state(
             || 0.0,
             move |width, cx| {
                 let w = cx[width];
@@ -37,7 +37,7 @@ pub fn hslider(value: impl Binding<f32>) -> impl SliderMods {
                     let end_x = w - r;
                     let x = (1.0 - v) * start_x + v * (end_x);
 
-                    let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+                    let paint = vger.color_paint(cx.apply_opacity(BUTTON_BACKGROUND_COLOR));
                     vger.fill_rect(
                         euclid::rect(
                             start_x,
@@ -48,13 +48,13 @@ pub fn hslider(value: impl Binding<f32>) -> impl SliderMods {
                         0.0,
                         paint,
                     );
-                    let paint = vger.color_paint(AZURE_HIGHLIGHT_BACKGROUND);
+                    let paint = vger.color_paint(cx.apply_opacity(AZURE_HIGHLIGHT_BACKGROUND));
                     vger.fill_rect(
                         euclid::rect(start_x, c.y - SLIDER_WIDTH / 2.0, x, SLIDER_WIDTH),
                         0.0,
                         paint,
                     );
-                    let paint = vger.color_paint(opts.thumb);
+                    let paint = vger.color_paint(cx.apply_opacity(opts.thumb));
                     vger.fill_circle([x, c.y], r, paint);
                 })
                 .geom(move |cx, sz, _| {
@@ -66,8 +66,8 @@ pub fn hslider(value: impl Binding<f32>) -> impl SliderMods {
                     *v = (*v + delta.x / w).clamp(0.0, 1.0)
                 })
             },
-        )
-        .role(accesskit::Role::Slider)
+        );
+        adjustable(slider.role(accesskit::Role::Slider), value)
     })
 }
 
@@ -88,7 +88,7 @@ where
 /// Vertical slider built from other Views.
 pub fn vslider(value: impl Binding<f32>) -> impl SliderMods {
     modview(move |opts: SliderOptions, _| {
-        state(
+        let slider = state(
             || 0.0,
             move |height, cx| {
                 let h = cx[height];
@@ -101,7 +101,7 @@ pub fn vslider(value: impl Binding<f32>) -> impl SliderMods {
                     let end_y = h - r;
                     let y = (1.0 - v) * start_y + v * end_y;
 
-                    let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+                    let paint = vger.color_paint(cx.apply_opacity(BUTTON_BACKGROUND_COLOR));
                     vger.fill_rect(
                         euclid::rect(
                             c.x - SLIDER_WIDTH / 2.0,
@@ -112,13 +112,13 @@ pub fn vslider(value: impl Binding<f32>) -> impl SliderMods {
                         0.0,
                         paint,
                     );
-                    let paint = vger.color_paint(AZURE_HIGHLIGHT_BACKGROUND);
+                    let paint = vger.color_paint(cx.apply_opacity(AZURE_HIGHLIGHT_BACKGROUND));
                     vger.fill_rect(
                         euclid::rect(c.x - SLIDER_WIDTH / 2.0, start_y, SLIDER_WIDTH, y),
                         0.0,
                         paint,
                     );
-                    let paint = vger.color_paint(opts.thumb);
+                    let paint = vger.color_paint(cx.apply_opacity(opts.thumb));
                     vger.fill_circle([c.x, y], r, paint);
                 })
                 .geom(move |cx, sz, _| {
@@ -130,7 +130,7 @@ pub fn vslider(value: impl Binding<f32>) -> impl SliderMods {
                     *v = (*v + delta.y / h).clamp(0.0, 1.0)
                 })
             },
-        )
-        .role(accesskit::Role::Slider)
+        );
+        adjustable(slider.role(accesskit::Role::Slider), value)
     })
 }
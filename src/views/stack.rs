@@ -77,7 +77,9 @@ impl<VT: ViewTuple + 'static, D: StackDirection + 'static> DynView for Stack<VT,
             c += 1;
 
             if DEBUG_LAYOUT {
-                let paint = args.vger.color_paint(CONTROL_BACKGROUND);
+                let paint = args
+                    .vger
+                    .color_paint(args.cx.apply_opacity(CONTROL_BACKGROUND));
                 args.vger.stroke_rect(
                     layout_box.rect.min(),
                     layout_box.rect.max(),
@@ -268,7 +270,7 @@ impl<VT: ViewTuple + 'static, D: StackDirection + 'static> DynView for Stack<VT,
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         let mut c = 0;
         let mut builder = accesskit::NodeBuilder::new(accesskit::Role::List);
@@ -283,7 +285,7 @@ impl<VT: ViewTuple + 'static, D: StackDirection + 'static> DynView for Stack<VT,
         });
         builder.set_children(children);
         let aid = cx.view_id(path).access_id();
-        nodes.push((aid, builder.build()));
+        nodes.push((aid, builder));
         Some(aid)
     }
 }
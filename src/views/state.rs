@@ -39,6 +39,9 @@ impl<S: 'static> Binding<S> for StateHandle<S> {
     fn get_mut<'a>(&self, cx: &'a mut Context) -> &'a mut S {
         cx.get_mut(*self)
     }
+    fn state_id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
 }
 
 #[derive(Clone)]
@@ -121,6 +124,8 @@ where
             args.cx.update_layout(path, layout_box);
 
             args.cx.id_stack.pop();
+        } else {
+            args.cx.keep_overlays(path);
         }
 
         args.cx.get_layout(path).rect.size
@@ -182,7 +187,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         let id = cx.view_id(path);
         cx.init_state(id, &self.default);
@@ -110,6 +110,9 @@ where
                     )
                 }
             }
+            Event::TouchCancel { id, .. } if cx.touches[*id] == vid => {
+                cx.touches[*id] = ViewId::default();
+            }
             _ => (),
         }
     }
@@ -150,7 +153,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = self.child.access(path, cx, nodes);
@@ -256,4 +259,18 @@ mod tests {
 
         assert!(!*s.get(&cx));
     }
+
+    #[test]
+    fn test_cancelled_tap_does_not_fire() {
+        let ui = state(|| 0, |count, _| rectangle().tap(move |cx| cx[count] += 1));
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let count = h.root_state::<i32>();
+
+        let position = [50.0, 50.0].into();
+        h.event(&Event::TouchBegin { id: 3, position });
+        h.event(&Event::TouchCancel { id: 3, position });
+        h.event(&Event::TouchEnd { id: 3, position });
+        assert_eq!(*h.get(count), 0);
+        assert!(h.cx.touches[3].is_default());
+    }
 }
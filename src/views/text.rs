@@ -1,9 +1,38 @@
 use crate::*;
+use unicode_segmentation::UnicodeSegmentation;
 
 pub trait TextModifiers: View + Sized {
     fn font_size(self, size: u32) -> Text;
     fn color(self, color: Color) -> Text;
     fn max_width(self, max_width: f32) -> Text;
+    fn text_align(self, align: TextAlign) -> Text;
+    /// Limits the text to `lines` lines, truncating the last.
+    fn line_limit(self, lines: usize) -> Text;
+    /// Truncates lines wider than `max_width`, and the last line when
+    /// there are more than `line_limit`. Tail is the default.
+    fn truncation(self, truncation: Truncation) -> Text;
+    /// Adds space between lines.
+    fn line_spacing(self, spacing: f32) -> Text;
+}
+
+/// How lines of text line up within the text's width.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Leading,
+    Center,
+    Trailing,
+    /// Spreads words out to fill every line but the last of a paragraph.
+    Justified,
+}
+
+/// Where text which doesn't fit is cut to make room for an ellipsis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Truncation {
+    Head,
+    Middle,
+    #[default]
+    Tail,
 }
 
 /// Struct for `text`.
@@ -13,34 +42,398 @@ pub struct Text {
     size: u32,
     color: Color,
     max_width: Option<f32>,
+    align: TextAlign,
+    line_limit: Option<usize>,
+    truncation: Option<Truncation>,
+    line_spacing: f32,
 }
 
 impl Text {
     pub const DEFAULT_SIZE: u32 = 18;
     pub fn color(self, color: Color) -> Text {
-        Text {
-            text: self.text,
-            size: self.size,
-            color,
-            max_width: self.max_width,
+        Text { color, ..self }
+    }
+
+    /// Lets the text be selected by dragging, double clicking a word or
+    /// pressing select all, and copied to the clipboard. It still can't
+    /// be edited.
+    ///
+    /// Truncated text, with a `line_limit` or `truncation`, can't be
+    /// selected, as not all of it is shown.
+    pub fn selectable(self) -> impl View {
+        let text = self.text.clone();
+        selectable(self, text)
+    }
+
+    /// Is some of the text cut off to fit?
+    pub(crate) fn truncates(&self) -> bool {
+        self.line_limit.is_some() || self.truncation.is_some()
+    }
+
+    /// Does the text need breaking into lines here, rather than by vger?
+    fn has_line_layout(&self) -> bool {
+        self.align != TextAlign::Leading
+            || self.line_limit.is_some()
+            || self.truncation.is_some()
+            || self.line_spacing != 0.0
+    }
+
+    /// Where each char is drawn, and the lines they're on, for selecting
+    /// the text. None if lines are truncated, as then not all the text
+    /// is drawn.
+    pub(crate) fn glyph_layout(
+        &self,
+        args: &mut DrawArgs,
+    ) -> Option<(Vec<LocalRect>, Vec<LineMetrics>)> {
+        let font = args.cx.font();
+        let size = self.size;
+        if !self.has_line_layout() {
+            // Drawing moves the glyphs to start at our origin.
+            let max_width = self.max_width;
+            let origin = args
+                .font_text_bounds(font, &self.text, size, max_width)
+                .origin;
+            let offset = -origin.to_vector();
+            let rects = args.font_glyph_positions(font, &self.text, size, max_width);
+            let mut lines = args.font_line_metrics(font, &self.text, size, max_width);
+            for line in &mut lines {
+                line.bounds = line.bounds.translate(offset);
+            }
+            return Some((rects.iter().map(|r| r.translate(offset)).collect(), lines));
+        }
+        if self.truncates() {
+            return None;
+        }
+        let (pieces, _) = self.layout_lines(&mut |s| args.font_text_bounds(font, s, size, None));
+        self.piece_glyphs(pieces, &mut |s| {
+            args.font_glyph_positions(font, s, size, None)
+        })
+    }
+
+    /// Places the glyphs of the pieces `layout_lines` draws, which are
+    /// the text's lines or words in order. The spaces and newlines
+    /// between them get empty rects.
+    fn piece_glyphs(
+        &self,
+        pieces: Vec<(String, LocalOffset)>,
+        glyph_positions: &mut dyn FnMut(&str) -> Vec<LocalRect>,
+    ) -> Option<(Vec<LocalRect>, Vec<LineMetrics>)> {
+        let mut rects: Vec<LocalRect> = vec![];
+        let mut lines: Vec<LineMetrics> = vec![];
+        let mut line_y = None;
+        let mut pos = 0;
+        for (piece, offset) in pieces {
+            let start = pos + self.text[pos..].find(piece.as_str())?;
+            let piece_rects: Vec<LocalRect> = glyph_positions(&piece)
+                .iter()
+                .map(|r| r.translate(offset))
+                .collect();
+            let gap = match rects.last() {
+                Some(r) => LocalRect::new([r.max_x(), r.min_y()].into(), LocalSize::zero()),
+                None => LocalRect::new(piece_rects[0].origin, LocalSize::zero()),
+            };
+            rects.extend(self.text[pos..start].chars().map(|_| gap));
+            if line_y != Some(offset.y) {
+                if let Some(line) = lines.last_mut() {
+                    line.glyph_end = rects.len();
+                }
+                lines.push(LineMetrics {
+                    glyph_start: rects.len(),
+                    glyph_end: rects.len(),
+                    bounds: piece_rects[0],
+                });
+                line_y = Some(offset.y);
+            }
+            let line = lines.last_mut().unwrap();
+            for r in &piece_rects {
+                line.bounds = line.bounds.union(r);
+            }
+            rects.extend(piece_rects);
+            line.glyph_end = rects.len();
+            pos = start + piece.len();
+        }
+        if let Some(last) = rects.last().cloned() {
+            let gap = LocalRect::new([last.max_x(), last.min_y()].into(), LocalSize::zero());
+            rects.extend(self.text[pos..].chars().map(|_| gap));
+            lines.last_mut().unwrap().glyph_end = rects.len();
+        }
+        Some((rects, lines))
+    }
+
+    /// Breaks the text into lines and places them, top down. Returns what
+    /// to draw where, and the size of it all.
+    fn layout_lines(
+        &self,
+        measure: &mut dyn FnMut(&str) -> LocalRect,
+    ) -> (Vec<(String, LocalOffset)>, LocalSize) {
+        let spacing = Spacing::new(measure);
+
+        // Each word is measured once, and lines are measured by adding
+        // up their words.
+        let mut lines = vec![];
+        for paragraph in self.text.split('\n') {
+            let mut line = Line::default();
+            for word in paragraph.split(' ') {
+                let word_width = width_of(measure, word);
+                if line.words.is_empty() {
+                    line.push(word, word_width, &spacing);
+                    continue;
+                }
+                let longer = line.width + spacing.space + word_width;
+                if self.max_width.is_some_and(|w| longer > w) {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(word, word_width, &spacing);
+            }
+            line.last = true;
+            lines.push(line);
+        }
+
+        let truncation = self.truncation.unwrap_or_default();
+        if let Some(limit) = self.line_limit.map(|n| n.max(1)) {
+            if lines.len() > limit {
+                let width = self.max_width.unwrap_or(lines[limit - 1].width);
+                let rest: Vec<_> = lines.drain(limit - 1..).map(|l| l.text).collect();
+                lines.push(truncate(
+                    &rest.join(" "),
+                    truncation,
+                    width,
+                    measure,
+                    &spacing,
+                ));
+            }
+        }
+        if let (Some(_), Some(width)) = (self.truncation, self.max_width) {
+            for line in &mut lines {
+                if line.width > width {
+                    *line = Line {
+                        last: line.last,
+                        ..truncate(&line.text, truncation, width, measure, &spacing)
+                    };
+                }
+            }
+        }
+
+        // Lines are as tall as the font, whatever glyphs they have.
+        let reference = measure("Ag");
+        let baseline = measure("x").min_y();
+        let ascent = reference.max_y() - baseline;
+        let line_height = reference.height();
+
+        let widest = lines.iter().map(|l| l.width).fold(0.0, f32::max);
+        let width = match (self.max_width, self.align) {
+            (Some(max_width), align) if align != TextAlign::Leading => max_width,
+            _ => widest,
+        };
+        let n = lines.len() as f32;
+        let height = n * line_height + (n - 1.0) * self.line_spacing;
+
+        let mut pieces = vec![];
+        for (i, line) in lines.into_iter().enumerate() {
+            let y = height - i as f32 * (line_height + self.line_spacing) - ascent - baseline;
+            let words: Vec<(&str, f32)> = line
+                .text
+                .split(' ')
+                .zip(line.words.iter().cloned())
+                .filter(|(w, _)| !w.is_empty())
+                .collect();
+            let placed = match self.align {
+                TextAlign::Justified if !line.last && words.len() > 1 => {
+                    let total: f32 = words.iter().map(|(_, w)| w).sum();
+                    let gap = (width - total) / (words.len() - 1) as f32;
+                    let mut x = 0.0;
+                    let mut placed = vec![];
+                    for (word, word_width) in words {
+                        placed.push((word.to_string(), x));
+                        x += word_width + gap;
+                    }
+                    placed
+                }
+                TextAlign::Leading | TextAlign::Justified => vec![(line.text, 0.0)],
+                TextAlign::Center => vec![(line.text, (width - line.width) / 2.0)],
+                TextAlign::Trailing => vec![(line.text, width - line.width)],
+            };
+            for (piece, x) in placed {
+                if !piece.trim().is_empty() {
+                    let origin = measure(&piece).origin;
+                    pieces.push((piece, LocalOffset::new(x - origin.x, y)));
+                }
+            }
+        }
+
+        (pieces, [width, height].into())
+    }
+}
+
+/// How far apart the ink of neighbouring glyphs and words is, so lines
+/// can be measured by adding up their parts.
+struct Spacing {
+    /// Between words either side of a space.
+    space: f32,
+    /// Between glyphs side by side.
+    gap: f32,
+}
+
+impl Spacing {
+    fn new(measure: &mut dyn FnMut(&str) -> LocalRect) -> Self {
+        let x = measure("x").width();
+        Self {
+            space: measure("x x").width() - 2.0 * x,
+            gap: measure("xx").width() - 2.0 * x,
         }
     }
 }
 
+/// A line of text with the widths of its words.
+#[derive(Default)]
+struct Line {
+    text: String,
+    width: f32,
+    /// One for each word between spaces, empty ones included.
+    words: Vec<f32>,
+    /// Does it end a paragraph?
+    last: bool,
+}
+
+impl Line {
+    fn push(&mut self, word: &str, word_width: f32, spacing: &Spacing) {
+        if !self.words.is_empty() {
+            self.text.push(' ');
+            self.width += spacing.space;
+        }
+        self.text.push_str(word);
+        self.width += word_width;
+        self.words.push(word_width);
+    }
+}
+
+/// Glyph bounds are nothing for blank text.
+fn width_of(measure: &mut dyn FnMut(&str) -> LocalRect, s: &str) -> f32 {
+    if s.trim().is_empty() {
+        0.0
+    } else {
+        measure(s).width()
+    }
+}
+
+/// Cuts `text` down to fit `width`, with an ellipsis where the cut was.
+/// Each grapheme is measured once, and the cuts we try measured by adding
+/// them up.
+fn truncate(
+    text: &str,
+    truncation: Truncation,
+    width: f32,
+    measure: &mut dyn FnMut(&str) -> LocalRect,
+    spacing: &Spacing,
+) -> Line {
+    let full = width_of(measure, text);
+    if full <= width {
+        return Line {
+            text: text.into(),
+            width: full,
+            ..Line::default()
+        };
+    }
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+
+    // A blank grapheme's width is what it adds between the glyphs
+    // either side.
+    let blank = |g: &str| g.trim().is_empty();
+    let mut sums = vec![0.0];
+    for g in &graphemes {
+        let w = if blank(g) {
+            spacing.space - 2.0 * spacing.gap
+        } else {
+            measure(g).width()
+        };
+        sums.push(sums.last().unwrap() + w);
+    }
+    let ellipsis = width_of(measure, "\u{2026}");
+
+    // Keeps the first `head` and last `tail` graphemes, less spaces
+    // next to the ellipsis.
+    let cut = |head: usize, tail: usize| {
+        let head = head
+            - graphemes[..head]
+                .iter()
+                .rev()
+                .take_while(|g| blank(g))
+                .count();
+        let tail = tail
+            - graphemes[graphemes.len() - tail..]
+                .iter()
+                .take_while(|g| blank(g))
+                .count();
+        // Glyphs are spaced by `gap`, from each other and the ellipsis.
+        let part = |range: std::ops::Range<usize>| {
+            sums[range.end] - sums[range.start] + range.len() as f32 * spacing.gap
+        };
+        let width = part(0..head) + ellipsis + part(graphemes.len() - tail..graphemes.len());
+        let text = format!(
+            "{}\u{2026}{}",
+            graphemes[..head].concat(),
+            graphemes[graphemes.len() - tail..].concat()
+        );
+        (text, width)
+    };
+    let split = |keep: usize| match truncation {
+        Truncation::Head => (0, keep),
+        Truncation::Middle => (keep.div_ceil(2), keep / 2),
+        Truncation::Tail => (keep, 0),
+    };
+
+    // Find the most graphemes we can keep.
+    let (mut lo, mut hi) = (0, graphemes.len());
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        let (head, tail) = split(mid);
+        if cut(head, tail).1 <= width {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    let (head, tail) = split(lo);
+    let (text, width) = cut(head, tail);
+    Line {
+        text,
+        width,
+        ..Line::default()
+    }
+}
+
 impl DynView for Text {
     fn draw(&self, _path: &mut IdPath, args: &mut DrawArgs) {
-        let vger = &mut args.vger;
-        let origin = vger
+        let color = args.cx.apply_opacity(self.color);
+        if self.has_line_layout() {
+            let size = self.size;
+            let (pieces, _) = self.layout_lines(&mut |s| args.text_bounds(s, size, None));
+            for (piece, offset) in pieces {
+                args.vger.save();
+                args.vger.translate(offset);
+                args.text(&piece, size, color, None);
+                args.vger.restore();
+            }
+            return;
+        }
+
+        let origin = args
             .text_bounds(self.text.as_str(), self.size, self.max_width)
             .origin;
 
-        vger.save();
-        vger.translate([-origin.x, -origin.y]);
-        vger.text(self.text.as_str(), self.size, self.color, self.max_width);
-        vger.restore();
+        args.vger.save();
+        args.vger.translate([-origin.x, -origin.y]);
+        args.text(self.text.as_str(), self.size, color, self.max_width);
+        args.vger.restore();
     }
     fn layout(&self, _path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
-        (args.text_bounds)(self.text.as_str(), self.size, None).size
+        if self.has_line_layout() {
+            let size = self.size;
+            return self
+                .layout_lines(&mut |s| args.text_bounds(s, size, None))
+                .1;
+        }
+        args.text_bounds(self.text.as_str(), self.size, None).size
     }
     fn hittest(&self, _path: &mut IdPath, _pt: LocalPoint, _cx: &mut Context) -> Option<ViewId> {
         None
@@ -50,39 +443,48 @@ impl DynView for Text {
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         let aid = cx.view_id(path).access_id();
         let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Label);
         builder.set_name(self.text.clone());
-        nodes.push((aid, builder.build()));
+        nodes.push((aid, builder));
         Some(aid)
     }
 }
 
 impl TextModifiers for Text {
     fn font_size(self, size: u32) -> Self {
-        Self {
-            text: self.text,
-            color: self.color,
-            size,
-            max_width: self.max_width,
-        }
+        Self { size, ..self }
     }
     fn color(self, color: Color) -> Text {
-        Text {
-            text: self.text,
-            size: self.size,
-            color,
-            max_width: self.max_width,
-        }
+        Text { color, ..self }
     }
     fn max_width(self, max_width: f32) -> Text {
         Text {
-            text: self.text,
-            size: self.size,
-            color: self.color,
             max_width: Some(max_width),
+            ..self
+        }
+    }
+    fn text_align(self, align: TextAlign) -> Text {
+        Text { align, ..self }
+    }
+    fn line_limit(self, lines: usize) -> Text {
+        Text {
+            line_limit: Some(lines),
+            ..self
+        }
+    }
+    fn truncation(self, truncation: Truncation) -> Text {
+        Text {
+            truncation: Some(truncation),
+            ..self
+        }
+    }
+    fn line_spacing(self, spacing: f32) -> Text {
+        Text {
+            line_spacing: spacing,
+            ..self
         }
     }
 }
@@ -96,66 +498,71 @@ pub fn text(name: &str) -> Text {
         size: Text::DEFAULT_SIZE,
         color: TEXT_COLOR,
         max_width: None,
+        align: TextAlign::Leading,
+        line_limit: None,
+        truncation: None,
+        line_spacing: 0.0,
     }
 }
 
+/// Shows a string which can be selected and copied, but not edited.
+pub fn selectable_text(name: &str) -> impl View {
+    text(name).selectable()
+}
+
 macro_rules! impl_text {
     ( $ty:ident ) => {
         impl DynView for $ty {
             fn draw(&self, _path: &mut IdPath, args: &mut DrawArgs) {
                 let txt = &format!("{}", self);
-                let vger = &mut args.vger;
-                let origin = vger.text_bounds(txt, Text::DEFAULT_SIZE, None).origin;
+                let color = args.cx.apply_opacity(TEXT_COLOR);
+                let origin = args.text_bounds(txt, Text::DEFAULT_SIZE, None).origin;
 
-                vger.save();
-                vger.translate([-origin.x, -origin.y]);
-                vger.text(txt, Text::DEFAULT_SIZE, TEXT_COLOR, None);
-                vger.restore();
+                args.vger.save();
+                args.vger.translate([-origin.x, -origin.y]);
+                args.text(txt, Text::DEFAULT_SIZE, color, None);
+                args.vger.restore();
             }
             fn layout(&self, _path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
                 let txt = &format!("{}", self);
-                (args.text_bounds)(txt, Text::DEFAULT_SIZE, None).size
+                args.text_bounds(txt, Text::DEFAULT_SIZE, None).size
             }
 
             fn access(
                 &self,
                 path: &mut IdPath,
                 cx: &mut Context,
-                nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+                nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
             ) -> Option<accesskit::NodeId> {
                 let aid = cx.view_id(path).access_id();
                 let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Label);
                 builder.set_name(format!("{}", self));
-                nodes.push((aid, builder.build()));
+                nodes.push((aid, builder));
                 Some(aid)
             }
         }
 
         impl TextModifiers for $ty {
             fn font_size(self, size: u32) -> Text {
-                Text {
-                    text: format!("{}", self),
-                    size,
-                    color: TEXT_COLOR,
-                    max_width: None,
-                }
+                text(&format!("{}", self)).font_size(size)
             }
             fn color(self, color: Color) -> Text {
-                Text {
-                    text: format!("{}", self),
-                    size: Text::DEFAULT_SIZE,
-                    color,
-                    max_width: None,
-                }
+                text(&format!("{}", self)).color(color)
             }
-
             fn max_width(self, max_width: f32) -> Text {
-                Text {
-                    text: format!("{}", self),
-                    size: Text::DEFAULT_SIZE,
-                    color: TEXT_COLOR,
-                    max_width: Some(max_width),
-                }
+                text(&format!("{}", self)).max_width(max_width)
+            }
+            fn text_align(self, align: TextAlign) -> Text {
+                text(&format!("{}", self)).text_align(align)
+            }
+            fn line_limit(self, lines: usize) -> Text {
+                text(&format!("{}", self)).line_limit(lines)
+            }
+            fn truncation(self, truncation: Truncation) -> Text {
+                text(&format!("{}", self)).truncation(truncation)
+            }
+            fn line_spacing(self, spacing: f32) -> Text {
+                text(&format!("{}", self)).line_spacing(spacing)
             }
         }
     };
@@ -176,58 +583,177 @@ impl_text!(f64);
 impl DynView for &'static str {
     fn draw(&self, _path: &mut IdPath, args: &mut DrawArgs) {
         let txt = &self.to_string();
-        let vger = &mut args.vger;
-        let origin = vger.text_bounds(txt, Text::DEFAULT_SIZE, None).origin;
+        let color = args.cx.apply_opacity(TEXT_COLOR);
+        let origin = args.text_bounds(txt, Text::DEFAULT_SIZE, None).origin;
 
-        vger.save();
-        vger.translate([-origin.x, -origin.y]);
-        vger.text(txt, Text::DEFAULT_SIZE, TEXT_COLOR, None);
-        vger.restore();
+        args.vger.save();
+        args.vger.translate([-origin.x, -origin.y]);
+        args.text(txt, Text::DEFAULT_SIZE, color, None);
+        args.vger.restore();
     }
     fn layout(&self, _path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
         let txt = &self.to_string();
-        (args.text_bounds)(txt, Text::DEFAULT_SIZE, None).size
+        args.text_bounds(txt, Text::DEFAULT_SIZE, None).size
     }
 
     fn access(
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         let aid = cx.view_id(path).access_id();
         let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Label);
         builder.set_name(self.to_string());
-        nodes.push((aid, builder.build()));
+        nodes.push((aid, builder));
         Some(aid)
     }
 }
 
 impl TextModifiers for &'static str {
     fn font_size(self, size: u32) -> Text {
-        Text {
-            text: self.to_string(),
-            size,
-            color: TEXT_COLOR,
-            max_width: None,
-        }
+        text(self).font_size(size)
     }
     fn color(self, color: Color) -> Text {
-        Text {
-            text: self.to_string(),
-            size: Text::DEFAULT_SIZE,
-            color,
-            max_width: None,
-        }
+        text(self).color(color)
     }
     fn max_width(self, max_width: f32) -> Text {
-        Text {
-            text: self.to_string(),
-            size: Text::DEFAULT_SIZE,
-            color: TEXT_COLOR,
-            max_width: Some(max_width),
-        }
+        text(self).max_width(max_width)
+    }
+    fn text_align(self, align: TextAlign) -> Text {
+        text(self).text_align(align)
+    }
+    fn line_limit(self, lines: usize) -> Text {
+        text(self).line_limit(lines)
+    }
+    fn truncation(self, truncation: Truncation) -> Text {
+        text(self).truncation(truncation)
+    }
+    fn line_spacing(self, spacing: f32) -> Text {
+        text(self).line_spacing(spacing)
     }
 }
 
 impl<V> private::Sealed for V where V: std::fmt::Display {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure(s: &str) -> LocalRect {
+        ApproxTextMeasure.text_bounds(s, Text::DEFAULT_SIZE, None)
+    }
+
+    #[test]
+    fn test_truncate() {
+        // Five chars fit.
+        let spacing = Spacing::new(&mut measure);
+        let cut = |text, truncation| truncate(text, truncation, 45.0, &mut measure, &spacing).text;
+        assert_eq!(cut("Resonance", Truncation::Head), "\u{2026}ance");
+        assert_eq!(cut("Resonance", Truncation::Middle), "Re\u{2026}ce");
+        assert_eq!(cut("Resonance", Truncation::Tail), "Reso\u{2026}");
+        assert_eq!(cut("Res", Truncation::Tail), "Res");
+        assert_eq!(cut("Reso nance", Truncation::Tail), "Reso\u{2026}");
+    }
+
+    #[test]
+    fn test_align() {
+        let (pieces, size) = text("ab")
+            .max_width(90.0)
+            .text_align(TextAlign::Center)
+            .layout_lines(&mut measure);
+        assert_eq!(size, [90.0, 18.0].into());
+        assert_eq!(
+            pieces,
+            vec![("ab".to_string(), LocalOffset::new(36.0, 0.0))]
+        );
+
+        let (pieces, _) = text("ab")
+            .max_width(90.0)
+            .text_align(TextAlign::Trailing)
+            .layout_lines(&mut measure);
+        assert_eq!(pieces[0].1.x, 72.0);
+    }
+
+    #[test]
+    fn test_justify() {
+        let (pieces, size) = text("aa bb cc dd")
+            .max_width(60.0)
+            .text_align(TextAlign::Justified)
+            .line_spacing(4.0)
+            .layout_lines(&mut measure);
+        assert_eq!(size, [60.0, 40.0].into());
+        assert_eq!(
+            pieces,
+            vec![
+                ("aa".to_string(), LocalOffset::new(0.0, 22.0)),
+                ("bb".to_string(), LocalOffset::new(42.0, 22.0)),
+                // The last line isn't spread out.
+                ("cc dd".to_string(), LocalOffset::new(0.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_limit() {
+        let t = text("one two three").max_width(70.0);
+        assert_eq!(
+            t.clone().line_limit(2).layout_lines(&mut measure).0.len(),
+            2
+        );
+
+        let (pieces, size) = t.line_limit(1).layout_lines(&mut measure);
+        assert_eq!(pieces[0].0, "one tw\u{2026}");
+        assert_eq!(size.height, 18.0);
+    }
+
+    #[test]
+    fn test_words_measured_once() {
+        let words: Vec<String> = (0..20).map(|i| format!("w{}", i)).collect();
+        let mut measured = vec![];
+        let mut counting = |s: &str| {
+            measured.push(s.to_string());
+            measure(s)
+        };
+        text(&words.join(" "))
+            .max_width(100.0)
+            .line_limit(2)
+            .layout_lines(&mut counting);
+
+        for word in &words {
+            assert!(measured.iter().filter(|m| *m == word).count() <= 1);
+        }
+        // Nothing but the pieces drawn, and the font's metrics, is
+        // measured with spaces.
+        let with_spaces = measured.iter().filter(|m| m.contains(' ')).count();
+        assert!(with_spaces <= 4, "{:?}", measured);
+    }
+
+    #[test]
+    fn test_piece_glyphs() {
+        let t = text("ab cd\nef")
+            .max_width(30.0)
+            .text_align(TextAlign::Center);
+        let (pieces, _) = t.layout_lines(&mut measure);
+        let mut positions = |s: &str| -> Vec<LocalRect> {
+            (0..s.chars().count())
+                .map(|i| LocalRect::new([i as f32 * 9.0, 0.0].into(), [9.0, 18.0].into()))
+                .collect()
+        };
+        let (rects, lines) = t.piece_glyphs(pieces, &mut positions).unwrap();
+
+        // A rect for every char, spaces and newlines included.
+        assert_eq!(rects.len(), 8);
+        let ranges: Vec<_> = lines.iter().map(|l| (l.glyph_start, l.glyph_end)).collect();
+        assert_eq!(ranges, vec![(0, 3), (3, 6), (6, 8)]);
+
+        // Lines are centered, and go down.
+        assert_eq!(rects[0].min_x(), 6.0);
+        assert_eq!(rects[3].min_x(), 6.0);
+        assert!(rects[3].min_y() < rects[0].min_y());
+        assert!(rects[2].is_empty());
+
+        // Truncated text isn't all drawn, so can't be selected.
+        assert!(t.line_limit(1).truncates());
+    }
+}
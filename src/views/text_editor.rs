@@ -1,27 +1,175 @@
 use crate::*;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+
+/// Text, cursor and selection saved for undo.
+#[derive(Clone, Debug, PartialEq)]
+struct EditSnapshot {
+    text: String,
+    cursor: usize,
+    selection_start: Option<usize>,
+}
+
+/// Kinds of edits. Consecutive edits of the same kind are undone together.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EditKind {
+    /// Typing. A group ends when a word starts after whitespace.
+    Insert { whitespace: bool },
+    /// Backspace or Delete.
+    Delete,
+    /// Cut or paste. Never grouped.
+    Replace,
+}
+
+/// Undo and redo stacks for `text_editor`.
+#[derive(Default)]
+struct UndoHistory {
+    undo: Vec<EditSnapshot>,
+    redo: Vec<EditSnapshot>,
+    /// Kind of the last edit, if it can still be extended.
+    last: Option<EditKind>,
+}
+
+impl UndoHistory {
+    /// Records the state before an edit, unless the edit
+    /// continues the current group.
+    fn record(&mut self, before: EditSnapshot, kind: EditKind) {
+        let coalesce = match (self.last, kind) {
+            (Some(EditKind::Delete), EditKind::Delete) => true,
+            (Some(EditKind::Insert { whitespace: prev }), EditKind::Insert { whitespace }) => {
+                // Start a new group at the beginning of each word.
+                !prev || whitespace
+            }
+            _ => false,
+        } && before.selection_start.is_none();
+
+        if !coalesce {
+            self.undo.push(before);
+        }
+        self.redo.clear();
+        self.last = Some(kind);
+    }
+
+    /// Stops the current group from being extended, e.g. after the cursor moves.
+    fn break_group(&mut self) {
+        self.last = None;
+    }
+}
 
 /// View-model for `text_editor`.
+///
+/// The cursor and selection are byte offsets into the text, and always sit
+/// on grapheme cluster boundaries. Layout is in glyphs, of which vger
+/// produces one per char, so we convert when talking to the layout.
 struct TextEditorState {
     cursor: usize,
     selection_start: Option<usize>,
     glyph_rects: Vec<LocalRect>,
     lines: Vec<LineMetrics>,
+    history: UndoHistory,
+    /// Where glyph space starts within the editor.
+    text_origin: LocalOffset,
+    /// Text being composed by the IME, shown at the cursor.
+    preedit: String,
+    /// Byte range of the IME's cursor within `preedit`.
+    preedit_cursor: Option<(usize, usize)>,
+}
+
+/// Returns the glyph index for the byte offset `pos`.
+fn glyph_index(text: &str, pos: usize) -> usize {
+    text[..pos].chars().count()
+}
+
+/// Returns the byte offset of the grapheme cluster containing glyph `glyph`.
+fn byte_offset(text: &str, glyph: usize) -> usize {
+    match text.char_indices().nth(glyph) {
+        Some((pos, _)) => grapheme_start(text, pos),
+        None => text.len(),
+    }
+}
+
+/// Returns the start of the grapheme cluster after the one at `pos`.
+fn next_grapheme(text: &str, pos: usize) -> usize {
+    GraphemeCursor::new(pos, text.len(), true)
+        .next_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(text.len())
+}
+
+/// Returns the start of the grapheme cluster before `pos`.
+fn prev_grapheme(text: &str, pos: usize) -> usize {
+    GraphemeCursor::new(pos, text.len(), true)
+        .prev_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(0)
+}
+
+/// Returns the start of the grapheme cluster containing byte `pos`.
+fn grapheme_start(text: &str, mut pos: usize) -> usize {
+    pos = pos.min(text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    match GraphemeCursor::new(pos, text.len(), true).is_boundary(text, 0) {
+        Ok(false) => prev_grapheme(text, pos),
+        _ => pos,
+    }
+}
+
+/// Returns the start of the word before `pos`.
+fn prev_word(text: &str, pos: usize) -> usize {
+    text.unicode_word_indices()
+        .rev()
+        .map(|(i, _)| i)
+        .find(|i| *i < pos)
+        .unwrap_or(0)
+}
+
+/// Returns the end of the word after `pos`.
+fn next_word(text: &str, pos: usize) -> usize {
+    text.unicode_word_indices()
+        .map(|(i, word)| i + word.len())
+        .find(|end| *end > pos)
+        .unwrap_or(text.len())
+}
+
+/// Returns the word (or run of whitespace or punctuation) at `pos`.
+fn word_at(text: &str, pos: usize) -> (usize, usize) {
+    let mut range = (pos, pos);
+    for (i, segment) in text.split_word_bound_indices() {
+        range = (i, i + segment.len());
+        if pos < range.1 {
+            break;
+        }
+    }
+    range
 }
 
 impl TextEditorState {
     /// Returns the position of the cursor in local coordinates.
-    fn cursor_pos(&self) -> LocalPoint {
-        if self.cursor == self.glyph_rects.len() {
-            if let Some(r) = self.glyph_rects.last() {
-                [r.origin.x + r.size.width, r.origin.y].into()
-            } else {
-                [0.0, -20.0].into()
-            }
-        } else {
-            self.glyph_rects[self.cursor].origin
+    fn cursor_pos(&self, text: &str) -> LocalPoint {
+        self.glyph_pos(text, self.cursor)
+    }
+
+    /// Returns the position of byte offset `pos` in local coordinates.
+    fn glyph_pos(&self, text: &str, pos: usize) -> LocalPoint {
+        match self.glyph_rects.get(glyph_index(text, pos)) {
+            Some(r) => r.origin,
+            None => match self.glyph_rects.last() {
+                Some(r) => [r.origin.x + r.size.width, r.origin.y].into(),
+                None => [0.0, -20.0].into(),
+            },
         }
     }
 
+    /// Moves the cursor and selection back onto grapheme boundaries within
+    /// `text`, in case the text was changed from outside the editor.
+    fn clamp(&mut self, text: &str) {
+        self.cursor = grapheme_start(text, self.cursor);
+        self.selection_start = self.selection_start.map(|s| grapheme_start(text, s));
+    }
+
     /// Returns the current selection range (start, end) with start <= end
     fn selection_range(&self) -> Option<(usize, usize)> {
         if let Some(start) = self.selection_start {
@@ -62,33 +210,124 @@ impl TextEditorState {
         text
     }
 
-    fn fwd(&mut self, len: usize, extend_selection: bool) {
+    fn fwd(&mut self, text: &str, extend_selection: bool) {
         if extend_selection {
             self.start_selection();
         } else {
             self.clear_selection();
         }
-        self.cursor += 1;
-        if self.cursor > len {
-            self.cursor = len;
+        self.cursor = next_grapheme(text, self.cursor);
+    }
+
+    fn back(&mut self, text: &str, extend_selection: bool) {
+        if extend_selection {
+            self.start_selection();
+        } else {
+            self.clear_selection();
         }
+        self.cursor = prev_grapheme(text, self.cursor);
     }
-    
-    fn back(&mut self, extend_selection: bool) {
+
+    /// Moves the cursor to `pos`.
+    fn move_to(&mut self, pos: usize, extend_selection: bool) {
         if extend_selection {
             self.start_selection();
         } else {
             self.clear_selection();
         }
-        if self.cursor > 0 {
-            self.cursor -= 1;
+        self.cursor = pos;
+    }
+
+    /// Selects from `start` to `end`, leaving the cursor at `end`.
+    fn select(&mut self, start: usize, end: usize) {
+        self.selection_start = Some(start);
+        self.cursor = end;
+    }
+
+    /// Returns the range of the line containing the cursor. Uses the laid out
+    /// lines if we have them, and otherwise splits at newlines.
+    fn line_range(&self, text: &str) -> (usize, usize) {
+        if self.lines.is_empty() {
+            let start = text[..self.cursor].rfind('\n').map_or(0, |i| i + 1);
+            let end = text[self.cursor..]
+                .find('\n')
+                .map_or(text.len(), |i| self.cursor + i);
+            return (start, end);
+        }
+
+        let i = self.find_line(glyph_index(text, self.cursor));
+        let line = self.lines[i];
+        let start = byte_offset(text, line.glyph_start);
+        if i + 1 == self.lines.len() {
+            (start, text.len())
+        } else {
+            // Stay before the space or newline which wraps the line.
+            let end = (line.glyph_end - 1).max(line.glyph_start);
+            (start, byte_offset(text, end))
+        }
+    }
+
+    /// Returns the cursor position closest to `p`, in glyph space.
+    fn hit_cursor(&self, p: LocalPoint, text: &str) -> usize {
+        let distance = |line: &LineMetrics| {
+            let b = line.bounds;
+            (b.min_y() - p.y).max(p.y - b.max_y()).max(0.0)
+        };
+
+        let mut closest: Option<(usize, &LineMetrics)> = None;
+        for (i, line) in self.lines.iter().enumerate() {
+            if closest.is_none_or(|(_, c)| distance(line) < distance(c)) {
+                closest = Some((i, line));
+            }
+        }
+
+        let glyph = match closest {
+            Some((i, line)) => (line.glyph_start..line.glyph_end)
+                .find(|g| p.x < self.glyph_rects[*g].center().x)
+                .unwrap_or(if i + 1 == self.lines.len() {
+                    line.glyph_end
+                } else {
+                    (line.glyph_end - 1).max(line.glyph_start)
+                }),
+            None => 0,
+        };
+        byte_offset(text, glyph)
+    }
+
+    /// Handles the `count`th click of a multiple click at `p` in the
+    /// editor's local space. Double clicks select a word and triple clicks
+    /// select a line.
+    fn click(&mut self, p: LocalPoint, text: &str, extend_selection: bool, count: usize) {
+        let pos = self.hit_cursor(p - self.text_origin, text);
+        self.history.break_group();
+
+        match (count - 1) % 3 + 1 {
+            1 => self.move_to(pos, extend_selection),
+            2 => {
+                self.cursor = pos;
+                let (start, end) = word_at(text, pos);
+                self.select(start, end);
+            }
+            _ => {
+                self.cursor = pos;
+                let (start, end) = self.line_range(text);
+                self.select(start, end);
+            }
         }
     }
 
-    fn find_line(&self) -> usize {
+    /// Extends the selection to the cursor position closest to `p`, for
+    /// drag selecting.
+    fn drag_to(&mut self, p: LocalPoint, text: &str) {
+        let pos = self.hit_cursor(p - self.text_origin, text);
+        self.move_to(pos, true);
+    }
+
+    /// Returns the line containing `glyph`.
+    fn find_line(&self, glyph: usize) -> usize {
         let mut i = 0;
         for line in &self.lines {
-            if self.cursor >= line.glyph_start && self.cursor < line.glyph_end {
+            if glyph >= line.glyph_start && glyph < line.glyph_end {
                 break;
             }
             i += 1;
@@ -116,57 +355,281 @@ impl TextEditorState {
         closest
     }
 
-    fn down(&mut self, extend_selection: bool) {
+    fn down(&mut self, text: &str, extend_selection: bool) {
         if extend_selection {
             self.start_selection();
         } else {
             self.clear_selection();
         }
-        
-        let p = self.cursor_pos();
+        if self.lines.is_empty() {
+            return;
+        }
 
-        let line = self.find_line() + 1;
+        let p = self.cursor_pos(text);
+
+        let line = self.find_line(glyph_index(text, self.cursor)) + 1;
         if line < self.lines.len() {
             let metrics = self.lines[line];
-            self.cursor =
+            let glyph =
                 self.closest_in_range(p, metrics.glyph_start..metrics.glyph_end, &self.glyph_rects);
+            self.cursor = byte_offset(text, glyph);
         }
     }
 
-    fn up(&mut self, extend_selection: bool) {
+    fn up(&mut self, text: &str, extend_selection: bool) {
         if extend_selection {
             self.start_selection();
         } else {
             self.clear_selection();
         }
-        
-        let p = self.cursor_pos();
+        if self.lines.is_empty() {
+            return;
+        }
+
+        let p = self.cursor_pos(text);
 
-        let line = self.find_line();
+        let line = self.find_line(glyph_index(text, self.cursor));
         if line > 0 {
             let metrics = self.lines[line - 1];
-            self.cursor =
+            let glyph =
                 self.closest_in_range(p, metrics.glyph_start..metrics.glyph_end, &self.glyph_rects);
+            self.cursor = byte_offset(text, glyph);
+        }
+    }
+
+    fn snapshot(&self, text: &str) -> EditSnapshot {
+        EditSnapshot {
+            text: text.to_string(),
+            cursor: self.cursor,
+            selection_start: self.selection_start,
+        }
+    }
+
+    fn restore(&mut self, snapshot: EditSnapshot) -> String {
+        self.cursor = snapshot.cursor.min(snapshot.text.len());
+        self.selection_start = snapshot.selection_start;
+        self.history.break_group();
+        snapshot.text
+    }
+
+    /// Reverts the last group of edits.
+    fn undo(&mut self, text: String) -> String {
+        match self.history.undo.pop() {
+            Some(snapshot) => {
+                let current = self.snapshot(&text);
+                self.history.redo.push(current);
+                self.restore(snapshot)
+            }
+            None => text,
+        }
+    }
+
+    /// Reapplies the last undone group of edits.
+    fn redo(&mut self, text: String) -> String {
+        match self.history.redo.pop() {
+            Some(snapshot) => {
+                let current = self.snapshot(&text);
+                self.history.undo.push(current);
+                self.restore(snapshot)
+            }
+            None => text,
+        }
+    }
+
+    /// Returns the selected text, if any.
+    fn selected_text<'a>(&self, text: &'a str) -> Option<&'a str> {
+        match self.selection_range() {
+            Some((start, end)) if start != end => Some(&text[start..end]),
+            _ => None,
+        }
+    }
+
+    /// Returns the text to show, which includes any IME composition.
+    fn display_text(&self, text: &str) -> String {
+        let mut display = text.to_string();
+        display.insert_str(self.cursor, &self.preedit);
+        display
+    }
+
+    /// Shows the IME's composition at the cursor.
+    fn set_preedit(&mut self, preedit: &str, cursor: Option<(usize, usize)>) {
+        self.preedit = preedit.to_string();
+        self.preedit_cursor = cursor;
+    }
+
+    /// Inserts text committed by the IME, replacing the selection.
+    fn commit(&mut self, committed: &str, text: String) -> String {
+        self.clamp(&text);
+        self.set_preedit("", None);
+        if committed.is_empty() {
+            return text;
+        }
+
+        let before = self.snapshot(&text);
+        let mut t = self.delete_selection(text);
+        t.insert_str(self.cursor, committed);
+        self.cursor += committed.len();
+        self.history.record(
+            before,
+            EditKind::Insert {
+                whitespace: committed.chars().all(char::is_whitespace),
+            },
+        );
+        t
+    }
+
+    /// Handles the cut, copy and paste shortcuts. Returns `None` if `k` isn't one of them.
+    fn clipboard_key(
+        &mut self,
+        k: &Key,
+        text: &str,
+        mods: KeyboardModifiers,
+        clipboard: &dyn Clipboard,
+    ) -> Option<String> {
+        if !mods.shortcut() {
+            return None;
+        }
+        self.clamp(text);
+        match k {
+            Key::Character('c') | Key::Character('C') => {
+                if let Some(selected) = self.selected_text(text) {
+                    clipboard.set_text(selected);
+                }
+                Some(text.to_string())
+            }
+            Key::Character('x') | Key::Character('X') => {
+                let selected = self.selected_text(text)?.to_string();
+                clipboard.set_text(&selected);
+                let before = self.snapshot(text);
+                let new_text = self.delete_selection(text.to_string());
+                self.history.record(before, EditKind::Replace);
+                Some(new_text)
+            }
+            Key::Character('v') | Key::Character('V') => {
+                let pasted = clipboard.get_text().unwrap_or_default();
+                if pasted.is_empty() {
+                    return Some(text.to_string());
+                }
+                let before = self.snapshot(text);
+                let mut new_text = self.delete_selection(text.to_string());
+                new_text.insert_str(self.cursor, &pasted);
+                self.cursor += pasted.len();
+                self.clear_selection();
+                self.history.record(before, EditKind::Replace);
+                Some(new_text)
+            }
+            _ => None,
+        }
+    }
+
+    fn key(&mut self, k: &Key, text: String, mods: KeyboardModifiers) -> String {
+        let shift_pressed = mods.shift;
+        self.clamp(&text);
+
+        // Shortcut characters are never inserted.
+        if let (true, Key::Character(c)) = (mods.shortcut(), k) {
+            return match c {
+                'z' if shift_pressed => self.redo(text),
+                'Z' => self.redo(text),
+                'z' => self.undo(text),
+                _ => text,
+            };
         }
+
+        let before = self.snapshot(&text);
+        let kind = match k {
+            Key::Backspace | Key::Delete => Some(EditKind::Delete),
+            Key::Character(c) => Some(EditKind::Insert {
+                whitespace: c.is_whitespace(),
+            }),
+            Key::Space | Key::Tab => Some(EditKind::Insert { whitespace: true }),
+            _ => None,
+        };
+
+        let new_text = self.edit(k, text, mods);
+
+        match kind {
+            Some(kind) if new_text != before.text => self.history.record(before, kind),
+            Some(_) => (),
+            None => self.history.break_group(),
+        }
+
+        new_text
     }
 
-    fn key(&mut self, k: &Key, text: String, shift_pressed: bool) -> String {
-        
+    fn edit(&mut self, k: &Key, text: String, mods: KeyboardModifiers) -> String {
+        let shift_pressed = mods.shift;
+
+        // Alt jumps by words on macOS, Control does elsewhere.
+        // Command jumps to the ends of lines.
+        let by_word = mods.alt || mods.control;
+        let by_line = mods.command;
+
+        let has_selection = matches!(self.selection_range(), Some((start, end)) if start != end);
+
+        match k {
+            Key::ArrowLeft if by_line => {
+                let (start, _) = self.line_range(&text);
+                self.move_to(start, shift_pressed);
+                return text;
+            }
+            Key::ArrowRight if by_line => {
+                let (_, end) = self.line_range(&text);
+                self.move_to(end, shift_pressed);
+                return text;
+            }
+            Key::ArrowUp if by_line => {
+                self.move_to(0, shift_pressed);
+                return text;
+            }
+            Key::ArrowDown if by_line => {
+                self.move_to(text.len(), shift_pressed);
+                return text;
+            }
+            Key::ArrowLeft if by_word => {
+                self.move_to(prev_word(&text, self.cursor), shift_pressed);
+                return text;
+            }
+            Key::ArrowRight if by_word => {
+                self.move_to(next_word(&text, self.cursor), shift_pressed);
+                return text;
+            }
+            Key::Backspace if (by_word || by_line) && !has_selection => {
+                let start = if by_line {
+                    self.line_range(&text).0
+                } else {
+                    prev_word(&text, self.cursor)
+                };
+                self.select(start, self.cursor);
+                return self.delete_selection(text);
+            }
+            Key::Delete if (by_word || by_line) && !has_selection => {
+                let end = if by_line {
+                    self.line_range(&text).1
+                } else {
+                    next_word(&text, self.cursor)
+                };
+                self.select(self.cursor, end);
+                return self.delete_selection(text);
+            }
+            _ => (),
+        }
+
         match k {
             Key::ArrowLeft => {
-                self.back(shift_pressed);
+                self.back(&text, shift_pressed);
                 text
             }
             Key::ArrowRight => {
-                self.fwd(text.len(), shift_pressed);
+                self.fwd(&text, shift_pressed);
                 text
             }
             Key::ArrowUp => {
-                self.up(shift_pressed);
+                self.up(&text, shift_pressed);
                 text
             }
             Key::ArrowDown => {
-                self.down(shift_pressed);
+                self.down(&text, shift_pressed);
                 text
             }
             Key::Backspace => {
@@ -177,8 +640,9 @@ impl TextEditorState {
                 if !had_selection && self.cursor > 0 {
                     // No selection was deleted, do normal backspace
                     let mut t = t;
-                    t.remove(self.cursor - 1);
-                    self.back(false);
+                    let start = prev_grapheme(&t, self.cursor);
+                    t.drain(start..self.cursor);
+                    self.cursor = start;
                     t
                 } else {
                     t
@@ -192,7 +656,8 @@ impl TextEditorState {
                 if !had_selection && self.cursor < t.len() {
                     // No selection was deleted, delete character at cursor
                     let mut t = t;
-                    t.remove(self.cursor);
+                    let end = next_grapheme(&t, self.cursor);
+                    t.drain(self.cursor..end);
                     t
                 } else {
                     t
@@ -202,7 +667,7 @@ impl TextEditorState {
                 // Replace selection or insert character
                 let mut t = self.delete_selection(text);
                 t.insert(self.cursor, *c);
-                self.cursor += 1;
+                self.cursor += c.len_utf8();
                 t
             }
             Key::Space => {
@@ -212,6 +677,12 @@ impl TextEditorState {
                 self.cursor += 1;
                 t
             }
+            Key::Tab => {
+                let mut t = self.delete_selection(text);
+                t.insert(self.cursor, '\t');
+                self.cursor += 1;
+                t
+            }
             Key::Home => {
                 if shift_pressed {
                     self.start_selection();
@@ -242,6 +713,10 @@ impl TextEditorState {
             selection_start: None,
             glyph_rects: vec![],
             lines: vec![],
+            history: UndoHistory::default(),
+            text_origin: LocalOffset::zero(),
+            preedit: String::new(),
+            preedit_cursor: None,
         }
     }
 }
@@ -250,6 +725,29 @@ impl TextEditorState {
 mod tests {
     use super::*;
 
+    const NO_MODS: KeyboardModifiers = KeyboardModifiers {
+        shift: false,
+        control: false,
+        alt: false,
+        command: false,
+    };
+
+    const SHIFT: KeyboardModifiers = KeyboardModifiers {
+        shift: true,
+        ..NO_MODS
+    };
+
+    const CMD: KeyboardModifiers = KeyboardModifiers {
+        command: true,
+        ..NO_MODS
+    };
+
+    const SHIFT_CMD: KeyboardModifiers = KeyboardModifiers {
+        shift: true,
+        command: true,
+        ..NO_MODS
+    };
+
     fn make_state(cursor: usize, text_len: usize) -> TextEditorState {
         // Create glyph rects for a single line of text, each char 10px wide, 20px tall
         let glyph_rects: Vec<LocalRect> = (0..text_len)
@@ -265,6 +763,7 @@ mod tests {
             selection_start: None,
             glyph_rects,
             lines,
+            ..TextEditorState::new()
         }
     }
 
@@ -292,13 +791,14 @@ mod tests {
             selection_start: None,
             glyph_rects,
             lines,
+            ..TextEditorState::new()
         }
     }
 
     #[test]
     fn test_cursor_pos_middle() {
         let s = make_state(2, 5);
-        let p = s.cursor_pos();
+        let p = s.cursor_pos("abcde");
         assert_eq!(p.x, 20.0);
         assert_eq!(p.y, 0.0);
     }
@@ -306,7 +806,7 @@ mod tests {
     #[test]
     fn test_cursor_pos_at_end() {
         let s = make_state(5, 5);
-        let p = s.cursor_pos();
+        let p = s.cursor_pos("abcde");
         // At end: last glyph origin.x + width
         assert_eq!(p.x, 50.0);
         assert_eq!(p.y, 0.0);
@@ -315,7 +815,7 @@ mod tests {
     #[test]
     fn test_cursor_pos_empty() {
         let s = TextEditorState::new();
-        let p = s.cursor_pos();
+        let p = s.cursor_pos("");
         assert_eq!(p.x, 0.0);
         assert_eq!(p.y, -20.0);
     }
@@ -323,23 +823,23 @@ mod tests {
     #[test]
     fn test_fwd_and_back() {
         let mut s = make_state(0, 5);
-        s.fwd(5, false);
+        s.fwd("abcde", false);
         assert_eq!(s.cursor, 1);
-        s.fwd(5, false);
+        s.fwd("abcde", false);
         assert_eq!(s.cursor, 2);
-        s.back(false);
+        s.back("abcde", false);
         assert_eq!(s.cursor, 1);
-        s.back(false);
+        s.back("abcde", false);
         assert_eq!(s.cursor, 0);
         // Can't go before 0
-        s.back(false);
+        s.back("abcde", false);
         assert_eq!(s.cursor, 0);
     }
 
     #[test]
     fn test_fwd_clamps_at_end() {
         let mut s = make_state(5, 5);
-        s.fwd(5, false);
+        s.fwd("abcde", false);
         assert_eq!(s.cursor, 5);
     }
 
@@ -347,18 +847,18 @@ mod tests {
     fn test_selection_with_shift() {
         let mut s = make_state(2, 5);
         // Move right with shift to start selection
-        s.fwd(5, true);
+        s.fwd("abcde", true);
         assert_eq!(s.cursor, 3);
         assert_eq!(s.selection_start, Some(2));
         assert_eq!(s.selection_range(), Some((2, 3)));
 
         // Extend selection
-        s.fwd(5, true);
+        s.fwd("abcde", true);
         assert_eq!(s.cursor, 4);
         assert_eq!(s.selection_range(), Some((2, 4)));
 
         // Move without shift clears selection
-        s.fwd(5, false);
+        s.fwd("abcde", false);
         assert_eq!(s.cursor, 5);
         assert_eq!(s.selection_start, None);
     }
@@ -366,7 +866,7 @@ mod tests {
     #[test]
     fn test_selection_backwards() {
         let mut s = make_state(3, 5);
-        s.back(true);
+        s.back("abcde", true);
         assert_eq!(s.cursor, 2);
         assert_eq!(s.selection_start, Some(3));
         // selection_range normalizes: start <= end
@@ -397,7 +897,7 @@ mod tests {
     #[test]
     fn test_key_character() {
         let mut s = make_state(2, 5);
-        let result = s.key(&Key::Character('x'), "abcde".to_string(), false);
+        let result = s.key(&Key::Character('x'), "abcde".to_string(), NO_MODS);
         assert_eq!(result, "abxcde");
         assert_eq!(s.cursor, 3);
     }
@@ -405,7 +905,7 @@ mod tests {
     #[test]
     fn test_key_space() {
         let mut s = make_state(0, 3);
-        let result = s.key(&Key::Space, "abc".to_string(), false);
+        let result = s.key(&Key::Space, "abc".to_string(), NO_MODS);
         assert_eq!(result, " abc");
         assert_eq!(s.cursor, 1);
     }
@@ -413,7 +913,7 @@ mod tests {
     #[test]
     fn test_key_backspace() {
         let mut s = make_state(3, 5);
-        let result = s.key(&Key::Backspace, "abcde".to_string(), false);
+        let result = s.key(&Key::Backspace, "abcde".to_string(), NO_MODS);
         assert_eq!(result, "abde");
         assert_eq!(s.cursor, 2);
     }
@@ -421,7 +921,7 @@ mod tests {
     #[test]
     fn test_key_backspace_at_start() {
         let mut s = make_state(0, 3);
-        let result = s.key(&Key::Backspace, "abc".to_string(), false);
+        let result = s.key(&Key::Backspace, "abc".to_string(), NO_MODS);
         assert_eq!(result, "abc");
         assert_eq!(s.cursor, 0);
     }
@@ -431,7 +931,7 @@ mod tests {
         let mut s = make_state(0, 5);
         s.selection_start = Some(1);
         s.cursor = 4;
-        let result = s.key(&Key::Backspace, "abcde".to_string(), false);
+        let result = s.key(&Key::Backspace, "abcde".to_string(), NO_MODS);
         assert_eq!(result, "ae");
         assert_eq!(s.cursor, 1);
     }
@@ -439,7 +939,7 @@ mod tests {
     #[test]
     fn test_key_delete() {
         let mut s = make_state(2, 5);
-        let result = s.key(&Key::Delete, "abcde".to_string(), false);
+        let result = s.key(&Key::Delete, "abcde".to_string(), NO_MODS);
         assert_eq!(result, "abde");
         assert_eq!(s.cursor, 2);
     }
@@ -447,7 +947,7 @@ mod tests {
     #[test]
     fn test_key_delete_at_end() {
         let mut s = make_state(3, 3);
-        let result = s.key(&Key::Delete, "abc".to_string(), false);
+        let result = s.key(&Key::Delete, "abc".to_string(), NO_MODS);
         assert_eq!(result, "abc");
         assert_eq!(s.cursor, 3);
     }
@@ -455,18 +955,18 @@ mod tests {
     #[test]
     fn test_key_home_end() {
         let mut s = make_state(3, 5);
-        let result = s.key(&Key::Home, "abcde".to_string(), false);
+        let result = s.key(&Key::Home, "abcde".to_string(), NO_MODS);
         assert_eq!(result, "abcde");
         assert_eq!(s.cursor, 0);
 
-        s.key(&Key::End, result, false);
+        s.key(&Key::End, result, NO_MODS);
         assert_eq!(s.cursor, 5);
     }
 
     #[test]
     fn test_key_home_with_shift() {
         let mut s = make_state(3, 5);
-        s.key(&Key::Home, "abcde".to_string(), true);
+        s.key(&Key::Home, "abcde".to_string(), SHIFT);
         assert_eq!(s.cursor, 0);
         assert_eq!(s.selection_start, Some(3));
         assert_eq!(s.selection_range(), Some((0, 3)));
@@ -477,7 +977,7 @@ mod tests {
         let mut s = make_state(0, 5);
         s.selection_start = Some(1);
         s.cursor = 3;
-        let result = s.key(&Key::Character('x'), "abcde".to_string(), false);
+        let result = s.key(&Key::Character('x'), "abcde".to_string(), NO_MODS);
         assert_eq!(result, "axde");
         assert_eq!(s.cursor, 2);
     }
@@ -487,10 +987,10 @@ mod tests {
         let mut s = make_state(2, 5);
         let text = "abcde".to_string();
 
-        let result = s.key(&Key::ArrowLeft, text, false);
+        let result = s.key(&Key::ArrowLeft, text, NO_MODS);
         assert_eq!(s.cursor, 1);
 
-        let result = s.key(&Key::ArrowRight, result, false);
+        let result = s.key(&Key::ArrowRight, result, NO_MODS);
         assert_eq!(s.cursor, 2);
         assert_eq!(result, "abcde");
     }
@@ -500,19 +1000,19 @@ mod tests {
         // Two lines: "abc" (3 chars) and "defgh" (5 chars)
         let mut s = make_multiline_state(1, &[3, 5]);
         // cursor at index 1 in first line, move down
-        s.down(false);
+        s.down("abcdefgh", false);
         // Should move to closest glyph in second line
         assert!(s.cursor >= 3 && s.cursor < 8);
 
         // Move back up
-        s.up(false);
+        s.up("abcdefgh", false);
         assert!(s.cursor < 3);
     }
 
     #[test]
     fn test_up_at_first_line_stays() {
         let mut s = make_multiline_state(1, &[3, 5]);
-        s.up(false);
+        s.up("abcdefgh", false);
         // Already on first line, cursor shouldn't change
         assert_eq!(s.cursor, 1);
     }
@@ -520,7 +1020,7 @@ mod tests {
     #[test]
     fn test_down_at_last_line_stays() {
         let mut s = make_multiline_state(4, &[3, 5]);
-        s.down(false);
+        s.down("abcdefgh", false);
         // Already on last line, cursor shouldn't change
         assert_eq!(s.cursor, 4);
     }
@@ -528,19 +1028,655 @@ mod tests {
     #[test]
     fn test_find_line() {
         let s = make_multiline_state(0, &[3, 5, 2]);
-        // cursor 0 is on line 0
-        assert_eq!(s.find_line(), 0);
+        // glyph 0 is on line 0
+        assert_eq!(s.find_line(0), 0);
+        // glyph 4 is on line 1 (glyphs 3..8)
+        assert_eq!(s.find_line(4), 1);
+        // glyph 9 is on line 2 (glyphs 8..10)
+        assert_eq!(s.find_line(9), 2);
+    }
 
-        let s2 = make_multiline_state(4, &[3, 5, 2]);
-        // cursor 4 is on line 1 (glyphs 3..8)
-        assert_eq!(s2.find_line(), 1);
+    fn type_str(s: &mut TextEditorState, text: String, typed: &str) -> String {
+        typed.chars().fold(text, |t, c| {
+            let k = if c == ' ' {
+                Key::Space
+            } else {
+                Key::Character(c)
+            };
+            s.key(&k, t, NO_MODS)
+        })
+    }
+
+    #[test]
+    fn test_undo_typing_by_word() {
+        let mut s = TextEditorState::new();
+        let t = type_str(&mut s, String::new(), "hello world");
+        assert_eq!(t, "hello world");
 
-        let s3 = make_multiline_state(9, &[3, 5, 2]);
-        // cursor 9 is on line 2 (glyphs 8..10)
-        assert_eq!(s3.find_line(), 2);
+        let t = s.key(&Key::Character('z'), t, CMD);
+        assert_eq!(t, "hello ");
+        assert_eq!(s.cursor, 6);
+
+        let t = s.key(&Key::Character('z'), t, CMD);
+        assert_eq!(t, "");
+        assert_eq!(s.cursor, 0);
+
+        // Nothing left to undo.
+        let t = s.key(&Key::Character('z'), t, CMD);
+        assert_eq!(t, "");
+    }
+
+    #[test]
+    fn test_redo() {
+        let mut s = TextEditorState::new();
+        let t = type_str(&mut s, String::new(), "ab cd");
+        let t = s.key(&Key::Character('z'), t, CMD);
+        let t = s.key(&Key::Character('z'), t, CMD);
+        assert_eq!(t, "");
+
+        let t = s.key(&Key::Character('Z'), t, SHIFT_CMD);
+        assert_eq!(t, "ab ");
+        let t = s.key(&Key::Character('Z'), t, SHIFT_CMD);
+        assert_eq!(t, "ab cd");
+        assert_eq!(s.cursor, 5);
+
+        // A new edit clears the redo stack.
+        let t = s.key(&Key::Character('z'), t, CMD);
+        let t = type_str(&mut s, t, "x");
+        let t = s.key(&Key::Character('Z'), t, SHIFT_CMD);
+        assert_eq!(t, "ab x");
+    }
+
+    #[test]
+    fn test_undo_coalesces_deletes() {
+        let mut s = make_state(5, 5);
+        let t = "abcde".to_string();
+        let t = s.key(&Key::Backspace, t, NO_MODS);
+        let t = s.key(&Key::Backspace, t, NO_MODS);
+        let t = s.key(&Key::Backspace, t, NO_MODS);
+        assert_eq!(t, "ab");
+
+        let t = s.key(&Key::Character('z'), t, CMD);
+        assert_eq!(t, "abcde");
+        assert_eq!(s.cursor, 5);
+    }
+
+    #[test]
+    fn test_cursor_move_breaks_group() {
+        let mut s = make_state(5, 5);
+        let t = "abcde".to_string();
+        let t = s.key(&Key::Backspace, t, NO_MODS);
+        let t = s.key(&Key::ArrowLeft, t, NO_MODS);
+        let t = s.key(&Key::Backspace, t, NO_MODS);
+        assert_eq!(t, "abd");
+
+        let t = s.key(&Key::Character('z'), t, CMD);
+        assert_eq!(t, "abcd");
+        assert_eq!(s.cursor, 3);
+    }
+
+    #[test]
+    fn test_undo_restores_selection() {
+        let mut s = make_state(0, 5);
+        s.selection_start = Some(1);
+        s.cursor = 4;
+        let t = s.key(&Key::Backspace, "abcde".to_string(), NO_MODS);
+        assert_eq!(t, "ae");
+
+        let t = s.key(&Key::Character('z'), t, CMD);
+        assert_eq!(t, "abcde");
+        assert_eq!(s.selection_range(), Some((1, 4)));
+        assert_eq!(s.cursor, 4);
+    }
+
+    #[test]
+    fn test_shortcut_does_not_insert() {
+        let mut s = make_state(0, 3);
+        let t = s.key(&Key::Character('q'), "abc".to_string(), CMD);
+        assert_eq!(t, "abc");
+        assert_eq!(s.cursor, 0);
+    }
+
+    #[test]
+    fn test_control_z_undoes() {
+        let ctrl = KeyboardModifiers {
+            control: true,
+            ..NO_MODS
+        };
+        let mut s = TextEditorState::new();
+        let t = type_str(&mut s, String::new(), "abc");
+        let t = s.key(&Key::Character('z'), t, ctrl);
+        assert_eq!(t, "");
+    }
+
+    #[test]
+    fn test_copy_paste() {
+        let clipboard = MemoryClipboard::new();
+        let mut s = make_state(1, 5);
+        s.selection_start = Some(1);
+        s.cursor = 3;
+        let t = s.clipboard_key(&Key::Character('c'), "abcde", CMD, &clipboard);
+        assert_eq!(t, Some("abcde".to_string()));
+        assert_eq!(clipboard.get_text(), Some("bc".to_string()));
+
+        s.clear_selection();
+        s.cursor = 5;
+        let t = s.clipboard_key(&Key::Character('v'), "abcde", CMD, &clipboard);
+        assert_eq!(t, Some("abcdebc".to_string()));
+        assert_eq!(s.cursor, 7);
+    }
+
+    #[test]
+    fn test_cut_and_undo() {
+        let clipboard = MemoryClipboard::new();
+        let mut s = make_state(0, 5);
+        s.selection_start = Some(1);
+        s.cursor = 4;
+        let t = s
+            .clipboard_key(&Key::Character('x'), "abcde", CMD, &clipboard)
+            .unwrap();
+        assert_eq!(t, "ae");
+        assert_eq!(s.cursor, 1);
+        assert_eq!(clipboard.get_text(), Some("bcd".to_string()));
+
+        let t = s.key(&Key::Character('z'), t, CMD);
+        assert_eq!(t, "abcde");
+        assert_eq!(s.selection_range(), Some((1, 4)));
+    }
+
+    #[test]
+    fn test_cut_without_selection_does_nothing() {
+        let clipboard = MemoryClipboard::new();
+        clipboard.set_text("keep");
+        let mut s = make_state(2, 5);
+        assert_eq!(
+            s.clipboard_key(&Key::Character('x'), "abcde", CMD, &clipboard),
+            None
+        );
+        assert_eq!(clipboard.get_text(), Some("keep".to_string()));
+    }
+
+    #[test]
+    fn test_paste_replaces_selection() {
+        let clipboard = MemoryClipboard::new();
+        clipboard.set_text("XY");
+        let mut s = make_state(0, 5);
+        s.selection_start = Some(1);
+        s.cursor = 4;
+        let t = s.clipboard_key(&Key::Character('v'), "abcde", CMD, &clipboard);
+        assert_eq!(t, Some("aXYe".to_string()));
+        assert_eq!(s.cursor, 3);
+        assert_eq!(s.selection_start, None);
+    }
+
+    #[test]
+    fn test_clipboard_shortcuts_in_harness() {
+        let ui = state(|| "hello".to_string(), |s, _| text_editor(s));
+        let mut h = TestHarness::new(&ui, [200.0, 100.0]);
+        let text = h.root_state::<String>();
+
+        // Focus the editor, then select "lo" from the end.
+        h.tap([100.0, 50.0]);
+        h.key(Key::End);
+        h.set_key_mods(SHIFT);
+        h.key(Key::ArrowLeft);
+        h.key(Key::ArrowLeft);
+
+        h.set_key_mods(CMD);
+        h.key(Key::Character('x'));
+        assert_eq!(h.get(text), "hel");
+        assert_eq!(h.cx.clipboard().get_text(), Some("lo".to_string()));
+
+        h.key(Key::Home);
+        h.key(Key::Character('v'));
+        assert_eq!(h.get(text), "lohel");
+    }
+
+    const ALT: KeyboardModifiers = KeyboardModifiers {
+        alt: true,
+        ..NO_MODS
+    };
+
+    const CTRL: KeyboardModifiers = KeyboardModifiers {
+        control: true,
+        ..NO_MODS
+    };
+
+    #[test]
+    fn test_word_boundaries() {
+        let text = "hello, wide world";
+        assert_eq!(next_word(text, 0), 5);
+        assert_eq!(next_word(text, 5), 11);
+        assert_eq!(next_word(text, 12), 17);
+        assert_eq!(next_word(text, 17), 17);
+        assert_eq!(prev_word(text, 17), 12);
+        assert_eq!(prev_word(text, 12), 7);
+        assert_eq!(prev_word(text, 7), 0);
+        assert_eq!(prev_word(text, 0), 0);
+
+        // Non-ASCII words are still words.
+        let text = "über straße";
+        assert_eq!(next_word(text, 0), "über".len());
+        assert_eq!(prev_word(text, text.len()), "über ".len());
+    }
+
+    #[test]
+    fn test_word_at() {
+        let text = "hello, world";
+        assert_eq!(word_at(text, 0), (0, 5));
+        assert_eq!(word_at(text, 3), (0, 5));
+        assert_eq!(word_at(text, 5), (5, 6));
+        assert_eq!(word_at(text, 7), (7, 12));
+        assert_eq!(word_at(text, 12), (7, 12));
+        assert_eq!(word_at("", 0), (0, 0));
+    }
+
+    #[test]
+    fn test_word_jumps() {
+        let text = "one two three".to_string();
+        let mut s = make_state(0, text.len());
+
+        let text = s.key(&Key::ArrowRight, text, ALT);
+        assert_eq!(s.cursor, 3);
+        let text = s.key(&Key::ArrowRight, text, CTRL);
+        assert_eq!(s.cursor, 7);
+        let text = s.key(&Key::ArrowLeft, text, ALT);
+        assert_eq!(s.cursor, 4);
+
+        let shift_alt = KeyboardModifiers { shift: true, ..ALT };
+        s.key(&Key::ArrowRight, text, shift_alt);
+        assert_eq!(s.selection_range(), Some((4, 7)));
+    }
+
+    #[test]
+    fn test_line_jumps() {
+        // Two wrapped lines: "abc " and "def".
+        let text = "abc def".to_string();
+        let mut s = make_multiline_state(5, &[4, 3]);
+
+        let text = s.key(&Key::ArrowLeft, text, CMD);
+        assert_eq!(s.cursor, 4);
+        let text = s.key(&Key::ArrowRight, text, CMD);
+        assert_eq!(s.cursor, 7);
+
+        s.cursor = 1;
+        let text = s.key(&Key::ArrowRight, text, CMD);
+        assert_eq!(s.cursor, 3);
+
+        let text = s.key(&Key::ArrowDown, text, CMD);
+        assert_eq!(s.cursor, 7);
+        s.key(&Key::ArrowUp, text, SHIFT_CMD);
+        assert_eq!(s.selection_range(), Some((0, 7)));
+    }
+
+    #[test]
+    fn test_line_jumps_without_layout() {
+        let mut s = TextEditorState::new();
+        s.cursor = 5;
+        let text = s.key(&Key::ArrowLeft, "ab\ncdef\ng".to_string(), CMD);
+        assert_eq!(s.cursor, 3);
+        s.key(&Key::ArrowRight, text, CMD);
+        assert_eq!(s.cursor, 7);
+    }
+
+    #[test]
+    fn test_delete_word() {
+        let text = "one two three".to_string();
+        let mut s = make_state(7, text.len());
+
+        let text = s.key(&Key::Backspace, text, ALT);
+        assert_eq!(text, "one  three");
+        assert_eq!(s.cursor, 4);
+
+        let text = s.key(&Key::Delete, text, CTRL);
+        assert_eq!(text, "one ");
+        assert_eq!(s.cursor, 4);
+
+        // Consecutive deletes undo together.
+        let text = s.key(&Key::Character('z'), text, CMD);
+        assert_eq!(text, "one two three");
+    }
+
+    #[test]
+    fn test_hit_cursor() {
+        let s = make_multiline_state(0, &[4, 3]);
+        let text = "abc def";
+        assert_eq!(s.hit_cursor([-5.0, 10.0].into(), text), 0);
+        assert_eq!(s.hit_cursor([14.0, 10.0].into(), text), 1);
+        assert_eq!(s.hit_cursor([16.0, 10.0].into(), text), 2);
+        assert_eq!(s.hit_cursor([100.0, 10.0].into(), text), 3);
+        assert_eq!(s.hit_cursor([100.0, -10.0].into(), text), 7);
+        assert_eq!(s.hit_cursor([0.0, -100.0].into(), text), 4);
+    }
+
+    #[test]
+    fn test_multi_click() {
+        let text = "one two\nthree";
+        let mut s = make_multiline_state(0, &[8, 5]);
+        let p: LocalPoint = [51.0, 10.0].into();
+
+        s.click(p, text, false, 1);
+        assert_eq!(s.cursor, 5);
+        assert_eq!(s.selection_start, None);
+
+        s.click(p, text, false, 2);
+        assert_eq!(s.selection_range(), Some((4, 7)));
+
+        s.click(p, text, false, 3);
+        assert_eq!(s.selection_range(), Some((0, 7)));
+
+        // A fourth click starts over.
+        s.click(p, text, false, 4);
+        assert_eq!(s.cursor, 5);
+        assert_eq!(s.selection_start, None);
+
+        // Shift click extends the selection.
+        s.click([0.0, -10.0].into(), text, true, 1);
+        assert_eq!(s.selection_range(), Some((5, 8)));
+    }
+
+    #[test]
+    fn test_drag_select() {
+        let text = "one two\nthree";
+        let mut s = make_multiline_state(0, &[8, 5]);
+        s.click([11.0, 10.0].into(), text, false, 1);
+        s.drag_to([36.0, 10.0].into(), text);
+        assert_eq!(s.selected_text(text), Some("ne "));
+
+        // Dragging back past the start selects backwards.
+        s.drag_to([0.0, 10.0].into(), text);
+        assert_eq!(s.selected_text(text), Some("o"));
+
+        s.drag_to([20.0, -10.0].into(), text);
+        assert_eq!(s.selected_text(text), Some("ne two\nth"));
+    }
+
+    #[test]
+    fn test_selectable_text_copies() {
+        let ui = selectable_text("hello world");
+        let mut h = TestHarness::new(&ui, [200.0, 100.0]);
+
+        // Without glyph positions from vger every point is the start,
+        // so a double click selects the first word.
+        h.tap([10.0, 9.0]);
+        h.tap([10.0, 9.0]);
+        h.set_key_mods(CMD);
+        h.key(Key::Character('c'));
+        assert_eq!(h.cx.clipboard().get_text(), Some("hello".to_string()));
+
+        h.key(Key::Character('a'));
+        h.key(Key::Character('c'));
+        assert_eq!(h.cx.clipboard().get_text(), Some("hello world".to_string()));
+
+        // Editing keys do nothing.
+        h.set_key_mods(NO_MODS);
+        h.key(Key::Backspace);
+        h.set_key_mods(CMD);
+        h.key(Key::Character('c'));
+        assert_eq!(h.cx.clipboard().get_text(), Some("hello world".to_string()));
+    }
+
+    #[test]
+    fn test_truncated_text_not_selectable() {
+        let ui = text("hello world")
+            .line_limit(1)
+            .max_width(50.0)
+            .selectable();
+        let mut h = TestHarness::new(&ui, [200.0, 100.0]);
+
+        h.tap([10.0, 9.0]);
+        h.set_key_mods(CMD);
+        h.key(Key::Character('a'));
+        h.key(Key::Character('c'));
+        assert_eq!(h.cx.clipboard().get_text(), None);
+    }
+
+    #[test]
+    fn test_grapheme_boundaries() {
+        // "e" + combining acute, then a thumbs up with a skin tone.
+        let text = "e\u{301}👍🏽!";
+        assert_eq!(next_grapheme(text, 0), 3);
+        assert_eq!(next_grapheme(text, 3), 11);
+        assert_eq!(next_grapheme(text, 11), 12);
+        assert_eq!(next_grapheme(text, 12), 12);
+        assert_eq!(prev_grapheme(text, 12), 11);
+        assert_eq!(prev_grapheme(text, 11), 3);
+        assert_eq!(prev_grapheme(text, 3), 0);
+        assert_eq!(prev_grapheme(text, 0), 0);
+
+        // Inside a char, or between a char and its combining mark.
+        assert_eq!(grapheme_start(text, 1), 0);
+        assert_eq!(grapheme_start(text, 5), 3);
+        assert_eq!(grapheme_start(text, 7), 3);
+        assert_eq!(grapheme_start(text, 100), 12);
+    }
+
+    #[test]
+    fn test_glyph_byte_mapping() {
+        let text = "aé👍🏽b";
+        assert_eq!(glyph_index(text, 0), 0);
+        assert_eq!(glyph_index(text, 1), 1);
+        assert_eq!(glyph_index(text, 3), 2);
+        assert_eq!(glyph_index(text, 11), 4);
+        assert_eq!(glyph_index(text, text.len()), 5);
+
+        assert_eq!(byte_offset(text, 2), 3);
+        // The skin tone modifier is its own glyph but belongs to the thumb.
+        assert_eq!(byte_offset(text, 3), 3);
+        assert_eq!(byte_offset(text, 4), 11);
+        assert_eq!(byte_offset(text, 5), text.len());
+    }
+
+    #[test]
+    fn test_cursor_pos_multibyte() {
+        let text = "aé👍🏽b";
+        let mut s = make_state(0, text.chars().count());
+        s.cursor = 3;
+        assert_eq!(s.cursor_pos(text).x, 20.0);
+        s.cursor = 11;
+        assert_eq!(s.cursor_pos(text).x, 40.0);
+        s.cursor = text.len();
+        assert_eq!(s.cursor_pos(text).x, 50.0);
+    }
+
+    #[test]
+    fn test_arrows_skip_grapheme_clusters() {
+        let text = "ae\u{301}🇫🇷z".to_string();
+        let mut s = make_state(0, text.chars().count());
+        let mut stops = vec![];
+        let mut text = text;
+        for _ in 0..5 {
+            text = s.key(&Key::ArrowRight, text, NO_MODS);
+            stops.push(s.cursor);
+        }
+        assert_eq!(stops, vec![1, 4, 12, 13, 13]);
+
+        let text = s.key(&Key::ArrowLeft, text, NO_MODS);
+        let _ = s.key(&Key::ArrowLeft, text, NO_MODS);
+        assert_eq!(s.cursor, 4);
+    }
+
+    #[test]
+    fn test_edit_multibyte() {
+        let mut s = TextEditorState::new();
+        let text = type_str(&mut s, String::new(), "héllo wörld");
+        assert_eq!(text, "héllo wörld");
+        assert_eq!(s.cursor, text.len());
+
+        // Backspace removes whole chars.
+        let text = s.key(&Key::Backspace, text, NO_MODS);
+        let text = s.key(&Key::Backspace, text, NO_MODS);
+        let text = s.key(&Key::Backspace, text, NO_MODS);
+        assert_eq!(text, "héllo wö");
+
+        let text = s.key(&Key::Backspace, text, NO_MODS);
+        assert_eq!(text, "héllo w");
+
+        // Delete forward over a combining sequence and an emoji.
+        let text = "e\u{301}👍🏽x".to_string();
+        s.cursor = 0;
+        let text = s.key(&Key::Delete, text, NO_MODS);
+        assert_eq!(text, "👍🏽x");
+        let text = s.key(&Key::Delete, text, NO_MODS);
+        assert_eq!(text, "x");
+    }
+
+    #[test]
+    fn test_selection_multibyte() {
+        let clipboard = MemoryClipboard::new();
+        let text = "日本語テキスト".to_string();
+        let mut s = make_state(0, text.chars().count());
+
+        let text = s.key(&Key::ArrowRight, text, SHIFT);
+        let text = s.key(&Key::ArrowRight, text, SHIFT);
+        assert_eq!(s.selection_range(), Some((0, 6)));
+
+        let cut = s
+            .clipboard_key(&Key::Character('x'), &text, CMD, &clipboard)
+            .unwrap();
+        assert_eq!(cut, "語テキスト");
+        assert_eq!(clipboard.get_text(), Some("日本".to_string()));
+
+        let text = s.key(&Key::Character('z'), cut, CMD);
+        assert_eq!(text, "日本語テキスト");
+    }
+
+    #[test]
+    fn test_clamps_cursor_after_external_change() {
+        let mut s = TextEditorState::new();
+        s.cursor = 10;
+        s.selection_start = Some(2);
+        // The text was replaced with something shorter. Byte 2 is inside "é".
+        let text = s.key(&Key::ArrowLeft, "hé".to_string(), NO_MODS);
+        assert_eq!(text, "hé");
+        assert_eq!(s.cursor, 1);
+        assert_eq!(s.selection_start, None);
+    }
+
+    #[test]
+    fn test_click_in_grapheme_cluster() {
+        // Glyphs: e, combining acute, x.
+        let text = "e\u{301}x";
+        let mut s = make_state(0, 3);
+        s.click([14.0, 10.0].into(), text, false, 1);
+        assert_eq!(s.cursor, 0);
+        s.click([21.0, 10.0].into(), text, false, 1);
+        assert_eq!(s.cursor, 3);
+    }
+
+    #[test]
+    fn test_preedit_display() {
+        let mut s = make_state(1, 2);
+        s.set_preedit("にほ", Some((3, 3)));
+        assert_eq!(s.display_text("ab"), "aにほb");
+
+        // Composition doesn't change the text itself.
+        s.set_preedit("", None);
+        assert_eq!(s.display_text("ab"), "ab");
+    }
+
+    #[test]
+    fn test_ime_commit() {
+        let mut s = make_state(1, 2);
+        s.set_preedit("にほん", None);
+        let text = s.commit("日本", "ab".to_string());
+        assert_eq!(text, "a日本b");
+        assert_eq!(s.cursor, 1 + "日本".len());
+        assert!(s.preedit.is_empty());
+
+        let text = s.key(&Key::Character('z'), text, CMD);
+        assert_eq!(text, "ab");
+        assert_eq!(s.cursor, 1);
+    }
+
+    #[test]
+    fn test_ime_commit_replaces_selection() {
+        let mut s = make_state(0, 5);
+        s.selection_start = Some(1);
+        s.cursor = 4;
+        let text = s.commit("é", "abcde".to_string());
+        assert_eq!(text, "aée");
+        assert_eq!(s.cursor, 3);
+        assert_eq!(s.selection_start, None);
+    }
+
+    #[test]
+    fn test_ime_events_in_harness() {
+        let ui = state(|| "ab".to_string(), |s, _| text_editor(s));
+        let mut h = TestHarness::new(&ui, [200.0, 100.0]);
+        let text = h.root_state::<String>();
+
+        // Events are ignored until the editor has focus.
+        h.event(&Event::ImeCommit("x".to_string()));
+        assert_eq!(h.get(text), "ab");
+
+        h.tap([100.0, 50.0]);
+        h.key(Key::End);
+        h.event(&Event::ImePreedit {
+            text: "ka".to_string(),
+            cursor: Some((2, 2)),
+        });
+        assert_eq!(h.get(text), "ab");
+
+        h.event(&Event::ImeCommit("か".to_string()));
+        assert_eq!(h.get(text), "abか");
+    }
+
+    #[test]
+    fn test_tab_moves_focus() {
+        let ui = state(
+            || "ab".to_string(),
+            |s, _| hstack((text_editor(s), text_editor(s))),
+        );
+        let mut h = TestHarness::new(&ui, [200.0, 100.0]);
+        let text = h.root_state::<String>();
+
+        h.key(Key::Tab);
+        let first = h.cx.focused();
+        assert!(first.is_some());
+        h.key(Key::Tab);
+        assert_ne!(h.cx.focused(), first);
+        h.set_key_mods(SHIFT);
+        h.key(Key::Tab);
+        assert_eq!(h.cx.focused(), first);
+        assert_eq!(h.get(text), "ab");
+    }
+
+    #[test]
+    fn test_tab_typed_when_asked() {
+        let ui = state(
+            || "ab".to_string(),
+            |s, _| hstack((text_editor(s), text_editor(s))).env(TabKey::TypesTab),
+        );
+        let mut h = TestHarness::new(&ui, [200.0, 100.0]);
+        let text = h.root_state::<String>();
+
+        h.key(Key::Tab);
+        let first = h.cx.focused();
+        h.key(Key::End);
+        h.key(Key::Tab);
+        assert_eq!(h.get(text), "ab\t");
+        assert_eq!(h.cx.focused(), first);
+
+        // Shift+Tab still leaves the editor.
+        h.set_key_mods(SHIFT);
+        h.key(Key::Tab);
+        assert_eq!(h.get(text), "ab\t");
+        assert_ne!(h.cx.focused(), first);
     }
 }
 
+/// What Tab does in a focused `text_editor`. Set with `env` to change
+/// it for part of the UI.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TabKey {
+    /// Tab and Shift+Tab move the focus, as in a form.
+    #[default]
+    MovesFocus,
+    /// Tab types a tab character. Shift+Tab still moves the focus back.
+    TypesTab,
+}
+
 /// A multi-line text editor.
 ///
 /// This shows how a complex View with internal
@@ -550,60 +1686,241 @@ pub fn text_editor(text: impl Binding<String>) -> impl View {
         state(TextEditorState::new, move |state, _| {
             canvas(move |cx, rect, vger| {
                 vger.translate([0.0, rect.height()]);
+                cx[state].text_origin = [0.0, rect.height()].into();
                 let font_size = 18;
                 let break_width = Some(rect.width());
 
-                vger.text(text.get(cx), font_size, TEXT_COLOR, break_width);
+                let t = text.get(cx).clone();
+                cx[state].clamp(&t);
+                let display = cx[state].display_text(&t);
+
+                let font = cx.font();
+                let color = cx.apply_opacity(TEXT_COLOR);
+                let mut args = DrawArgs { cx, vger };
+                args.font_text(font, &display, font_size, color, break_width);
 
                 if has_focus {
-                    let rects = vger.glyph_positions(text.get(cx), font_size, break_width);
-                    let lines = vger.line_metrics(text.get(cx), font_size, break_width);
+                    let rects = args.font_glyph_positions(font, &display, font_size, break_width);
+                    let lines = args.font_line_metrics(font, &display, font_size, break_width);
+                    let DrawArgs { cx, vger } = args;
 
                     cx[state].glyph_rects = rects;
                     cx[state].lines = lines;
 
                     // Render selection background
                     if let Some((start, end)) = cx[state].selection_range() {
-                        if start != end {
-                            let selection_paint = vger.color_paint(vger::Color::new(0.3, 0.6, 1.0, 0.3));
-                            
+                        if start != end && cx[state].preedit.is_empty() {
+                            let start = glyph_index(&t, start);
+                            let end = glyph_index(&t, end);
+                            let selection_paint = vger.color_paint(
+                                cx.apply_opacity(vger::Color::new(0.3, 0.6, 1.0, 0.3)),
+                            );
+
                             for i in start..end.min(cx[state].glyph_rects.len()) {
                                 let rect = cx[state].glyph_rects[i];
                                 vger.fill_rect(
-                                    LocalRect::new(rect.origin, [rect.size.width.max(2.0), 20.0].into()),
+                                    LocalRect::new(
+                                        rect.origin,
+                                        [rect.size.width.max(2.0), 20.0].into(),
+                                    ),
                                     0.0,
-                                    selection_paint
+                                    selection_paint,
                                 );
                             }
-                            
+
                             // Handle selection at end of text
-                            if end >= cx[state].glyph_rects.len() && !cx[state].glyph_rects.is_empty() {
+                            if end >= cx[state].glyph_rects.len()
+                                && !cx[state].glyph_rects.is_empty()
+                            {
                                 if let Some(last_rect) = cx[state].glyph_rects.last() {
-                                    let end_pos = [last_rect.origin.x + last_rect.size.width, last_rect.origin.y];
+                                    let end_pos = [
+                                        last_rect.origin.x + last_rect.size.width,
+                                        last_rect.origin.y,
+                                    ];
                                     vger.fill_rect(
                                         LocalRect::new(end_pos.into(), [2.0, 20.0].into()),
                                         0.0,
-                                        selection_paint
+                                        selection_paint,
                                     );
                                 }
                             }
                         }
                     }
 
+                    // Underline the IME composition.
+                    let cursor = cx[state].cursor;
+                    let preedit_len = cx[state].preedit.len();
+                    if preedit_len > 0 {
+                        let underline_paint = vger.color_paint(cx.apply_opacity(TEXT_COLOR));
+                        let start = glyph_index(&display, cursor);
+                        let end = glyph_index(&display, cursor + preedit_len);
+                        for i in start..end.min(cx[state].glyph_rects.len()) {
+                            let rect = cx[state].glyph_rects[i];
+                            let line = cx[state].lines[cx[state].find_line(i)];
+                            vger.fill_rect(
+                                LocalRect::new(
+                                    [rect.min_x(), line.bounds.min_y() - 2.0].into(),
+                                    [rect.width(), 1.0].into(),
+                                ),
+                                0.0,
+                                underline_paint,
+                            );
+                        }
+                    }
+
                     // Render cursor
-                    let cursor_paint = vger.color_paint(vger::Color::MAGENTA);
-                    let p = cx[state].cursor_pos();
-                    vger.fill_rect(LocalRect::new(p, [2.0, 20.0].into()), 0.0, cursor_paint);
+                    let caret = match cx[state].preedit_cursor {
+                        _ if preedit_len == 0 => Some(cursor),
+                        Some((pos, _)) => Some(cursor + pos),
+                        None => None,
+                    };
+                    let p = cx[state].glyph_pos(&display, caret.unwrap_or(cursor));
+                    let cursor_rect = LocalRect::new(p, [2.0, 20.0].into());
+                    if caret.is_some() {
+                        let cursor_paint = vger.color_paint(cx.apply_opacity(vger::Color::MAGENTA));
+                        vger.fill_rect(cursor_rect, 0.0, cursor_paint);
+                    }
+
+                    cx.ime_cursor_area = Some(
+                        vger.current_transform()
+                            .outer_transformed_rect(&cursor_rect),
+                    );
+                }
+            })
+            .touch(move |cx, info| {
+                if info.state == TouchState::Begin {
+                    let shift_pressed = cx.key_mods.shift;
+                    let t = text.with(cx, |t| t.clone());
+                    cx[state].click(info.pt, &t, shift_pressed, info.count);
+                }
+            })
+            .ime(move |cx, event| {
+                if has_focus {
+                    match event {
+                        Event::ImePreedit {
+                            text: preedit,
+                            cursor,
+                        } => {
+                            cx[state].set_preedit(preedit, *cursor);
+                        }
+                        Event::ImeCommit(committed) => {
+                            let t = text.with(cx, |t| t.clone());
+                            let new_t = cx[state].commit(committed, t);
+                            text.with_mut(cx, |t| *t = new_t);
+                        }
+                        _ => (),
+                    }
                 }
             })
             .key(move |cx, k| {
                 if has_focus {
+                    if k == Key::Tab {
+                        let tab_key = cx.init_env(&TabKey::default);
+                        if tab_key == TabKey::MovesFocus || cx.key_mods.shift {
+                            return;
+                        }
+                        cx.key_handled = true;
+                    }
                     let t = text.with(cx, |t| t.clone());
-                    let shift_pressed = cx.key_mods.shift;
-                    let new_t = cx[state].key(&k, t, shift_pressed);
+                    let mods = cx.key_mods;
+                    let clipboard = cx.clipboard();
+                    let new_t = match cx[state].clipboard_key(&k, &t, mods, &*clipboard) {
+                        Some(new_t) => new_t,
+                        None => cx[state].key(&k, t, mods),
+                    };
                     text.with_mut(cx, |t| *t = new_t);
                 }
             })
         })
     })
 }
+
+/// Read-only text which can be selected and copied. Used by
+/// `Text::selectable`.
+pub(crate) fn selectable(view: Text, text: String) -> impl View {
+    let truncated = view.truncates();
+    focus(move |has_focus| {
+        let view = view.clone();
+        let text = text.clone();
+        state(TextEditorState::new, move |state, _| {
+            let t = text.clone();
+            let layout = view.clone();
+            let highlight = canvas(move |cx, _, vger| {
+                let mut args = DrawArgs { cx, vger };
+                let (rects, lines) = layout.glyph_layout(&mut args).unwrap_or_default();
+                let DrawArgs { cx, vger } = args;
+                cx[state].glyph_rects = rects;
+                cx[state].lines = lines;
+
+                let s = &cx[state];
+                match s.selection_range() {
+                    Some((start, end)) if has_focus && start != end => {
+                        let color = cx.apply_opacity(vger::Color::new(0.3, 0.6, 1.0, 0.3));
+                        let paint = vger.color_paint(color);
+                        let start = glyph_index(&t, start);
+                        let end = glyph_index(&t, end).min(s.glyph_rects.len());
+                        for i in start..end {
+                            let rect = s.glyph_rects[i];
+                            let line = s.lines[s.find_line(i)];
+                            // Reach the next glyph on the line, to cover spaces.
+                            let max_x = match s.glyph_rects.get(i + 1) {
+                                Some(next) if i + 1 < line.glyph_end => next.min_x(),
+                                _ => rect.max_x(),
+                            };
+                            vger.fill_rect(
+                                LocalRect::new(
+                                    [rect.min_x(), line.bounds.min_y()].into(),
+                                    [max_x - rect.min_x(), line.bounds.height()].into(),
+                                ),
+                                0.0,
+                                paint,
+                            );
+                        }
+                    }
+                    _ => (),
+                }
+            });
+
+            let t = text.clone();
+            let t2 = text.clone();
+            let selection = view.clone().background(highlight);
+            crate::state(TapCount::default, move |taps, _| {
+                let t = t.clone();
+                selection
+                    .clone()
+                    .drag_p(move |cx, pt, gesture, _| match gesture {
+                        _ if truncated => (),
+                        GestureState::Began => {
+                            let thresholds = cx.init_env(&GestureThresholds::default);
+                            let now = cx.now();
+                            let count = cx[taps].begin(pt, now, &thresholds);
+                            let extend = cx.key_mods.shift;
+                            cx[state].click(pt, &t, extend, count)
+                        }
+                        GestureState::Changed => cx[state].drag_to(pt, &t),
+                        GestureState::Ended => {
+                            let now = cx.now();
+                            cx[taps].end(Some((now, pt)))
+                        }
+                        _ => cx[taps].end(None),
+                    })
+            })
+            .key(move |cx, k| {
+                if truncated || !has_focus || !cx.key_mods.shortcut() {
+                    return;
+                }
+                match k {
+                    Key::Character('c') | Key::Character('C') => {
+                        if let Some(selected) = cx[state].selected_text(&t2) {
+                            cx.clipboard().set_text(selected);
+                        }
+                    }
+                    Key::Character('a') | Key::Character('A') => {
+                        cx[state].select(0, t2.len());
+                    }
+                    _ => (),
+                }
+            })
+        })
+    })
+}
@@ -110,6 +110,17 @@ pub fn toggle_with_config(on: impl Binding<bool>, config: ToggleConfig) -> impl
                     cx[animation] = target;
                 }
             })
+            .role(accesskit::Role::Switch)
+            .access_action(accesskit::Action::Default, move |cx, _| {
+                on.with_mut(cx, |b| *b = !*b);
+            })
+            .access_node(move |_, node| {
+                node.set_toggled(if is_on {
+                    accesskit::Toggled::True
+                } else {
+                    accesskit::Toggled::False
+                })
+            })
         },
     )
 }
@@ -1,10 +1,35 @@
 use crate::*;
 use std::any::Any;
+use std::time::Duration;
+use web_time::Instant;
+
+/// How quick and still touches must be to count as taps, multiple taps
+/// or long presses. Set with `env` to change them for part of the UI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureThresholds {
+    /// Longest wait between the taps of a double (or triple...) tap.
+    pub multi_tap_interval: Duration,
+
+    /// How far a touch can move and still be a tap or long press, and
+    /// how close together the taps of a multiple tap must be.
+    pub tap_distance: f32,
+}
+
+impl Default for GestureThresholds {
+    fn default() -> Self {
+        Self {
+            multi_tap_interval: Duration::from_millis(400),
+            tap_distance: 10.0,
+        }
+    }
+}
 
 #[derive(Clone, PartialEq)]
 pub enum TouchState {
     Begin,
     End,
+    /// The touch was cancelled rather than lifted.
+    Cancel,
 }
 
 #[derive(Clone)]
@@ -15,8 +40,42 @@ pub struct TouchInfo {
     /// The mouse button that was used for the touch if a mouse was used.
     pub button: Option<MouseButton>,
 
-    /// The state of the touch. IE: Begin or End.
+    /// The state of the touch. IE: Begin, End or Cancel.
     pub state: TouchState,
+
+    /// 1 for a single tap, 2 for the second tap of a double tap, and so on.
+    pub count: usize,
+}
+
+/// Taps so far in a multiple tap, and where and when the last one ended.
+#[derive(Clone, Default)]
+pub(crate) struct TapCount {
+    count: usize,
+    last: Option<(Instant, LocalPoint)>,
+}
+
+impl TapCount {
+    /// Counts a touch beginning at `position`, returning 1 for a single
+    /// tap, 2 for the second tap of a double tap, and so on.
+    pub(crate) fn begin(
+        &mut self,
+        position: LocalPoint,
+        now: Instant,
+        thresholds: &GestureThresholds,
+    ) -> usize {
+        let again = self.last.is_some_and(|(time, pt)| {
+            now.duration_since(time) <= thresholds.multi_tap_interval
+                && (position - pt).length() <= thresholds.tap_distance
+        });
+        self.count = if again { self.count + 1 } else { 1 };
+        self.count
+    }
+
+    /// Remembers where and when a touch ended, or forgets a cancelled one
+    /// so the next touch starts a new count.
+    pub(crate) fn end(&mut self, last: Option<(Instant, LocalPoint)>) {
+        self.last = last;
+    }
 }
 
 pub trait TouchFn: Clone {
@@ -87,6 +146,12 @@ where
     pub fn new(v: V, f: F) -> Self {
         Self { child: v, func: f }
     }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<TapCount> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &TapCount::default);
+        StateHandle::new(id)
+    }
 }
 
 impl<V, F> DynView for Touch<V, F>
@@ -102,30 +167,45 @@ where
         actions: &mut Vec<Box<dyn Any>>,
     ) {
         let vid = cx.view_id(path);
+        let s = self.state(path, cx);
         match &event {
             Event::TouchBegin { id, position } => {
                 if self.hittest(path, *position, cx).is_some() {
                     cx.touches[*id] = vid;
+                    let thresholds = cx.init_env(&GestureThresholds::default);
+                    let now = cx.now();
+                    let count = cx[s].begin(*position, now, &thresholds);
                     self.func.call(
                         cx,
                         TouchInfo {
                             pt: *position,
                             button: cx.mouse_button,
                             state: TouchState::Begin,
+                            count,
                         },
                         actions,
                     )
                 }
             }
-            Event::TouchEnd { id, position } => {
+            Event::TouchEnd { id, position } | Event::TouchCancel { id, position } => {
                 if cx.touches[*id] == vid {
                     cx.touches[*id] = ViewId::default();
+                    let state = match event {
+                        Event::TouchEnd { .. } => TouchState::End,
+                        _ => TouchState::Cancel,
+                    };
+                    let last = match state {
+                        TouchState::End => Some((cx.now(), *position)),
+                        _ => None,
+                    };
+                    cx[s].end(last);
                     self.func.call(
                         cx,
                         TouchInfo {
                             pt: *position,
                             button: cx.mouse_button,
-                            state: TouchState::End,
+                            state,
+                            count: cx[s].count,
                         },
                         actions,
                     )
@@ -162,6 +242,7 @@ where
     }
 
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
         path.push(0);
         self.child.gc(path, cx, map);
         path.pop();
@@ -171,7 +252,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = self.child.access(path, cx, nodes);
@@ -181,3 +262,29 @@ where
 }
 
 impl<V, F> private::Sealed for Touch<V, F> where V: View {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tap_count() {
+        let ui = state(Vec::new, |s, _| {
+            rectangle().touch(move |cx, info| {
+                if info.state == TouchState::End {
+                    cx[s].push(info.count)
+                }
+            })
+        });
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = h.root_state::<Vec<usize>>();
+
+        h.tap([50.0, 50.0]);
+        h.tap([51.0, 50.0]);
+        h.tap([50.0, 52.0]);
+        h.advance_time(Duration::from_secs(1));
+        h.tap([50.0, 50.0]);
+        h.tap([90.0, 90.0]);
+        assert_eq!(*h.get(s), vec![1, 2, 3, 1, 1]);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for the `transform`, `rotation` and `scale` modifiers.
+#[derive(Clone)]
+pub struct TransformView<V> {
+    child: V,
+    transform: LocalToLocal,
+    /// Apply the transform about the view's center rather than its origin?
+    centered: bool,
+}
+
+impl<V> TransformView<V>
+where
+    V: View,
+{
+    pub fn new(child: V, transform: LocalToLocal, centered: bool) -> Self {
+        Self {
+            child,
+            transform,
+            centered,
+        }
+    }
+
+    /// The transform from the child's space to ours.
+    fn transform(&self, path: &IdPath, cx: &Context) -> LocalToLocal {
+        if self.centered {
+            let c = cx.get_layout(path).rect.center().to_vector();
+            LocalToLocal::translation(-c.x, -c.y)
+                .then(&self.transform)
+                .then_translate(c)
+        } else {
+            self.transform
+        }
+    }
+}
+
+/// Splits a transform into a translation, rotation, non-uniform scale and
+/// another rotation, applied in that order, since that's all vger can do.
+fn decompose(xf: &LocalToLocal) -> (LocalOffset, f32, LocalOffset, f32) {
+    // Singular value decomposition of the linear part.
+    let e = (xf.m11 + xf.m22) / 2.0;
+    let f = (xf.m11 - xf.m22) / 2.0;
+    let g = (xf.m12 + xf.m21) / 2.0;
+    let h = (xf.m12 - xf.m21) / 2.0;
+    let q = (e * e + h * h).sqrt();
+    let r = (f * f + g * g).sqrt();
+    let a1 = g.atan2(f);
+    let a2 = h.atan2(e);
+
+    (
+        [xf.m31, xf.m32].into(),
+        (a2 + a1) / 2.0,
+        [q + r, q - r].into(),
+        (a2 - a1) / 2.0,
+    )
+}
+
+/// Makes vger draw through `xf`, on top of its current transform.
+pub(crate) fn apply_transform(vger: &mut Vger, xf: &LocalToLocal) {
+    let (translation, phi, scale, theta) = decompose(xf);
+    vger.translate(translation);
+    vger.rotate(phi);
+    vger.scale(scale);
+    vger.rotate(theta);
+}
+
+impl<V> DynView for TransformView<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let event = match self.transform(path, cx).inverse() {
+            Some(inverse) => event.transform(&inverse),
+            None => event.clone(),
+        };
+        path.push(0);
+        self.child.process(&event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        args.vger.save();
+        apply_transform(args.vger, &self.transform(path, args.cx));
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        let offset = args.cx.get_layout(path).offset;
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::origin(), sz),
+                offset,
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let xf = self.transform(path, cx).then(&xform);
+        path.push(0);
+        self.child.dirty(path, xf, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let inverse = self.transform(path, cx).inverse()?;
+        path.push(0);
+        let hit_id = self.child.hittest(path, inverse.transform_point(pt), cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for TransformView<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: LocalToLocal, b: LocalToLocal) {
+        for (x, y) in a.to_array().iter().zip(b.to_array().iter()) {
+            assert!((x - y).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_decompose() {
+        let transforms = [
+            LocalToLocal::rotation(euclid::Angle::radians(0.7)).then_translate([3.0, 4.0].into()),
+            LocalToLocal::scale(2.0, -0.5).then_rotate(euclid::Angle::radians(-1.2)),
+            LocalToLocal::new(1.0, 0.0, 0.5, 1.0, 10.0, 0.0), // shear
+        ];
+        for xf in transforms {
+            let (t, phi, s, theta) = decompose(&xf);
+            let recomposed = LocalToLocal::identity()
+                .pre_translate(t)
+                .pre_rotate(euclid::Angle::radians(phi))
+                .pre_scale(s.x, s.y)
+                .pre_rotate(euclid::Angle::radians(theta));
+            assert_close(recomposed, xf);
+        }
+    }
+
+    #[test]
+    fn test_hittest_scaled() {
+        let ui = rectangle().size([20.0, 20.0]).scale(2.0);
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+
+        // The rectangle now covers -10..30 around its center.
+        assert!(h.hittest([-5.0, -5.0]).is_some());
+        assert!(h.hittest([25.0, 25.0]).is_some());
+        assert!(h.hittest([35.0, 10.0]).is_none());
+    }
+
+    #[test]
+    fn test_rotated_tap() {
+        let ui = state(
+            || None,
+            |hit, _| {
+                rectangle()
+                    .size([40.0, 10.0])
+                    .touch(move |cx, info| cx[hit] = Some(info.pt))
+                    .rotation(std::f32::consts::FRAC_PI_2)
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let hit = h.root_state::<Option<LocalPoint>>();
+
+        // Standing upright, the bar covers x in 15..25 and y in -15..25.
+        assert!(h.hittest([10.0, 5.0]).is_none());
+        h.tap([20.0, 20.0]);
+        let p = h.get(hit).unwrap();
+        assert!(
+            (p.x - 35.0).abs() < 1e-3 && (p.y - 5.0).abs() < 1e-3,
+            "{:?}",
+            p
+        );
+    }
+
+    #[test]
+    fn test_dirty_uses_transformed_bounds() {
+        let ui = state(|| 0, |_, _| rectangle().size([10.0, 10.0]))
+            .transform(LocalToLocal::scale(3.0, 3.0));
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        let s = StateHandle::<i32>::new(h.view_id(&[0, 0]));
+        h.cx[s] = 1;
+
+        let mut path = vec![0];
+        ui.dirty(&mut path, LocalToWorld::identity(), &mut h.cx);
+        let rects: Vec<WorldRect> = h.cx.dirty_region.rects().to_vec();
+        assert_eq!(
+            rects,
+            vec![WorldRect::new([0.0, 0.0].into(), [30.0, 30.0].into())]
+        );
+    }
+}
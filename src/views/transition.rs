@@ -0,0 +1,89 @@
+use crate::*;
+
+/// How a view enters and leaves when it's inserted into or removed
+/// from a `cond` or `list`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transition {
+    /// Fades in and out.
+    Fade,
+
+    /// Slides in from, and out to, an offset from the view's position.
+    Slide(LocalOffset),
+
+    /// Grows from, and shrinks to, the view's center.
+    Scale,
+}
+
+impl Transition {
+    /// Draws a view part of the way through the transition. A `progress`
+    /// of 0 is fully gone and 1 is fully present.
+    pub(crate) fn draw(
+        &self,
+        progress: f32,
+        size: LocalSize,
+        args: &mut DrawArgs,
+        f: impl FnOnce(&mut DrawArgs),
+    ) {
+        let opacity = args.cx.opacity;
+        args.vger.save();
+
+        match *self {
+            Transition::Fade => args.cx.opacity = opacity * progress.clamp(0.0, 1.0),
+            Transition::Slide(offset) => args.vger.translate(offset * (1.0 - progress)),
+            Transition::Scale => {
+                let center = LocalRect::new(LocalPoint::origin(), size)
+                    .center()
+                    .to_vector();
+                let s = progress.max(0.0);
+                args.vger.translate(center);
+                args.vger.scale([s, s]);
+                args.vger.translate(-center);
+            }
+        }
+
+        f(args);
+
+        args.vger.restore();
+        args.cx.opacity = opacity;
+    }
+}
+
+/// Tracks a view which is transitioning in or out.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TransitionProgress {
+    pub progress: Tween<f32>,
+    pub size: LocalSize,
+}
+
+impl TransitionProgress {
+    pub fn present(size: LocalSize) -> Self {
+        Self {
+            progress: Tween::new(1.0),
+            size,
+        }
+    }
+
+    pub fn appear(size: LocalSize, curve: Curve) -> Self {
+        let mut t = Self {
+            progress: Tween::new(0.0),
+            size,
+        };
+        t.progress
+            .animate_to(1.0, curve, DEFAULT_ANIMATION_DURATION);
+        t
+    }
+
+    pub fn disappear(&mut self, curve: Curve) {
+        self.progress
+            .animate_to(0.0, curve, DEFAULT_ANIMATION_DURATION);
+    }
+
+    pub fn reappear(&mut self, curve: Curve) {
+        self.progress
+            .animate_to(1.0, curve, DEFAULT_ANIMATION_DURATION);
+    }
+
+    pub fn is_gone(&self) -> bool {
+        !self.progress.is_running() && self.progress.to == 0.0
+    }
+}
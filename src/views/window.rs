@@ -82,7 +82,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = self.child.access(path, cx, nodes);
@@ -167,7 +167,7 @@ where
         &self,
         path: &mut IdPath,
         cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::NodeBuilder)>,
     ) -> Option<accesskit::NodeId> {
         path.push(0);
         let node_id = self.child.access(path, cx, nodes);
@@ -177,3 +177,206 @@ where
 }
 
 impl<V> private::Sealed for FullscreenView<V> {}
+
+/// A window declared with `window`. The backend opens one for each
+/// and routes its events to `view`.
+///
+/// Without a backend there's nothing to read these, bar the tests.
+#[derive(Clone)]
+#[cfg_attr(not(any(feature = "winit", test)), allow(dead_code))]
+pub(crate) struct WindowInfo {
+    pub title: Arc<str>,
+    pub size: LocalSize,
+    /// Where the content lives in the view tree, so it shares state
+    /// with the main window.
+    pub path: IdPath,
+    pub view: AnyView,
+    pub on_close: Option<CloseFn>,
+}
+
+type CloseFn = Arc<dyn Fn(&mut Context)>;
+
+/// Struct for `window`.
+#[derive(Clone)]
+pub struct WindowView {
+    title: Arc<str>,
+    size: LocalSize,
+    content: AnyView,
+    on_close: Option<CloseFn>,
+}
+
+impl WindowView {
+    /// Calls a function when the user closes the window. It stays closed
+    /// until it's removed from the view tree and declared again, so this
+    /// is the place to update whatever state declares it.
+    pub fn on_close(self, f: impl Fn(&mut Context) + 'static) -> Self {
+        Self {
+            on_close: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+
+impl DynView for WindowView {
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        // Input is routed to the content by the window it happens in,
+        // but animations and commands aren't tied to a window.
+        if let Event::Anim | Event::Command(_) = event {
+            path.push(0);
+            self.content.process(event, path, cx, actions);
+            path.pop();
+        }
+    }
+
+    fn draw(&self, _path: &mut IdPath, _args: &mut DrawArgs) {
+        // Drawn in its own window.
+    }
+
+    fn layout(&self, _path: &mut IdPath, _args: &mut LayoutArgs) -> LocalSize {
+        LocalSize::zero()
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.content.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        let id = cx.view_id(path);
+        map.push(id);
+
+        path.push(0);
+        cx.windows.insert(
+            id,
+            WindowInfo {
+                title: self.title.clone(),
+                size: self.size,
+                path: path.clone(),
+                view: self.content.clone(),
+                on_close: self.on_close.clone(),
+            },
+        );
+        self.content.gc(path, cx, map);
+        path.pop();
+    }
+}
+
+impl private::Sealed for WindowView {}
+
+/// Opens another window showing `view`. The window stays open while
+/// this view is part of the tree, and its content shares state with
+/// the rest of the app. Use `on_close` to find out when the user
+/// closes it.
+///
+/// Only the main window's views are given to accessibility tools for now.
+pub fn window(title: &str, size: impl Into<LocalSize>, view: impl View) -> WindowView {
+    WindowView {
+        title: title.into(),
+        size: size.into(),
+        content: AnyView::new(view),
+        on_close: None,
+    }
+}
+
+impl Context {
+    /// Lets the declared window `id` know the user closed it.
+    #[cfg(any(feature = "winit", test))]
+    pub(crate) fn window_closed(&mut self, id: ViewId) {
+        if let Some(f) = self.windows.get(&id).and_then(|w| w.on_close.clone()) {
+            f(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_declared_during_gc() {
+        let ui = state(
+            || 0,
+            |count, cx| {
+                let n = cx[count];
+                vstack((
+                    rectangle().size([10.0, 10.0]),
+                    window("palette", [200.0, 100.0], format!("{}", n)),
+                ))
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        h.gc();
+
+        assert_eq!(h.cx.windows.len(), 1);
+        let info = h.cx.windows.values().next().unwrap().clone();
+        assert_eq!(&*info.title, "palette");
+        assert_eq!(info.size, LocalSize::new(200.0, 100.0));
+
+        // It takes no space in the main window.
+        assert!(h.rect(&[0, 0, 1]).is_none_or(|r| r.is_empty()));
+    }
+
+    #[test]
+    fn test_window_content_shares_state() {
+        let ui = state(
+            || 0,
+            |count, _| {
+                window(
+                    "counter",
+                    [100.0, 100.0],
+                    rectangle().tap(move |cx| cx[count] += 1),
+                )
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        h.gc();
+        let info = h.cx.windows.values().next().unwrap().clone();
+
+        // Route a tap to the window the way the backend does.
+        let mut path = info.path.clone();
+        let mut args = LayoutArgs {
+            sz: info.size,
+            cx: &mut h.cx,
+            text_bounds: &mut |_, _, _| LocalRect::zero(),
+        };
+        info.view.layout(&mut path, &mut args);
+        let position = LocalPoint::new(50.0, 50.0);
+        h.cx.process_root(
+            &info.view,
+            &info.path,
+            &Event::TouchBegin { id: 0, position },
+        );
+        h.cx.process_root(&info.view, &info.path, &Event::TouchEnd { id: 0, position });
+
+        let count = h.root_state::<i32>();
+        assert_eq!(*h.get(count), 1);
+
+        // The state survives gc since the window is still declared.
+        h.gc();
+        assert_eq!(*h.get(count), 1);
+    }
+
+    #[test]
+    fn test_on_close() {
+        let ui = state(
+            || false,
+            |closed, _| {
+                window("palette", [100.0, 100.0], rectangle()).on_close(move |cx| cx[closed] = true)
+            },
+        );
+        let mut h = TestHarness::new(&ui, [100.0, 100.0]);
+        h.gc();
+        let closed = h.root_state::<bool>();
+
+        let id = *h.cx.windows.keys().next().unwrap();
+        h.cx.window_closed(id);
+        assert!(*h.get(closed));
+    }
+}
@@ -1,24 +1,33 @@
 use crate::*;
 
 use futures::executor::block_on;
-use std::{collections::HashMap, sync::Arc};
 #[cfg(not(target_arch = "wasm32"))]
 use std::{collections::VecDeque, sync::Mutex};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
-use euclid::Point2D;
+use euclid::Size2D;
+use web_time::Instant;
 #[cfg(not(target_arch = "wasm32"))]
 use winit::event_loop::EventLoopProxy;
 use winit::{
     application::ApplicationHandler,
+    dpi::{LogicalPosition, LogicalSize},
     event::{
-        DeviceEvent, DeviceId, ElementState as wElementState, KeyEvent as WKeyEvent,
-        MouseButton as WMouseButton, Touch, TouchPhase, WindowEvent,
+        DeviceEvent, DeviceId, ElementState as wElementState, Ime, KeyEvent as WKeyEvent,
+        MouseButton as WMouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent,
     },
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
     keyboard,
     window::{Window, WindowId},
 };
 
+/// How far one line of mouse wheel scrolling moves.
+const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
 #[cfg(not(target_arch = "wasm32"))]
 type WorkQueue = VecDeque<Box<dyn FnOnce(&mut Context) + Send>>;
 
@@ -43,6 +52,34 @@ pub fn on_main(f: impl FnOnce(&mut Context) + Send + 'static) {
     }
 }
 
+/// The operating system's clipboard.
+#[cfg(not(target_arch = "wasm32"))]
+struct SystemClipboard {
+    clipboard: Mutex<arboard::Clipboard>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clipboard for SystemClipboard {
+    fn get_text(&self) -> Option<String> {
+        self.clipboard.lock().unwrap().get_text().ok()
+    }
+
+    fn set_text(&self, text: &str) {
+        if let Err(err) = self.clipboard.lock().unwrap().set_text(text) {
+            log::error!("error setting clipboard text: {:?}", err);
+        }
+    }
+}
+
+/// The GPU, shared by every window.
+struct Gpu {
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+}
+
+/// A window's surface and what we draw it with.
 struct DrawContext {
     surface: wgpu::Surface<'static>,
     device: Arc<wgpu::Device>,
@@ -51,7 +88,41 @@ struct DrawContext {
     vger: Vger,
 }
 
-async fn setup(window: Arc<Window>) -> DrawContext {
+/// Finds an adapter which can draw to `surface` and opens the device.
+async fn setup_gpu(instance: wgpu::Instance, surface: &wgpu::Surface<'static>) -> Gpu {
+    let adapter = wgpu::util::initialize_adapter_from_env_or_default(&instance, Some(surface))
+        .await
+        .expect("No suitable GPU adapters found on the system!");
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let adapter_info = adapter.get_info();
+        log::debug!("Using {} ({:?})", adapter_info.name, adapter_info.backend);
+    }
+
+    let trace_dir = std::env::var("WGPU_TRACE");
+    let (device, queue) = adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+            },
+            trace_dir.ok().as_ref().map(std::path::Path::new),
+        )
+        .await
+        .expect("Unable to find a suitable GPU adapter!");
+
+    Gpu {
+        instance,
+        adapter,
+        device: Arc::new(device),
+        queue: Arc::new(queue),
+    }
+}
+
+/// Creates a surface for `window`, and the GPU too if this is the first.
+fn setup(window: Arc<Window>, gpu: &mut Option<Gpu>) -> DrawContext {
     #[cfg(target_arch = "wasm32")]
     {
         use winit::platform::web::WindowExtWebSys;
@@ -75,41 +146,24 @@ async fn setup(window: Arc<Window>) -> DrawContext {
 
     // log::info!("Initializing the surface...");
 
-    let instance_desc = wgpu::InstanceDescriptor::default();
-
-    let instance = wgpu::Instance::new(instance_desc);
     let size = window.inner_size();
-    let surface = instance
-        .create_surface(window)
-        .expect("Failed to create surface!");
-    let adapter = wgpu::util::initialize_adapter_from_env_or_default(&instance, Some(&surface))
-        .await
-        .expect("No suitable GPU adapters found on the system!");
-
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let adapter_info = adapter.get_info();
-        log::debug!("Using {} ({:?})", adapter_info.name, adapter_info.backend);
+    let surface = match gpu {
+        Some(gpu) => gpu.instance.create_surface(window),
+        None => {
+            let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+            let surface = instance.create_surface(window);
+            if let Ok(surface) = &surface {
+                *gpu = Some(block_on(setup_gpu(instance, surface)));
+            }
+            surface
+        }
     }
-
-    let trace_dir = std::env::var("WGPU_TRACE");
-    let (device, queue) = adapter
-        .request_device(
-            &wgpu::DeviceDescriptor {
-                label: None,
-                required_features: wgpu::Features::default(),
-                required_limits: wgpu::Limits::default(),
-            },
-            trace_dir.ok().as_ref().map(std::path::Path::new),
-        )
-        .await
-        .expect("Unable to find a suitable GPU adapter!");
-    let device = Arc::new(device);
-    let queue = Arc::new(queue);
+    .expect("Failed to create surface!");
+    let gpu = gpu.as_ref().unwrap();
 
     let config = wgpu::SurfaceConfiguration {
         usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-        format: surface.get_capabilities(&adapter).formats[0],
+        format: surface.get_capabilities(&gpu.adapter).formats[0],
         width: size.width,
         height: size.height,
         present_mode: wgpu::PresentMode::Fifo,
@@ -117,21 +171,27 @@ async fn setup(window: Arc<Window>) -> DrawContext {
         alpha_mode: wgpu::CompositeAlphaMode::Auto,
         view_formats: vec![],
     };
-    surface.configure(&device, &config);
+    surface.configure(&gpu.device, &config);
 
-    let vger = Vger::new(device.clone(), queue.clone(), config.format);
+    let vger = Vger::new(gpu.device.clone(), gpu.queue.clone(), config.format);
 
     DrawContext {
         surface,
-        device,
-        queue,
+        device: gpu.device.clone(),
+        queue: gpu.queue.clone(),
         config,
         vger,
     }
 }
 
-fn process_event(cx: &mut Context, view: &impl View, event: &Event, window: &Window) {
-    cx.process(view, event);
+fn process_event(
+    cx: &mut Context,
+    view: &impl View,
+    root: &IdPath,
+    event: &Event,
+    window: &Window,
+) {
+    cx.process_root(view, root, event);
 
     if cx.grab_cursor && !cx.prev_grab_cursor {
         log::debug!("grabbing cursor");
@@ -153,27 +213,225 @@ fn process_event(cx: &mut Context, view: &impl View, event: &Event, window: &Win
     cx.prev_grab_cursor = cx.grab_cursor;
 }
 
+fn gesture_state(phase: TouchPhase) -> GestureState {
+    match phase {
+        TouchPhase::Started => GestureState::Began,
+        TouchPhase::Moved => GestureState::Changed,
+        TouchPhase::Ended => GestureState::Ended,
+        TouchPhase::Cancelled => GestureState::Cancelled,
+    }
+}
+
+/// An open window and its GPU resources.
+struct WindowState {
+    window: Arc<Window>,
+    context: DrawContext,
+    title: Arc<str>,
+    // The event system does not expose the cursor position on-demand.
+    // We track all the mouse movement events to make this easier to access
+    // by event handlers.
+    mouse_position: LocalPoint,
+    // Cursor area last reported to the IME.
+    ime_cursor_area: Option<WorldRect>,
+}
+
+impl WindowState {
+    fn new(window: Window, title: Arc<str>, gpu: &mut Option<Gpu>) -> Self {
+        let window = Arc::new(window);
+        window.set_ime_allowed(true);
+
+        // Set up the rendering context.
+        let context = setup(window.clone(), gpu);
+
+        Self {
+            window,
+            context,
+            title,
+            mouse_position: LocalPoint::zero(),
+            ime_cursor_area: None,
+        }
+    }
+
+    /// Size of the window in logical pixels.
+    fn size(&self) -> Size2D<f32, WorldSpace> {
+        let window_size = self.window.inner_size();
+        let scale = self.window.scale_factor() as f32;
+        [
+            window_size.width as f32 / scale,
+            window_size.height as f32 / scale,
+        ]
+        .into()
+    }
+
+    /// Converts a position from winit into our flipped, logical coordinates.
+    fn local_position(&self, x: f64, y: f64) -> LocalPoint {
+        let scale = self.window.scale_factor() as f32;
+        [
+            x as f32 / scale,
+            (self.context.config.height as f32 - y as f32) / scale,
+        ]
+        .into()
+    }
+
+    fn resize(&mut self) {
+        let size = self.window.inner_size();
+        // log::debug!("Resizing to {:?}", size);
+        self.context.config.width = size.width.max(1);
+        self.context.config.height = size.height.max(1);
+        self.context
+            .surface
+            .configure(&self.context.device, &self.context.config);
+        self.window.request_redraw();
+    }
+
+    fn render(&mut self, cx: &mut Context, view: &impl View, root: &IdPath) {
+        let window_size = self.size();
+        let scale = self.window.scale_factor() as f32;
+        let context = &mut self.context;
+
+        // log::debug!("RedrawRequested");
+        cx.render_root(
+            RenderInfo {
+                device: &context.device,
+                surface: &context.surface,
+                config: &context.config,
+                queue: &context.queue,
+            },
+            view,
+            root,
+            &mut context.vger,
+            window_size,
+            scale,
+        );
+
+        // Keep the IME candidate window next to the text cursor.
+        let area = cx.ime_cursor_area();
+        if area != self.ime_cursor_area {
+            if let Some(rect) = area {
+                self.window.set_ime_cursor_area(
+                    LogicalPosition::new(rect.min_x(), window_size.height - rect.max_y()),
+                    LogicalSize::new(rect.width(), rect.height()),
+                );
+            }
+            self.ime_cursor_area = area;
+        }
+    }
+}
+
+/// Identifies a window: the main one, or one declared with `window`.
+type WindowTarget = Option<ViewId>;
+
 struct EventHandler<T>
 where
     T: View,
 {
     title: Arc<str>,
     running: bool,
-    // The GPU resources, if running.
-    context: Option<DrawContext>,
-    // The event handling loop is terminated when the main window is closed.
-    // We can trigger this by dropping the window, so we wrap it in the Option
-    // type.  This is a bit of a hack, but it works.
-    window: Option<Arc<Window>>,
-    // The event system does not expose the cursor position on-demand.
-    // We track all the mouse movement events to make this easier to access
-    // by event handlers.
-    mouse_position: Point2D<f32, LocalSpace>,
+    // The GPU, once the first window has been opened.
+    gpu: Option<Gpu>,
+    // The main window and its GPU resources, if running. The event
+    // handling loop is terminated when the main window is closed.
+    main: Option<WindowState>,
+    // Windows declared with `window`, keyed by the id of their view.
+    windows: HashMap<ViewId, WindowState>,
+    // Declared windows the user closed. They stay closed until they're
+    // removed from the view tree and declared again.
+    closed: HashSet<ViewId>,
+    // The window the mouse was last over.
+    pointer_window: WindowTarget,
     cx: Context,
     view: T,
     access_nodes: Vec<(accesskit::NodeId, accesskit::Node)>,
 }
 
+impl<T> EventHandler<T>
+where
+    T: View,
+{
+    fn target(&self, window_id: WindowId) -> Option<WindowTarget> {
+        if self.main.as_ref()?.window.id() == window_id {
+            return Some(None);
+        }
+        self.windows
+            .iter()
+            .find(|(_, w)| w.window.id() == window_id)
+            .map(|(id, _)| Some(*id))
+    }
+
+    fn window_state(&mut self, target: WindowTarget) -> Option<&mut WindowState> {
+        match target {
+            None => self.main.as_mut(),
+            Some(id) => self.windows.get_mut(&id),
+        }
+    }
+
+    /// Sends an event to the view shown in a window.
+    fn process(&mut self, target: WindowTarget, event: &Event) {
+        match target {
+            None => {
+                if let Some(main) = &self.main {
+                    process_event(&mut self.cx, &self.view, &vec![0], event, &main.window)
+                }
+            }
+            Some(id) => {
+                if let (Some(ws), Some(info)) = (self.windows.get(&id), self.cx.windows.get(&id)) {
+                    let info = info.clone();
+                    process_event(&mut self.cx, &info.view, &info.path, event, &ws.window)
+                }
+            }
+        }
+    }
+
+    fn render(&mut self, target: WindowTarget) {
+        match target {
+            None => {
+                if let Some(main) = &mut self.main {
+                    main.render(&mut self.cx, &self.view, &vec![0]);
+                }
+            }
+            Some(id) => {
+                if let (Some(ws), Some(info)) =
+                    (self.windows.get_mut(&id), self.cx.windows.get(&id))
+                {
+                    let info = info.clone();
+                    ws.render(&mut self.cx, &info.view, &info.path);
+                }
+            }
+        }
+    }
+
+    /// Opens and closes windows to match those declared in the view tree.
+    fn sync_windows(&mut self, event_loop: &ActiveEventLoop) {
+        let declared = &self.cx.windows;
+        self.windows.retain(|id, _| declared.contains_key(id));
+        self.closed.retain(|id| declared.contains_key(id));
+
+        for (id, info) in declared {
+            match self.windows.get_mut(id) {
+                Some(ws) if ws.title != info.title => {
+                    ws.title = info.title.clone();
+                    ws.window.set_title(&info.title);
+                }
+                Some(_) => (),
+                None if !self.closed.contains(id) => {
+                    let window_attributes = Window::default_attributes()
+                        .with_title(info.title.to_string())
+                        .with_inner_size(LogicalSize::new(info.size.width, info.size.height));
+                    match event_loop.create_window(window_attributes) {
+                        Err(e) => log::error!("Error creating window: {:?}", e),
+                        Ok(window) => {
+                            let ws = WindowState::new(window, info.title.clone(), &mut self.gpu);
+                            ws.window.request_redraw();
+                            self.windows.insert(*id, ws);
+                        }
+                    }
+                }
+                None => (),
+            }
+        }
+    }
+}
+
 impl<T> ApplicationHandler for EventHandler<T>
 where
     T: View,
@@ -190,17 +448,16 @@ where
 
         // Create the main window.
         let window_attributes = Window::default_attributes().with_title(self.title.to_string());
-        self.window = match event_loop.create_window(window_attributes) {
+        self.main = match event_loop.create_window(window_attributes) {
             Err(e) => {
                 log::error!("Error creating window: {:?}", e);
                 return;
             }
-            Ok(window) => Some(Arc::new(window)),
+            Ok(window) => Some(WindowState::new(window, self.title.clone(), &mut self.gpu)),
         };
-        let window = self.window.as_ref().unwrap();
 
-        // Set up the rendering context.
-        self.context = Some(block_on(setup(window.clone())));
+        // Make sure the first update finds any other windows.
+        self.cx.set_dirty();
     }
 
     fn window_event(
@@ -209,28 +466,27 @@ where
         window_id: WindowId,
         event: WindowEvent,
     ) {
-        // ControlFlow::Poll continuously runs the event loop, even if the OS hasn't
-        // dispatched any events. This is ideal for games and similar applications.
-        // event_loop.set_control_flow(ControlFlow::Poll);
-
-        // ControlFlow::Wait pauses the event loop if no events are available to process.
-        // This is ideal for non-game applications that only update in response to user
-        // input, and uses significantly less power/CPU time than ControlFlow::Poll.
-        event_loop.set_control_flow(ControlFlow::Wait);
+        // Do not handle events from windows we've already closed.
+        let target = match self.target(window_id) {
+            Some(target) => target,
+            None => return,
+        };
 
         match event {
-            WindowEvent::CloseRequested => {
-                log::debug!("The close button was pressed; stopping");
-                event_loop.exit()
-            }
+            WindowEvent::CloseRequested => match target {
+                None => {
+                    log::debug!("The close button was pressed; stopping");
+                    event_loop.exit()
+                }
+                Some(id) => {
+                    self.windows.remove(&id);
+                    self.closed.insert(id);
+                    self.cx.window_closed(id);
+                }
+            },
             WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
-                if let (Some(window), Some(context)) = (&self.window, &mut self.context) {
-                    let size = window.inner_size();
-                    // log::debug!("Resizing to {:?}", size);
-                    context.config.width = size.width.max(1);
-                    context.config.height = size.height.max(1);
-                    context.surface.configure(&context.device, &context.config);
-                    window.request_redraw();
+                if let Some(ws) = self.window_state(target) {
+                    ws.resize();
                 }
             }
             WindowEvent::RedrawRequested => {
@@ -239,30 +495,14 @@ where
                 // It's preferable for applications that do not render continuously to render in
                 // this event rather than in MainEventsCleared, since rendering in here allows
                 // the program to gracefully handle redraws requested by the OS.
-
-                if let (Some(window), Some(context)) = (&self.window, &mut self.context) {
-                    let window_size = window.inner_size();
-                    let scale = window.scale_factor() as f32;
-                    // log::debug!("window_size: {:?}", window_size);
-                    let width = window_size.width as f32 / scale;
-                    let height = window_size.height as f32 / scale;
-
-                    // log::debug!("RedrawRequested");
-                    self.cx.render(
-                        RenderInfo {
-                            device: &context.device,
-                            surface: &context.surface,
-                            config: &context.config,
-                            queue: &context.queue,
-                        },
-                        &self.view,
-                        &mut context.vger,
-                        [width, height].into(),
-                        scale,
-                    );
-                }
+                self.render(target);
             }
             WindowEvent::MouseInput { state, button, .. } => {
+                let position = match self.window_state(target) {
+                    Some(ws) => ws.mouse_position,
+                    None => return,
+                };
+
                 match state {
                     wElementState::Pressed => {
                         self.cx.mouse_button = match button {
@@ -280,14 +520,7 @@ where
                         };
 
                         self.cx.set_dirty();
-
-                        if let Some(window) = &self.window {
-                            let event = Event::TouchBegin {
-                                id: 0,
-                                position: self.mouse_position,
-                            };
-                            process_event(&mut self.cx, &self.view, &event, window)
-                        }
+                        self.process(target, &Event::TouchBegin { id: 0, position });
                     }
                     wElementState::Released => {
                         self.cx.mouse_button = None;
@@ -300,68 +533,104 @@ where
                         };
 
                         self.cx.set_dirty();
-
-                        if let Some(window) = &self.window {
-                            let event = Event::TouchEnd {
-                                id: 0,
-                                position: self.mouse_position,
-                            };
-                            process_event(&mut self.cx, &self.view, &event, window)
-                        }
+                        self.process(target, &Event::TouchEnd { id: 0, position });
                     }
                 };
             }
             WindowEvent::Touch(Touch {
-                phase, location, ..
+                id: touch_id,
+                phase,
+                location,
+                ..
             }) => {
-                if let (Some(window), Some(context)) = (&self.window, &self.context) {
-                    // Do not handle events from other windows.
-                    if window_id != window.id() {
-                        return;
-                    }
+                let position = match self.window_state(target) {
+                    Some(ws) => ws.local_position(location.x, location.y),
+                    None => return,
+                };
 
-                    let scale = window.scale_factor() as f32;
-                    let position = [
-                        location.x as f32 / scale,
-                        (context.config.height as f32 - location.y as f32) / scale,
-                    ]
-                    .into();
-
-                    let delta = position - self.cx.previous_position[0];
-
-                    // TODO: Multi-Touch management
-                    let event = match phase {
-                        TouchPhase::Started => Some(Event::TouchBegin { id: 0, position }),
-                        TouchPhase::Moved => Some(Event::TouchMove {
-                            id: 0,
-                            position,
-                            delta,
-                        }),
-                        TouchPhase::Ended | TouchPhase::Cancelled => {
-                            Some(Event::TouchEnd { id: 0, position })
-                        }
-                    };
+                let slot = match phase {
+                    TouchPhase::Started => self.cx.begin_touch(touch_id),
+                    TouchPhase::Moved => self.cx.touch_slot(touch_id),
+                    TouchPhase::Ended | TouchPhase::Cancelled => self.cx.end_touch(touch_id),
+                };
+                let id = match slot {
+                    Some(id) => id,
+                    None => return,
+                };
 
-                    if let Some(event) = event {
-                        process_event(&mut self.cx, &self.view, &event, window);
-                    }
-                }
+                let delta = position - self.cx.previous_position[id];
+                self.cx.previous_position[id] = position;
+
+                let event = match phase {
+                    TouchPhase::Started => Event::TouchBegin { id, position },
+                    TouchPhase::Moved => Event::TouchMove {
+                        id,
+                        position,
+                        delta,
+                    },
+                    TouchPhase::Ended => Event::TouchEnd { id, position },
+                    TouchPhase::Cancelled => Event::TouchCancel { id, position },
+                };
+
+                self.process(target, &event);
             }
             WindowEvent::CursorMoved { position, .. } => {
-                if let (Some(window), Some(context)) = (&self.window, &self.context) {
-                    let scale = window.scale_factor() as f32;
-                    self.mouse_position = [
-                        position.x as f32 / scale,
-                        (context.config.height as f32 - position.y as f32) / scale,
-                    ]
-                    .into();
-
-                    // let event = Event::TouchMove {
-                    //     id: 0,
-                    //     position: self.mouse_position,
-                    // };
-                    // process_event(&mut self.cx, &self.view, &event, &window)
-                }
+                let position = match self.window_state(target) {
+                    Some(ws) => {
+                        ws.mouse_position = ws.local_position(position.x, position.y);
+                        ws.mouse_position
+                    }
+                    None => return,
+                };
+                self.pointer_window = target;
+                self.process(target, &Event::PointerMove { position });
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.process(target, &Event::MouseLeftWindow);
+            }
+
+            WindowEvent::PinchGesture { delta, phase, .. } => {
+                let position = match self.window_state(target) {
+                    Some(ws) => ws.mouse_position,
+                    None => return,
+                };
+                let event = Event::Magnify {
+                    delta: delta as f32,
+                    position,
+                    state: gesture_state(phase),
+                };
+                self.process(target, &event);
+            }
+            WindowEvent::RotationGesture { delta, phase, .. } => {
+                let position = match self.window_state(target) {
+                    Some(ws) => ws.mouse_position,
+                    None => return,
+                };
+                let event = Event::Rotate {
+                    delta: delta.to_radians(),
+                    position,
+                    state: gesture_state(phase),
+                };
+                self.process(target, &event);
+            }
+
+            WindowEvent::MouseWheel { delta, .. } => {
+                let (position, scale) = match self.window_state(target) {
+                    Some(ws) => (ws.mouse_position, ws.window.scale_factor()),
+                    None => return,
+                };
+
+                // Flip y coordinate.
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => {
+                        [x * SCROLL_LINE_HEIGHT, -y * SCROLL_LINE_HEIGHT].into()
+                    }
+                    MouseScrollDelta::PixelDelta(pos) => {
+                        [(pos.x / scale) as f32, -(pos.y / scale) as f32].into()
+                    }
+                };
+
+                self.process(target, &Event::Scroll { delta, position })
             }
 
             WindowEvent::KeyboardInput {
@@ -395,18 +664,32 @@ where
                     keyboard::Key::Named(keyboard::NamedKey::F10) => Some(Key::F10),
                     keyboard::Key::Named(keyboard::NamedKey::F11) => Some(Key::F11),
                     keyboard::Key::Named(keyboard::NamedKey::F12) => Some(Key::F12),
-                    keyboard::Key::Character(str) => {
-                        str.chars().next().map(Key::Character)
-                    }
+                    keyboard::Key::Character(str) => str.chars().next().map(Key::Character),
                     _ => None,
                 };
 
                 if let (Some(key), wElementState::Pressed) = (key, key_event.state) {
-                    self.cx.process(&self.view, &Event::Key(key))
+                    self.process(target, &Event::Key(key))
                 }
 
                 if let (Some(key), wElementState::Released) = (key, key_event.state) {
-                    self.cx.process(&self.view, &Event::KeyReleased(key))
+                    self.process(target, &Event::KeyReleased(key))
+                }
+            }
+
+            WindowEvent::Ime(ime) => {
+                let event = match ime {
+                    Ime::Preedit(text, cursor) => Some(Event::ImePreedit { text, cursor }),
+                    Ime::Commit(text) => Some(Event::ImeCommit(text)),
+                    Ime::Disabled => Some(Event::ImePreedit {
+                        text: String::new(),
+                        cursor: None,
+                    }),
+                    Ime::Enabled => None,
+                };
+
+                if let Some(event) = event {
+                    self.process(target, &event)
                 }
             }
 
@@ -445,19 +728,19 @@ where
             // Flip y coordinate.
             let d: LocalOffset = [delta.0 as f32, -delta.1 as f32].into();
 
-            let event = Event::TouchMove {
-                id: 0,
-                position: self.mouse_position,
-                delta: d,
-            };
-
-            if let Some(window) = &self.window {
-                process_event(&mut self.cx, &self.view, &event, window);
+            let target = self.pointer_window;
+            if let Some(ws) = self.window_state(target) {
+                let event = Event::TouchMove {
+                    id: 0,
+                    position: ws.mouse_position,
+                    delta: d,
+                };
+                self.process(target, &event);
             }
         }
     }
 
-    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         // Application update code.
 
         // Queue a RedrawRequested event.
@@ -466,27 +749,40 @@ where
         // redraw, in applications which do not always need to. Applications
         // that redraw continuously can just render here instead.
 
-        if let (Some(window), Some(context)) = (&self.window, &mut self.context) {
-            let window_size = window.inner_size();
-            let scale = window.scale_factor() as f32;
+        if let Some(main) = &mut self.main {
+            let window_size = main.size();
             // log::debug!("window_size: {:?}", window_size);
-            let width = window_size.width as f32 / scale;
-            let height = window_size.height as f32 / scale;
 
             if self.cx.update(
                 &self.view,
-                &mut context.vger,
+                &mut main.context.vger,
                 &mut self.access_nodes,
-                [width, height].into(),
+                window_size,
             ) {
-                window.request_redraw();
+                // State is shared, so any window may need redrawing.
+                main.window.request_redraw();
+                for ws in self.windows.values() {
+                    ws.window.request_redraw();
+                }
             }
 
             if self.cx.window_title != self.title {
                 self.title = self.cx.window_title.clone();
-                window.set_title(&self.cx.window_title);
+                main.window.set_title(&self.cx.window_title);
             }
         }
+
+        self.sync_windows(event_loop);
+
+        // ControlFlow::Wait pauses the event loop if no events are available to process.
+        // While something is animating, wake up again for the next frame instead.
+        if self.cx.is_animating() {
+            event_loop.set_control_flow(ControlFlow::WaitUntil(
+                Instant::now() + Duration::from_secs_f32(ANIMATION_FRAME),
+            ));
+        } else {
+            event_loop.set_control_flow(ControlFlow::Wait);
+        }
     }
 }
 
@@ -498,9 +794,11 @@ pub fn rui(view: impl View) {
     let mut app = EventHandler {
         title: window_title.into(),
         running: false,
-        context: None,
-        window: None,
-        mouse_position: LocalPoint::zero(),
+        gpu: None,
+        main: None,
+        windows: HashMap::new(),
+        closed: HashSet::new(),
+        pointer_window: None,
         cx: Context::new(),
         view,
         access_nodes: vec![],
@@ -509,6 +807,13 @@ pub fn rui(view: impl View) {
     #[cfg(not(target_arch = "wasm32"))]
     {
         *GLOBAL_EVENT_LOOP_PROXY.lock().unwrap() = Some(event_loop.create_proxy());
+
+        match arboard::Clipboard::new() {
+            Ok(clipboard) => app.cx.set_clipboard(SystemClipboard {
+                clipboard: Mutex::new(clipboard),
+            }),
+            Err(err) => log::error!("error opening system clipboard: {:?}", err),
+        }
     }
 
     let mut commands: Vec<CommandInfo> = Vec::new();